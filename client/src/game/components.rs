@@ -49,6 +49,16 @@ pub struct Game;
 #[derive(Debug, Component)]
 pub struct FinishedGame;
 
+/// Component that stores the rule that has ended the game.
+#[derive(Clone, Copy, Debug, Component, Deref)]
+pub struct Termination(core::TerminationReason);
+
+impl From<core::TerminationReason> for Termination {
+    fn from(value: core::TerminationReason) -> Self {
+        Self(value)
+    }
+}
+
 /// Component that indicates that the game is being played now.
 #[derive(Debug, Component)]
 pub struct ActiveGame;
@@ -330,16 +330,21 @@ impl StateUpdated {
 #[derive(Debug, Event)]
 pub struct Draw {
     game: Entity,
+    reason: core::TerminationReason,
 }
 
 impl Draw {
-    pub fn new(game: Entity) -> Self {
-        Self { game }
+    pub fn new(game: Entity, reason: core::TerminationReason) -> Self {
+        Self { game, reason }
     }
 
     pub fn game(&self) -> Entity {
         self.game
     }
+
+    pub fn reason(&self) -> core::TerminationReason {
+        self.reason
+    }
 }
 
 /// Event that signals that the game is finished with a win of `player`.
@@ -347,11 +352,20 @@ impl Draw {
 pub struct PlayerWon {
     game: Entity,
     player: core::PlayerPosition,
+    reason: core::TerminationReason,
 }
 
 impl PlayerWon {
-    pub fn new(game: Entity, player: core::PlayerPosition) -> Self {
-        Self { game, player }
+    pub fn new(
+        game: Entity,
+        player: core::PlayerPosition,
+        reason: core::TerminationReason,
+    ) -> Self {
+        Self {
+            game,
+            player,
+            reason,
+        }
     }
 
     pub fn game(&self) -> Entity {
@@ -361,4 +375,8 @@ impl PlayerWon {
     pub fn player(&self) -> core::PlayerPosition {
         self.player
     }
+
+    pub fn reason(&self) -> core::TerminationReason {
+        self.reason
+    }
 }
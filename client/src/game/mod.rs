@@ -26,7 +26,7 @@ use systems::*;
 
 pub use components::{
     ActiveGame, Board, BotDifficulty, CurrentUser, GameLink, NetworkGame, PendingActionQueue,
-    Winner,
+    Termination, Winner,
 };
 pub use events::{
     BotReady, Draw, GameDataReady, GameEntityReady, PlayerWon, StateUpdated, TurnStart,
@@ -72,6 +72,7 @@ impl Plugin for GamePlugin {
                     send_pending_action::<core::GridIndex>.in_set(grpc::NetworkSystems),
                     action_confirmation_failed::<core::GridIndex>,
                     handle_action_from_server::<core::GridIndex>,
+                    handle_state_from_server::<TicTacToe>,
                     close_session,
                 ),
             )
@@ -82,7 +83,8 @@ impl Plugin for GamePlugin {
                     handle_local_game_creation,
                     handle_state_updated,
                     update_current_player,
-                    handle_draw,
+                    // termination reason is stored before the game over overlay is requested
+                    handle_draw.after(set_game_finished),
                     handle_win,
                     set_game_finished,
                     update_current_user,
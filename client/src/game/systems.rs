@@ -4,7 +4,7 @@ use bevy::prelude::*;
 use game_server::{core, proto};
 use smallvec::SmallVec;
 
-use super::components::{ActionResendTimer, FinishedGame, Game, LocalGame};
+use super::components::{ActionResendTimer, FinishedGame, Game, LocalGame, Termination};
 use super::pending_action::ConfirmationStatus;
 use super::{
     ActionConfirmationFailed, ActiveGame, CurrentPlayer, CurrentUser, Draw, GameEntityReady,
@@ -324,6 +324,34 @@ pub fn handle_action_from_server<T: Copy + Send + Sync + 'static>(
     }
 }
 
+/// Receive [`grpc::SessionStateReceived`] event and set the state that has been changed
+/// on the server without a turn, e.g. by resignation, agreed draw or timeout.
+/// Send [`StateUpdated`] event if the state of the local game has changed.
+pub fn handle_state_from_server<T>(
+    mut game: Query<&mut LocalGame<T>, With<ActiveGame>>,
+    mut state_received: EventReader<grpc::SessionStateReceived>,
+    mut state_updated: EventWriter<StateUpdated>,
+) where
+    T: core::Game + Send + Sync + 'static,
+{
+    for event in state_received.read() {
+        info!(
+            "game {} player {} action: {:?}",
+            event.session_entity(),
+            event.player(),
+            event.action()
+        );
+        let Ok(mut game) = game.get_mut(event.session_entity()) else {
+            continue;
+        };
+        if game.state() == event.state() {
+            continue;
+        }
+        game.set_state(event.state());
+        state_updated.send(StateUpdated::new(event.session_entity(), event.state()));
+    }
+}
+
 /// Whenever action is confirmed or next action in the queue is changed
 /// take first consecutive confirmed actions from [`PendingActionQueue`],
 /// apply them and send [`ActionApplied`] and [`StateUpdated`] events.  
@@ -371,7 +399,7 @@ pub fn apply_confirmed<T>(
 }
 
 /// Receive [`StateUpdated`] event and send [`TurnStart`], [`PlayerWon`] or [`Draw`]
-/// depending on a new state.
+/// depending on a new state, nobody's turn starts while the game is being seeded.
 pub fn handle_state_updated(
     mut state_updated: EventReader<StateUpdated>,
     mut turn_start: EventWriter<TurnStart>,
@@ -384,11 +412,17 @@ pub fn handle_state_updated(
             core::GameState::Turn(next_player) => {
                 turn_start.send(TurnStart::new(event.game(), next_player));
             }
-            core::GameState::Finished(core::FinishedState::Win(winner)) => {
-                player_won.send(PlayerWon::new(event.game(), winner));
+            core::GameState::Waiting(players) => {
+                for player in players.iter() {
+                    turn_start.send(TurnStart::new(event.game(), player));
+                }
+            }
+            core::GameState::Seeding(_) => {}
+            core::GameState::Finished(core::FinishedState::Win(winner, reason)) => {
+                player_won.send(PlayerWon::new(event.game(), winner, reason));
             }
-            core::GameState::Finished(core::FinishedState::Draw) => {
-                draw.send(Draw::new(event.game()));
+            core::GameState::Finished(core::FinishedState::Draw(reason)) => {
+                draw.send(Draw::new(event.game(), reason));
             }
         }
     }
@@ -454,18 +488,22 @@ pub fn handle_win(
     }
 }
 
-/// Listen for [`Draw`] and [`PlayerWon`] events and insert [`FinishedGame`] component
-/// into game entity.
+/// Listen for [`Draw`] and [`PlayerWon`] events and insert [`FinishedGame`] and [`Termination`]
+/// components into game entity.
 pub fn set_game_finished(
     mut commands: Commands,
     mut draw: EventReader<Draw>,
     mut player_won: EventReader<PlayerWon>,
 ) {
     for event in draw.read() {
-        commands.entity(event.game()).insert(FinishedGame);
+        commands
+            .entity(event.game())
+            .insert((FinishedGame, Termination::from(event.reason())));
     }
     for event in player_won.read() {
-        commands.entity(event.game()).insert(FinishedGame);
+        commands
+            .entity(event.game())
+            .insert((FinishedGame, Termination::from(event.reason())));
     }
 }
 
@@ -622,7 +660,7 @@ mod test {
         // first game is finished with draw
         app.world_mut()
             .resource_mut::<Events<Draw>>()
-            .send(Draw::new(game1));
+            .send(Draw::new(game1, core::TerminationReason::Stalemate));
         app.update();
 
         // only first game has FinishedGame component
@@ -632,12 +670,26 @@ mod test {
         // second game is finished with win
         app.world_mut()
             .resource_mut::<Events<PlayerWon>>()
-            .send(PlayerWon::new(game2, 0));
+            .send(PlayerWon::new(
+                game2,
+                0,
+                core::TerminationReason::LineCompleted,
+            ));
         app.update();
 
         // both games now have FinishedGame component
         assert!(app.world().entity(game1).contains::<FinishedGame>());
         assert!(app.world().entity(game2).contains::<FinishedGame>());
+
+        // termination reasons are stored as well
+        assert_eq!(
+            **get_component::<Termination>(app.world(), game1),
+            core::TerminationReason::Stalemate
+        );
+        assert_eq!(
+            **get_component::<Termination>(app.world(), game2),
+            core::TerminationReason::LineCompleted
+        );
     }
 
     #[test]
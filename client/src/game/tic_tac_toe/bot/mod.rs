@@ -4,7 +4,7 @@ mod systems;
 
 use bevy::prelude::*;
 
-use super::{LocalGame, PlayerActionInitialized, TTTBoard};
+use super::{LocalGame, PlayerActionInitialized};
 use components::Delay;
 use systems::*;
 
@@ -3,10 +3,10 @@ use std::path::Path;
 use bevy::asset::io::file::FileAssetReader;
 use bevy::prelude::*;
 use game_server::core;
+use game_server::core::tic_tac_toe::TicTacToe;
 use smallvec::SmallVec;
 use tic_tac_toe_ai::Agent;
 
-use super::TTTBoard;
 use crate::game::BotDifficulty;
 
 pub const Q_LEARNING_AGENT_PATH: &str = "assets/agents/";
@@ -63,7 +63,7 @@ impl QLearningModel {
         res
     }
 
-    pub fn get_move(&self, difficulty: BotDifficulty, board: &TTTBoard) -> Option<core::GridIndex> {
+    pub fn get_move(&self, difficulty: BotDifficulty, game: &TicTacToe) -> Option<core::GridIndex> {
         let agent = match difficulty {
             BotDifficulty::Easy => self.easy.as_ref(),
             BotDifficulty::Medium => self.medium.as_ref(),
@@ -71,7 +71,7 @@ impl QLearningModel {
         };
         agent.and_then(|agent| {
             agent
-                .get_best_action(&board)
+                .get_best_action(game)
                 .and_then(|action| Some(action.into()))
         })
     }
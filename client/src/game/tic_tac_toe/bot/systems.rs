@@ -2,30 +2,28 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 use game_server::core;
+use game_server::core::tic_tac_toe::TicTacToe;
 use game_server::core::Game as _;
 use rand::Rng;
 
 use super::{Delay, LocalGame, PlayerActionInitialized, QLearningModel, Strategy};
 use crate::game::{
-    ActiveGame, BotAuthority, BotDifficulty, BotReady, CurrentPlayer, PlayerPosition, TTTBoard,
+    ActiveGame, BotAuthority, BotDifficulty, BotReady, CurrentPlayer, PlayerPosition,
 };
 
 const MIN_ACTION_DELAY: u64 = 500;
 const MAX_ACTION_DELAY: u64 = 1500;
 
-/// Chooses random board index from a set of available cells.
-/// In case there is no empty cells in a board returns `None`.
-fn get_random_action(board: &TTTBoard) -> Option<core::GridIndex> {
-    let empty_cells: Vec<_> = board
-        .all_indexed()
-        .filter_map(|(index, cell)| if cell.is_none() { Some(index) } else { None })
-        .collect();
-    if empty_cells.is_empty() {
+/// Chooses random action from a set of legal ones.
+/// In case there is no legal actions returns `None`.
+fn get_random_action(game: &TicTacToe) -> Option<core::GridIndex> {
+    let actions = game.legal_actions();
+    if actions.is_empty() {
         return None;
     }
     let mut rng = rand::thread_rng();
-    let index = rng.gen_range(0..empty_cells.len());
-    Some(empty_cells[index])
+    let index = rng.gen_range(0..actions.len());
+    Some(actions[index])
 }
 
 /// For every bot that has a [`Delay`] component and which turn has started
@@ -80,10 +78,10 @@ pub fn initialize_action(
             continue;
         };
         let action = match strategy {
-            Strategy::Random => get_random_action(game.board()),
+            Strategy::Random => get_random_action(game),
             Strategy::QLearning => {
                 if let Some(difficulty) = difficulty {
-                    model.get_move(*difficulty, game.board())
+                    model.get_move(*difficulty, game)
                 } else {
                     error!("unable to get bot difficulty");
                     None
@@ -17,7 +17,6 @@ use systems::*;
 const X_SPRITE_PATH: &str = "sprites/X.png";
 const O_SPRITE_PATH: &str = "sprites/O.png";
 
-type TTTBoard = <TicTacToe as core::Game>::Board;
 type Action = <TicTacToe as core::Game>::TurnData;
 
 type LocalGame = super::LocalGame<TicTacToe>;
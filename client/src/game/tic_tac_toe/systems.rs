@@ -156,9 +156,9 @@ pub fn handle_get_game(
                     for (j, cell) in row.iter().enumerate() {
                         if let core::BoardCell(Some(player)) = cell {
                             let pos = core::GridIndex::new(i, j);
-                            let local_cell = &mut local_game.board_mut()[pos];
-                            if local_cell.is_none() {
-                                *local_cell = *cell;
+                            let mut board = local_game.board_mut();
+                            if board[pos].is_none() {
+                                board[pos] = *cell;
                                 action_applied.send(PlayerActionApplied::new(
                                     event.entity(),
                                     *player,
@@ -226,7 +226,7 @@ pub fn create(
                     commands.entity(enemy_id).insert(CurrentPlayer);
                 }
             }
-            core::GameState::Finished(core::FinishedState::Win(player)) => {
+            core::GameState::Finished(core::FinishedState::Win(player, _)) => {
                 if player == user_position {
                     commands.entity(user_id).insert(Winner);
                 } else if player == enemy_position {
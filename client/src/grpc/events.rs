@@ -1,8 +1,8 @@
 use std::fmt;
 
 use bevy::prelude::*;
-use game_server::core;
 use game_server::rpc_server::RpcResult;
+use game_server::{core, proto};
 
 use super::error::GrpcError;
 use crate::util;
@@ -166,6 +166,47 @@ impl<T> SessionUpdateReceived<T> {
     }
 }
 
+/// Event that indicates that the game state was changed on the server without a turn.
+#[derive(Debug, Event)]
+pub struct SessionStateReceived {
+    session_entity: Entity,
+    player: core::PlayerPosition,
+    action: proto::GameAction,
+    state: core::GameState,
+}
+
+impl SessionStateReceived {
+    pub fn new(
+        entity: Entity,
+        player: core::PlayerPosition,
+        action: proto::GameAction,
+        state: core::GameState,
+    ) -> Self {
+        Self {
+            session_entity: entity,
+            player,
+            action,
+            state,
+        }
+    }
+
+    pub fn session_entity(&self) -> Entity {
+        self.session_entity
+    }
+
+    pub fn player(&self) -> core::PlayerPosition {
+        self.player
+    }
+
+    pub fn action(&self) -> proto::GameAction {
+        self.action
+    }
+
+    pub fn state(&self) -> core::GameState {
+        self.state
+    }
+}
+
 /// Event that indicates that an error was receiver from the server.
 #[derive(Debug, Event)]
 pub struct SessionErrorReceived {
@@ -235,7 +276,6 @@ impl fmt::Display for LogInFailed {
 }
 
 impl LogInFailed {
-
     pub fn new(error: GrpcError) -> Self {
         Self(error)
     }
@@ -19,7 +19,7 @@ pub use components::LogInRequest;
 pub use events::{
     CloseSession, Connected, Disconnected, LogInSuccess, LogOut, OpenSession, RpcResultReady,
     SessionActionReadyToSend, SessionActionSendFailed, SessionClosed, SessionErrorReceived,
-    SessionOpened, SessionUpdateReceived,
+    SessionOpened, SessionStateReceived, SessionUpdateReceived,
 };
 pub use resources::GrpcClient;
 
@@ -40,22 +40,42 @@ pub fn client_exists_and_connected(client: Option<Res<GrpcClient>>) -> bool {
 type GrpcResult<T> = Result<T, error::GrpcError>;
 
 #[derive(Debug)]
-pub struct GameSessionUpdate<T> {
-    player: core::PlayerPosition,
-    action: T,
+pub enum GameSessionUpdate<T> {
+    /// Turn made by the player.
+    Turn {
+        player: core::PlayerPosition,
+        action: T,
+    },
+    /// State changed without a turn: the player resigned, offered or answered a draw,
+    /// or ran out of time.
+    State {
+        player: core::PlayerPosition,
+        action: proto::GameAction,
+        state: core::GameState,
+    },
 }
 
 impl<T> GameSessionUpdate<T> {
     pub fn new(player: core::PlayerPosition, action: T) -> Self {
-        Self { player, action }
+        Self::Turn { player, action }
     }
 
-    pub fn player(&self) -> core::PlayerPosition {
-        self.player
+    pub fn state(
+        player: core::PlayerPosition,
+        action: proto::GameAction,
+        state: core::GameState,
+    ) -> Self {
+        Self::State {
+            player,
+            action,
+            state,
+        }
     }
 
-    pub fn action(&self) -> &T {
-        &self.action
+    pub fn player(&self) -> core::PlayerPosition {
+        match self {
+            Self::Turn { player, .. } | Self::State { player, .. } => *player,
+        }
     }
 }
 
@@ -99,6 +119,7 @@ impl Plugin for GrpcPlugin {
             .add_event::<SessionActionSendFailed<core::GridIndex>>()
             .add_event::<SessionActionReadyToSend<core::GridIndex>>()
             .add_event::<SessionUpdateReceived<core::GridIndex>>()
+            .add_event::<SessionStateReceived>()
             .add_event::<SessionErrorReceived>()
             .add_event::<AuthLinkReceived>()
             .add_event::<AuthTokenReceived>()
@@ -180,6 +180,20 @@ impl GrpcClient {
             };
             while let Some(res) = reply_stream.next().await {
                 let update_result = match res {
+                    // timeouts and player actions are sent without turn data
+                    Ok(reply) if reply.turn_data.is_empty() => {
+                        let action = reply.action();
+                        reply
+                            .game_state
+                            .ok_or(core::ProtobufError::MessageDataMissing {
+                                missing_field: "game_state".to_string(),
+                            })
+                            .and_then(core::GameState::try_from)
+                            .map(|state| {
+                                GameSessionUpdate::state(reply.player_position, action, state)
+                            })
+                            .map_err(Into::into)
+                    }
                     Ok(reply) => match T::TurnData::from_protobuf(&reply.turn_data) {
                         Ok(action) => Ok(GameSessionUpdate::new(reply.player_position, action)),
                         Err(err) => Err(err.into()),
@@ -16,10 +16,13 @@ use super::error::GrpcError;
 use super::events::{
     AuthLinkReceived, AuthTokenReceived, CloseSession, Connected, Disconnected, LogInFailed,
     LogOut, OpenSession, RpcResultReady, SessionActionReadyToSend, SessionActionSendFailed,
-    SessionClosed, SessionErrorReceived, SessionOpened, SessionUpdateReceived,
+    SessionClosed, SessionErrorReceived, SessionOpened, SessionStateReceived,
+    SessionUpdateReceived,
 };
 use super::resources::{ConnectTimer, ConnectionStatusWatcher, ServerEndpoint, SessionCheckTimer};
-use super::{AuthClient, GameClient, GameSession, GrpcClient, HealthClient, LogInSuccess};
+use super::{
+    AuthClient, GameClient, GameSession, GameSessionUpdate, GrpcClient, HealthClient, LogInSuccess,
+};
 use crate::common::PollOnce;
 use crate::game::{ActiveGame, NetworkGame};
 use crate::Settings;
@@ -383,13 +386,15 @@ pub fn init_session_update_receive_task<T>(
 }
 
 /// Poll channel receive task. If it returned with error, just print a message, otherwise
-/// send [`SessionUpdateReceived`] event in case of successful update or
-/// [`SessionErrorReceived`] event in case of session error.  
+/// send [`SessionUpdateReceived`] event in case of a turn, [`SessionStateReceived`] event
+/// in case of a state change without a turn or [`SessionErrorReceived`] event in case
+/// of session error.  
 /// `T` is a type of action.
 pub fn handle_session_update_receive<T>(
     mut commands: Commands,
     mut session: Query<(Entity, &mut ReceiveSessionUpdateTask<T>)>,
     mut update_received: EventWriter<SessionUpdateReceived<T>>,
+    mut state_received: EventWriter<SessionStateReceived>,
     mut error_received: EventWriter<SessionErrorReceived>,
 ) where
     T: Copy + Send + Sync + 'static,
@@ -397,11 +402,23 @@ pub fn handle_session_update_receive<T>(
     for (session_entity, mut task) in session.iter_mut() {
         if let Some(res) = task.poll_once(commands.entity(session_entity)) {
             match res {
-                Ok(update) => {
+                Ok(GameSessionUpdate::Turn { player, action }) => {
                     update_received.send(SessionUpdateReceived::<T>::new(
                         session_entity,
-                        update.player(),
-                        *update.action(),
+                        player,
+                        action,
+                    ));
+                }
+                Ok(GameSessionUpdate::State {
+                    player,
+                    action,
+                    state,
+                }) => {
+                    state_received.send(SessionStateReceived::new(
+                        session_entity,
+                        player,
+                        action,
+                        state,
                     ));
                 }
                 Err(err) => {
@@ -594,7 +611,6 @@ mod test {
 
     use super::*;
     use crate::grpc::error::GrpcError;
-    use crate::grpc::GameSessionUpdate;
 
     type DummySession = GameSession<DummyGame, ()>;
 
@@ -624,11 +640,19 @@ mod test {
             Ok(core::GameState::Turn(0))
         }
 
+        fn legal_actions(&self) -> Vec<Self::TurnData> {
+            vec![()]
+        }
+
+        fn undo(&mut self) -> core::GameResult<core::GameState> {
+            Err(core::GameError::NothingToUndo)
+        }
+
         fn board(&self) -> &Self::Board {
             &self.board
         }
 
-        fn board_mut(&mut self) -> &mut Self::Board {
+        fn board_mut(&mut self) -> impl std::ops::DerefMut<Target = Self::Board> {
             &mut self.board
         }
 
@@ -911,6 +935,7 @@ mod test {
         IoTaskPool::get_or_init(|| TaskPool::default());
         let mut app = App::new();
         app.add_event::<SessionUpdateReceived<()>>();
+        app.add_event::<SessionStateReceived>();
         app.add_event::<SessionErrorReceived>();
         app.add_systems(
             Update,
@@ -978,6 +1003,42 @@ mod test {
         app.update();
         assert!(app.world().entity(session).contains::<ReceiveUpdateTask>());
 
+        // send state change without a turn
+        let resigned = core::GameState::Finished(core::FinishedState::Win(
+            1,
+            core::TerminationReason::Resignation,
+        ));
+        tasks::block_on(make_send_update_task(
+            s.clone(),
+            Ok(GameSessionUpdate::state(
+                0,
+                proto::GameAction::Resign,
+                resigned,
+            )),
+        ));
+
+        // this should trigger session state event and remove receive task
+        update_while(&mut app, |w| contains_task(w, session), |_| {});
+        assert!(!app.world().entity(session).contains::<ReceiveUpdateTask>());
+        let update_events = app.world().resource::<Events<SessionUpdateReceived<()>>>();
+        assert!(update_events
+            .get_cursor()
+            .read(update_events)
+            .next()
+            .is_none());
+        let state_events = app.world().resource::<Events<SessionStateReceived>>();
+        let mut cursor = state_events.get_cursor();
+        itertools::assert_equal(
+            cursor
+                .read(state_events)
+                .map(|e| (e.session_entity(), e.player(), e.action(), e.state())),
+            std::iter::once((session, 0, proto::GameAction::Resign, resigned)),
+        );
+
+        // this should spawn receive task
+        app.update();
+        assert!(app.world().entity(session).contains::<ReceiveUpdateTask>());
+
         tasks::block_on(make_send_update_task(
             s.clone(),
             Err(GrpcError::GameSessionUpdateFailed("".into())),
@@ -38,15 +38,29 @@ pub fn update(
                                     };
                                     format!("Next: {}", user_id)
                                 }
-                                core::GameState::Finished(core::FinishedState::Win(id)) => {
+                                core::GameState::Waiting(players) => {
+                                    let user_ids = players
+                                        .iter()
+                                        .filter_map(|id| game.get_user_id(id))
+                                        .collect::<Vec<_>>();
+                                    format!("Waiting: {:?}", user_ids)
+                                }
+                                core::GameState::Seeding(players) => {
+                                    let user_ids = players
+                                        .iter()
+                                        .filter_map(|id| game.get_user_id(id))
+                                        .collect::<Vec<_>>();
+                                    format!("Seeding: {:?}", user_ids)
+                                }
+                                core::GameState::Finished(core::FinishedState::Win(id, reason)) => {
                                     let Some(user_id) = game.get_user_id(id) else {
                                         error!("unable to show game: corrupted GameInfo");
                                         continue;
                                     };
-                                    format!("Winner: {}", user_id)
+                                    format!("Winner: {} ({})", user_id, reason)
                                 }
-                                core::GameState::Finished(core::FinishedState::Draw) => {
-                                    "Draw".into()
+                                core::GameState::Finished(core::FinishedState::Draw(reason)) => {
+                                    format!("Draw ({})", reason)
                                 }
                             };
                             builder
@@ -21,7 +21,9 @@ use super::resources::RefreshGamesTimer;
 use super::{GameReady, GameReadyToExit, GameTag, JoinPressed};
 use crate::app_state::{AppState, AppStateTransition, MenuState};
 use crate::commands::CommandsExt;
-use crate::game::{ActiveGame, Board, CurrentUser, GameInfo, GameLink, GameMenuContext, Winner};
+use crate::game::{
+    ActiveGame, Board, CurrentUser, GameInfo, GameLink, GameMenuContext, Termination, Winner,
+};
 use crate::{grpc, util::watched_value};
 use crate::{Settings, UserIdChanged};
 
@@ -449,6 +451,7 @@ pub fn handle_player_games(
 pub fn create_game_over_overlay(
     mut commands: Commands,
     winner: Query<(Option<&CurrentUser>, &Parent), With<Winner>>,
+    termination: Query<&Termination>,
     mut ready_to_exit: EventReader<GameReadyToExit>,
     asset_server: Res<AssetServer>,
 ) {
@@ -458,11 +461,14 @@ pub fn create_game_over_overlay(
         let winner = winner
             .iter()
             .find(|(_, parent)| parent.get() == event.get());
-        let text = match winner.map(|val| val.0) {
-            Some(Some(_)) => "You win!",
-            Some(None) => "You lose!",
-            None => "It's a draw!",
+        let mut text = match winner.map(|val| val.0) {
+            Some(Some(_)) => "You win!".to_string(),
+            Some(None) => "You lose!".to_string(),
+            None => "It's a draw!".to_string(),
         };
+        if let Ok(termination) = termination.get(event.get()) {
+            text.push_str(&format!(" ({})", **termination));
+        }
         commands
             .spawn(OverlayNodeBundle::default())
             .with_children(|builder| {
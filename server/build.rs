@@ -17,6 +17,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 format!("{}/auth_service.proto", PROTO_FOLDER),
                 format!("{}/common.proto", PROTO_FOLDER),
                 format!("{}/chess.proto", PROTO_FOLDER),
+                format!("{}/connect_four.proto", PROTO_FOLDER),
+                format!("{}/checkers.proto", PROTO_FOLDER),
+                format!("{}/go.proto", PROTO_FOLDER),
+                format!("{}/battleship.proto", PROTO_FOLDER),
+                format!("{}/rock_paper_scissors.proto", PROTO_FOLDER),
+                format!("{}/backgammon.proto", PROTO_FOLDER),
+                format!("{}/dots_and_boxes.proto", PROTO_FOLDER),
             ],
             &[PROTO_FOLDER],
         )?;
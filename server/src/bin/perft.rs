@@ -0,0 +1,38 @@
+extern crate server;
+
+use std::time::Instant;
+
+use clap::Parser;
+
+use server::core::chess::{Chess, INITIAL_POSITION};
+
+/// Counts leaf nodes of the chess moves tree to verify move generation
+#[derive(Debug, Parser)]
+struct Args {
+    /// Depth of the moves tree
+    depth: usize,
+    /// Position in Forsyth–Edwards Notation
+    #[arg(default_value = INITIAL_POSITION)]
+    fen: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let chess = match Chess::from_fen(&args.fen) {
+        Ok(chess) => chess,
+        Err(err) => {
+            eprintln!("invalid position: {}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let start = Instant::now();
+    let mut total = 0;
+    for (data, nodes) in chess.perft_divide(args.depth) {
+        println!("{}: {}", data, nodes);
+        total += nodes;
+    }
+    println!();
+    println!("Nodes searched: {}", total);
+    println!("Time: {:?}", start.elapsed());
+}
@@ -0,0 +1,532 @@
+use std::fmt::{Display, Formatter};
+
+use generic_array::typenum;
+use prost::Message;
+
+use super::grid::GridLayout;
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    game_fields, BoardCell, CheckBounds, FromProtobuf, Game, GameError, GameResult, GameState,
+    Grid, GridIndex, History, PlayerPosition, PlayerSet, ProtobufError, ProtobufResult, Rng, Seed,
+    Snapshot, TerminationReason, ToProtobuf,
+};
+use crate::proto;
+
+type Cell = BoardCell<Stack>;
+/// Points in a single row followed by the bars and the borne off checkers of both players.
+type Board = Grid<Cell, typenum::U1, typenum::U28>;
+
+/// Checkers each player has.
+pub const CHECKERS: u32 = 15;
+const POINTS: usize = 24;
+/// Points of the home board, checkers are borne off once all of them are gathered there.
+const HOME: usize = 6;
+const DIE_SIDES: u32 = 6;
+/// Checkers of a player at the start of the game by distance from its edge of the board.
+const STARTING_POSITION: [(usize, u32); 4] = [(24, 2), (13, 5), (8, 3), (6, 5)];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stack {
+    pub owner: PlayerPosition,
+    pub count: u32,
+}
+
+impl FromProtobuf for Stack {
+    fn from_protobuf(buf: &[u8]) -> ProtobufResult<Self> {
+        let stack = proto::BackgammonStack::decode(buf)?;
+        Ok(Self {
+            owner: stack.owner,
+            count: stack.count,
+        })
+    }
+}
+
+impl ToProtobuf for Stack {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        Ok(proto::BackgammonStack {
+            owner: self.owner,
+            count: self.count,
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Move of a single checker between columns of the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Move {
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    pub fn new(from: usize, to: usize) -> Self {
+        Self { from, to }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.from, self.to)
+    }
+}
+
+/// Moves of single checkers in order, empty if none of the dice can be used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnData {
+    pub moves: Vec<Move>,
+}
+
+impl TurnData {
+    pub fn new(moves: Vec<Move>) -> Self {
+        Self { moves }
+    }
+}
+
+impl Display for TurnData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some((first, rest)) = self.moves.split_first() else {
+            return f.write_str("pass");
+        };
+        write!(f, "{}", first)?;
+        for step in rest {
+            write!(f, " {}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromProtobuf for TurnData {
+    fn from_protobuf(buf: &[u8]) -> ProtobufResult<Self> {
+        let turn = proto::BackgammonTurn::decode(buf)?;
+        let moves = turn
+            .moves
+            .into_iter()
+            .map(|step| Ok(Move::new(step.from.try_into()?, step.to.try_into()?)))
+            .collect::<Result<_, ProtobufError>>()?;
+        Ok(Self::new(moves))
+    }
+}
+
+impl CheckBounds for TurnData {
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        self.moves.iter().try_for_each(|step| {
+            cell(step.from).check_bounds(rows, cols)?;
+            cell(step.to).check_bounds(rows, cols)
+        })
+    }
+}
+
+impl ToProtobuf for TurnData {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        let moves = self
+            .moves
+            .into_iter()
+            .map(|step| {
+                Ok(proto::BackgammonMove {
+                    from: step.from.try_into()?,
+                    to: step.to.try_into()?,
+                })
+            })
+            .collect::<Result<_, ProtobufError>>()?;
+        Ok(proto::BackgammonTurn { moves }.encode_to_vec())
+    }
+}
+
+fn cell(col: usize) -> GridIndex {
+    GridIndex::new(0, col)
+}
+
+/// Column of the bar where the hit checkers of the player wait to enter the board again.
+fn bar(player: PlayerPosition) -> usize {
+    POINTS + player as usize
+}
+
+/// Column holding the checkers the player has borne off.
+fn off(player: PlayerPosition) -> usize {
+    POINTS + 2 + player as usize
+}
+
+/// Returns the column of the point at the distance from the player's edge of the board,
+/// the first player moves towards column 0 and the second one towards the last point.
+fn point(player: PlayerPosition, distance: usize) -> usize {
+    match player {
+        0 => distance - 1,
+        _ => POINTS - distance,
+    }
+}
+
+/// Returns the distance of the point from the player's edge of the board,
+/// checkers on the bar are the farthest ones.
+fn distance(player: PlayerPosition, col: usize) -> usize {
+    match player {
+        _ if col == bar(player) => POINTS + 1,
+        0 => col + 1,
+        _ => POINTS - col,
+    }
+}
+
+fn count(board: &Board, col: usize, player: PlayerPosition) -> u32 {
+    match *board[cell(col)] {
+        Some(stack) if stack.owner == player => stack.count,
+        _ => 0,
+    }
+}
+
+fn add(board: &mut Board, col: usize, player: PlayerPosition) {
+    let count = count(board, col, player) + 1;
+    board[cell(col)] = Stack {
+        owner: player,
+        count,
+    }
+    .into();
+}
+
+/// Returns the column the checker of the player on `from` reaches with the die,
+/// [`None`] if the rules don't allow the move.
+fn destination(board: &Board, player: PlayerPosition, from: usize, die: u32) -> Option<usize> {
+    if count(board, from, player) == 0 {
+        return None;
+    }
+    if from != bar(player) && count(board, bar(player), player) > 0 {
+        return None;
+    }
+    let distance = distance(player, from);
+    let die = die as usize;
+    if distance > die {
+        let to = point(player, distance - die);
+        return match *board[cell(to)] {
+            Some(stack) if stack.owner != player && stack.count > 1 => None,
+            _ => Some(to),
+        };
+    }
+    let occupied = |distance: usize| count(board, point(player, distance), player) > 0;
+    let gathered = !(HOME + 1..=POINTS).any(occupied);
+    // a higher die bears off the farthest checker only
+    let farthest = !(distance + 1..=HOME).any(occupied);
+    (gathered && (distance == die || farthest)).then_some(off(player))
+}
+
+/// Moves the checker, a lone checker of the opponent on the destination is hit and put on the bar.
+fn apply(board: &mut Board, player: PlayerPosition, step: Move) {
+    let count = count(board, step.from, player) - 1;
+    board[cell(step.from)] = match count {
+        0 => BoardCell(None),
+        _ => Stack {
+            owner: player,
+            count,
+        }
+        .into(),
+    };
+    if let Some(stack) = *board[cell(step.to)] {
+        if stack.owner != player {
+            board[cell(step.to)] = BoardCell(None);
+            add(board, bar(stack.owner), stack.owner);
+        }
+    }
+    add(board, step.to, player);
+}
+
+/// Collects every sequence of moves the dice allow along with the dice used,
+/// a sequence ends once none of the remaining dice can be used.
+fn sequences(
+    board: &Board,
+    player: PlayerPosition,
+    dice: &[u32],
+    path: &mut Vec<(Move, u32)>,
+    found: &mut Vec<Vec<(Move, u32)>>,
+) {
+    let mut moved = false;
+    for (i, &die) in dice.iter().enumerate() {
+        if dice[..i].contains(&die) {
+            continue;
+        }
+        for from in (0..POINTS).chain([bar(player)]) {
+            let Some(to) = destination(board, player, from, die) else {
+                continue;
+            };
+            moved = true;
+            let step = Move::new(from, to);
+            let mut next = board.clone();
+            apply(&mut next, player, step);
+            let mut rest = dice.to_vec();
+            rest.remove(i);
+            path.push((step, die));
+            sequences(&next, player, &rest, path, found);
+            path.pop();
+        }
+    }
+    if !moved {
+        found.push(path.clone());
+    }
+}
+
+/// Players move their checkers around the board by the dice and bear them off
+/// from their home boards, the one who bears off every checker first wins.
+/// The game starts once the seed of the dice is agreed on, until then players are waited for.
+#[derive(Clone, Debug)]
+pub struct Backgammon {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Board,
+    /// Dice rolled for the player to move, doubles are played four times.
+    dice: Vec<u32>,
+    rng: Rng,
+    history: History<Snapshot<Self, (Vec<u32>, Rng)>>,
+}
+
+impl Default for Backgammon {
+    fn default() -> Self {
+        let mut board = Board::default();
+        for player in 0..PlayerPosition::from(Self::NUM_PLAYERS) {
+            for (distance, count) in STARTING_POSITION {
+                board[cell(point(player, distance))] = Stack {
+                    owner: player,
+                    count,
+                }
+                .into();
+            }
+        }
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Seeding(PlayerSet::first(Self::NUM_PLAYERS)),
+            board,
+            dice: vec![],
+            rng: Rng::default(),
+            history: History::default(),
+        }
+    }
+}
+
+impl Backgammon {
+    fn roll(&mut self) {
+        let (first, second) = (self.rng.roll(DIE_SIDES), self.rng.roll(DIE_SIDES));
+        self.dice = if first == second {
+            vec![first; 4]
+        } else {
+            vec![first, second]
+        };
+    }
+}
+
+impl Game for Backgammon {
+    const NUM_PLAYERS: u8 = 2;
+    const CHANCE: bool = true;
+    type TurnData = TurnData;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Board;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        match self.state {
+            GameState::Seeding(_) => return Err(GameError::DiceNotRolled),
+            GameState::Finished(_) => return Err(GameError::GameIsFinished),
+            GameState::Waiting(_) => return Err(GameError::PlayerPoolCorrupted),
+            GameState::Turn(_) => {}
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        let cols = self.board.cols();
+        if let Some(step) = data
+            .moves
+            .iter()
+            .find(|step| step.from >= cols || step.to >= cols)
+        {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                step
+            )));
+        }
+        if !self.legal_actions().contains(&data) {
+            return Err(GameError::invalid_move(format!(
+                "{} can't be played with {:?}",
+                data, self.dice
+            )));
+        }
+
+        self.history
+            .push(Snapshot::take(self, (self.dice.clone(), self.rng.clone())));
+        for &step in data.moves.iter() {
+            apply(&mut self.board, id, step);
+        }
+        if count(&self.board, off(id), id) == CHECKERS {
+            self.dice.clear();
+            return Ok(self.set_winner(id, TerminationReason::BorneOff));
+        }
+        self.switch_player()?;
+        self.roll();
+        Ok(self.state)
+    }
+
+    /// Turns using as many dice as possible, if only one of them can be used it has to be
+    /// the higher one when it's possible.
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        let GameState::Turn(player) = self.state else {
+            return vec![];
+        };
+        let mut found = vec![];
+        sequences(&self.board, player, &self.dice, &mut vec![], &mut found);
+        let longest = found.iter().map(Vec::len).max().unwrap_or_default();
+        found.retain(|path| path.len() == longest);
+        let highest = self.dice.iter().max().copied().unwrap_or_default();
+        if longest == 1 && found.iter().any(|path| path[0].1 == highest) {
+            found.retain(|path| path[0].1 == highest);
+        }
+        let mut turns = found
+            .into_iter()
+            .map(|path| path.into_iter().map(|(step, _)| step).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        turns.sort();
+        turns.dedup();
+        turns.into_iter().map(TurnData::new).collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        (self.dice, self.rng) = self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+
+    /// Rolls a die for each player, the one with the higher die moves first using both dice,
+    /// equal dice are rolled again.
+    fn seed(&mut self, seed: Seed) -> GameResult<GameState> {
+        self.rng = Rng::new(seed);
+        let (first, second) = loop {
+            let (first, second) = (self.rng.roll(DIE_SIDES), self.rng.roll(DIE_SIDES));
+            if first != second {
+                break (first, second);
+            }
+        };
+        self.dice = vec![first, second];
+        self.switch_player_to(if first > second { 0 } else { 1 })
+    }
+
+    fn dice(&self) -> Vec<u32> {
+        self.dice.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{FinishedState, WithCells};
+
+    fn game_with(checkers: &[(usize, PlayerPosition, u32)], dice: &[u32]) -> Backgammon {
+        let stacks = checkers
+            .iter()
+            .map(|&(col, owner, count)| ((0, col), Stack { owner, count }))
+            .collect::<Vec<_>>();
+        let mut game = Backgammon::with_cells(&stacks);
+        game.state = GameState::Turn(0);
+        game.dice = dice.to_vec();
+        game
+    }
+
+    #[test]
+    fn test_seed() {
+        let mut game = Backgammon::new();
+        for player in 0..2 {
+            let checkers = (0..game.board.cols())
+                .map(|col| count(&game.board, col, player))
+                .sum::<u32>();
+            assert_eq!(checkers, CHECKERS);
+        }
+        assert_eq!(game.state(), GameState::Seeding(PlayerSet::first(2)));
+        assert_eq!(
+            game.update(0, TurnData::new(vec![])).unwrap_err(),
+            GameError::DiceNotRolled
+        );
+
+        let GameState::Turn(first) = game.seed([3; 32]).unwrap() else {
+            panic!("game has to start after seeding");
+        };
+        let opening = game.dice();
+        assert_eq!(opening.len(), 2);
+        assert!(opening[first as usize] > opening[(first ^ 1) as usize]);
+
+        // the same seed gives the same dice
+        let mut other = Backgammon::new();
+        other.seed([3; 32]).unwrap();
+        let turn = game.legal_actions().swap_remove(0);
+        game.update(first, turn.clone()).unwrap();
+        other.update(first, turn.clone()).unwrap();
+        let next = game.dice();
+        assert_eq!(other.dice(), next);
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(first));
+        assert_eq!(game.dice(), opening);
+        game.update(first, turn).unwrap();
+        assert_eq!(game.dice(), next);
+    }
+
+    #[test]
+    fn test_hit() {
+        // the second player's checker on column 2 is alone, the one on column 4 is blocked
+        let mut game = game_with(&[(5, 0, 1), (2, 1, 1), (4, 1, 2)], &[3, 1]);
+        let turn = TurnData::new(vec![Move::new(5, 2), Move::new(2, 1)]);
+        assert_eq!(game.legal_actions(), vec![turn.clone()]);
+        assert_eq!(
+            game.update(0, TurnData::new(vec![Move::new(5, 2)]))
+                .unwrap_err(),
+            GameError::invalid_move("5/2 can't be played with [3, 1]".to_string())
+        );
+
+        assert_eq!(game.update(0, turn).unwrap(), GameState::Turn(1));
+        assert_eq!(count(&game.board, 1, 0), 1);
+        assert_eq!(count(&game.board, bar(1), 1), 1);
+        // the hit checker has to enter the board first
+        assert!(game
+            .legal_actions()
+            .iter()
+            .all(|turn| turn.moves[0].from == bar(1)));
+    }
+
+    #[test]
+    fn test_dice_usage() {
+        // either die can be used alone, the higher one has to be
+        let game = game_with(&[(12, 0, 1), (26, 0, 14), (1, 1, 2)], &[6, 5]);
+        assert_eq!(
+            game.legal_actions(),
+            vec![TurnData::new(vec![Move::new(12, 6)])]
+        );
+
+        // the checker on the bar can't enter, so the turn is passed
+        let mut checkers = vec![(bar(0), 0, 1)];
+        checkers.extend((18..24).map(|col| (col, 1, 2)));
+        let mut game = game_with(&checkers, &[3, 4]);
+        assert_eq!(game.legal_actions(), vec![TurnData::new(vec![])]);
+        assert_eq!(
+            game.update(0, TurnData::new(vec![])).unwrap(),
+            GameState::Turn(1)
+        );
+    }
+
+    #[test]
+    fn test_bear_off() {
+        let mut game = game_with(&[(6, 0, 1), (0, 0, 1), (26, 0, 13)], &[1, 2]);
+        assert_eq!(
+            game.update(0, TurnData::new(vec![Move::new(0, 26)]))
+                .unwrap_err(),
+            GameError::invalid_move("0/26 can't be played with [1, 2]".to_string())
+        );
+
+        // a higher die bears off the farthest checker
+        let mut game = game_with(&[(3, 0, 1), (0, 0, 1), (26, 0, 13)], &[6, 1]);
+        assert!(!game
+            .legal_actions()
+            .contains(&TurnData::new(vec![Move::new(0, 26), Move::new(0, 26)])));
+        assert_eq!(
+            game.update(0, TurnData::new(vec![Move::new(3, 26), Move::new(0, 26)]))
+                .unwrap(),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::BorneOff))
+        );
+        assert!(game.dice().is_empty());
+        assert!(game.legal_actions().is_empty());
+    }
+}
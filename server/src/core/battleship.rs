@@ -0,0 +1,314 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use generic_array::typenum::{self, Unsigned};
+use prost::Message;
+
+use super::grid::GridLayout;
+use super::player_pool::PlayerIdQueue;
+use super::random::split_mix;
+use crate::core::{
+    game_fields, BoardCell, FromProtobuf, Game, GameBoard, GameError, GameResult, GameState, Grid,
+    GridIndex, History, PlayerPosition, ProtobufError, ProtobufResult, Snapshot, TerminationReason,
+    ToProtobuf,
+};
+use crate::proto;
+
+type Size = typenum::U10;
+type Cell = BoardCell<Square>;
+/// Seas of both players side by side, the first player owns the left one.
+type Board = Grid<Cell, Size, typenum::U20>;
+
+/// Lengths of the ships each player has.
+pub const FLEET: [usize; 5] = [5, 4, 3, 3, 2];
+
+/// Content of a cell, empty cells are water nobody has shot at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Square {
+    Ship,
+    Hit,
+    Miss,
+}
+
+impl FromProtobuf for Square {
+    fn from_protobuf(buf: &[u8]) -> ProtobufResult<Self> {
+        let square = match proto::BattleshipCell::decode(buf)?.square() {
+            proto::BattleshipSquare::Ship => Square::Ship,
+            proto::BattleshipSquare::Hit => Square::Hit,
+            proto::BattleshipSquare::Miss => Square::Miss,
+            proto::BattleshipSquare::Unspecified => {
+                return Err(ProtobufError::MessageDataMissing {
+                    missing_field: "square".to_string(),
+                })
+            }
+        };
+        Ok(square)
+    }
+}
+
+impl ToProtobuf for Square {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        let square = match self {
+            Square::Ship => proto::BattleshipSquare::Ship,
+            Square::Hit => proto::BattleshipSquare::Hit,
+            Square::Miss => proto::BattleshipSquare::Miss,
+        };
+        Ok(proto::BattleshipCell {
+            square: square.into(),
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Players take turns shooting at the opponent's sea, the one who hits every ship first wins.
+/// Ships of the opponent stay hidden until they are hit.
+#[derive(Clone, Debug)]
+pub struct Battleship {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Board,
+    history: History<Snapshot<Self>>,
+}
+
+impl Battleship {
+    /// Creates a game with the fleets placed by the seed, equal seeds give equal fleets.
+    pub fn from_seed(seed: u64) -> Self {
+        let size = Size::to_usize();
+        let mut random = seed;
+        let mut next_random = || {
+            let value;
+            (random, value) = split_mix(random);
+            value
+        };
+        let mut board = Board::default();
+        for player in 0..Self::NUM_PLAYERS as usize {
+            for len in FLEET {
+                loop {
+                    let vertical = next_random().is_multiple_of(2);
+                    let (rows, cols) = if vertical {
+                        (size - len + 1, size)
+                    } else {
+                        (size, size - len + 1)
+                    };
+                    let row = (next_random() % rows as u64) as usize;
+                    let col = (next_random() % cols as u64) as usize + player * size;
+                    let cells = (0..len)
+                        .map(|i| {
+                            if vertical {
+                                GridIndex::new(row + i, col)
+                            } else {
+                                GridIndex::new(row, col + i)
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    if cells.iter().all(|&pos| board[pos].is_none()) {
+                        for pos in cells {
+                            board[pos] = Square::Ship.into();
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            board,
+            history: History::default(),
+        }
+    }
+
+    /// Returns the player whose sea contains `pos`.
+    fn owner(pos: GridIndex) -> PlayerPosition {
+        (pos.col() / Size::to_usize()) as PlayerPosition
+    }
+
+    fn has_ships(&self, player: PlayerPosition) -> bool {
+        self.board
+            .all_indexed()
+            .any(|(pos, cell)| Self::owner(pos) == player && *cell == Square::Ship.into())
+    }
+}
+
+impl Game for Battleship {
+    const NUM_PLAYERS: u8 = 2;
+    const HIDDEN_INFORMATION: bool = true;
+    type TurnData = GridIndex;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Board;
+
+    /// Creates a game with randomly placed fleets.
+    fn new() -> Self {
+        Self::from_seed(RandomState::new().build_hasher().finish())
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if self.is_finished() {
+            return Err(GameError::GameIsFinished);
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        if !self.board.contains(data) {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                data
+            )));
+        }
+        if Self::owner(data) == id {
+            return Err(GameError::invalid_move(format!(
+                "{} is in your own sea",
+                data
+            )));
+        }
+        let square = match *self.board[data] {
+            None => Square::Miss,
+            Some(Square::Ship) => Square::Hit,
+            Some(Square::Hit | Square::Miss) => {
+                return Err(GameError::invalid_move(format!(
+                    "{} has already been shot at",
+                    data
+                )))
+            }
+        };
+
+        self.history.push(Snapshot::take(self, ()));
+        self.board[data] = square.into();
+        if square == Square::Hit && !self.has_ships(Self::owner(data)) {
+            return Ok(self.set_winner(id, TerminationReason::FleetSunk));
+        }
+        self.switch_player()
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        let GameState::Turn(player) = self.state else {
+            return vec![];
+        };
+        self.board
+            .all_indexed()
+            .filter(|(pos, cell)| {
+                Self::owner(*pos) != player && matches!(***cell, None | Some(Square::Ship))
+            })
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+
+    /// Returns 10 rows of 20 cells with the sea of the first player on the left.
+    /// Ships the viewer doesn't own are hidden until they are hit,
+    /// both fleets are revealed once the game is finished.
+    fn board_view(&self, viewer: Option<PlayerPosition>) -> Vec<Vec<Cell>> {
+        let mut board = self.board.clone();
+        if !self.is_finished() {
+            for (pos, cell) in self.board.all_indexed() {
+                if Some(Self::owner(pos)) != viewer && *cell == Square::Ship.into() {
+                    board[pos] = BoardCell(None);
+                }
+            }
+        }
+        board.get_content()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::FinishedState;
+
+    /// Game with a single ship of two cells in the top left corner of each sea.
+    fn game_with_boats() -> Battleship {
+        let mut game = Battleship::from_seed(0);
+        let mut board = Board::default();
+        for pos in [(0, 0), (0, 1), (0, 10), (0, 11)] {
+            board[pos.into()] = Square::Ship.into();
+        }
+        game.set_board(board);
+        game
+    }
+
+    fn ships(content: &[Vec<Cell>], player: PlayerPosition) -> usize {
+        content
+            .iter()
+            .flat_map(|row| row.iter().enumerate())
+            .filter(|(col, cell)| {
+                Battleship::owner(GridIndex::new(0, *col)) == player
+                    && **cell == Square::Ship.into()
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_fleets() {
+        let game = Battleship::from_seed(42);
+        let content = game.get_board_content();
+        let fleet_size = FLEET.iter().sum::<usize>();
+        assert_eq!(ships(&content, 0), fleet_size);
+        assert_eq!(ships(&content, 1), fleet_size);
+        assert_eq!(
+            Battleship::from_seed(42).get_board_content(),
+            game.get_board_content()
+        );
+        assert_eq!(game.legal_actions().len(), 100);
+    }
+
+    #[test]
+    fn test_shots() {
+        let mut game = game_with_boats();
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 0)).unwrap_err(),
+            GameError::invalid_move("C0,R0 is in your own sea".to_string())
+        );
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 10)).unwrap(),
+            GameState::Turn(1)
+        );
+        assert_eq!(game.board()[GridIndex::new(0, 10)], Square::Hit.into());
+        assert_eq!(
+            game.update(1, GridIndex::new(5, 5)).unwrap(),
+            GameState::Turn(0)
+        );
+        assert_eq!(game.board()[GridIndex::new(5, 5)], Square::Miss.into());
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 10)).unwrap_err(),
+            GameError::invalid_move("C10,R0 has already been shot at".to_string())
+        );
+        assert!(!game.legal_actions().contains(&GridIndex::new(0, 10)));
+
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 11)).unwrap(),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::FleetSunk))
+        );
+        assert!(game.legal_actions().is_empty());
+        assert_eq!(game.undo().unwrap(), GameState::Turn(0));
+        assert_eq!(game.board()[GridIndex::new(0, 11)], Square::Ship.into());
+    }
+
+    #[test]
+    fn test_board_view() {
+        let mut game = game_with_boats();
+        game.update(0, GridIndex::new(0, 10)).unwrap();
+
+        let view = game.board_view(Some(0));
+        assert_eq!(ships(&view, 0), 2);
+        assert_eq!(ships(&view, 1), 0);
+        assert_eq!(view[0][10], Square::Hit.into());
+        let view = game.board_view(Some(1));
+        assert_eq!(ships(&view, 0), 0);
+        assert_eq!(ships(&view, 1), 1);
+        let view = game.board_view(None);
+        assert_eq!(ships(&view, 0) + ships(&view, 1), 0);
+
+        // fleets are revealed once the game is finished
+        game.update(1, GridIndex::new(9, 9)).unwrap();
+        game.update(0, GridIndex::new(0, 11)).unwrap();
+        assert_eq!(ships(&game.board_view(None), 0), 2);
+    }
+}
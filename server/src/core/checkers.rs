@@ -0,0 +1,453 @@
+use generic_array::typenum::{self, Unsigned};
+use prost::Message;
+
+use super::grid::{GridLayout, WithGridIndex};
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    game_fields, BoardCell, CheckBounds, FromProtobuf, Game, GameError, GameResult, GameState,
+    Grid, GridIndex, History, PlayerPosition, ProtobufError, ProtobufResult, Snapshot,
+    TerminationReason, ToProtobuf,
+};
+use crate::proto;
+
+type Size = typenum::U8;
+type Cell = BoardCell<Piece>;
+type Board = Grid<Cell, Size, Size>;
+
+/// Rows each player fills with men at the start of the game.
+const STARTING_ROWS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Piece {
+    pub owner: PlayerPosition,
+    pub king: bool,
+}
+
+impl Piece {
+    pub fn man(owner: PlayerPosition) -> Self {
+        Self { owner, king: false }
+    }
+
+    pub fn king(owner: PlayerPosition) -> Self {
+        Self { owner, king: true }
+    }
+
+    /// Men move towards the opponent's side only, kings move in every direction.
+    fn directions(&self) -> &'static [Diagonal] {
+        match (self.king, self.owner) {
+            (true, _) => &Diagonal::ALL,
+            (false, 0) => &[Diagonal::TopLeft, Diagonal::TopRight],
+            (false, _) => &[Diagonal::BottomLeft, Diagonal::BottomRight],
+        }
+    }
+
+    /// Checks if the man reaches the farthest row on `pos` and becomes a king.
+    fn is_promoted_on(&self, pos: GridIndex) -> bool {
+        let last_row = match self.owner {
+            0 => 0,
+            _ => Size::to_usize() - 1,
+        };
+        !self.king && pos.row() == last_row
+    }
+}
+
+impl ToProtobuf for Piece {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        Ok(proto::CheckersPiece {
+            owner: self.owner,
+            king: self.king,
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Cells visited by the piece in order, a single step or a chain of jumps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnData {
+    pub path: Vec<GridIndex>,
+}
+
+impl TurnData {
+    pub fn new(path: Vec<GridIndex>) -> Self {
+        Self { path }
+    }
+
+    fn is_capture(&self) -> bool {
+        match self.path.as_slice() {
+            [from, to, ..] => from.row().abs_diff(to.row()) == 2,
+            _ => false,
+        }
+    }
+}
+
+impl FromProtobuf for TurnData {
+    fn from_protobuf(buf: &[u8]) -> Result<Self, ProtobufError> {
+        let turn = proto::CheckersTurn::decode(buf)?;
+        let path = turn
+            .path
+            .into_iter()
+            .map(|pos| Ok(GridIndex::new(pos.row.try_into()?, pos.col.try_into()?)))
+            .collect::<Result<_, ProtobufError>>()?;
+        Ok(Self::new(path))
+    }
+}
+
+impl CheckBounds for TurnData {
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        self.path
+            .iter()
+            .try_for_each(|pos| pos.check_bounds(rows, cols))
+    }
+}
+
+impl ToProtobuf for TurnData {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        let path = self
+            .path
+            .into_iter()
+            .map(proto::Position::try_from)
+            .collect::<Result<_, _>>()?;
+        proto::CheckersTurn { path }.to_protobuf()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Diagonal {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Diagonal {
+    const ALL: [Diagonal; 4] = [
+        Diagonal::TopLeft,
+        Diagonal::TopRight,
+        Diagonal::BottomLeft,
+        Diagonal::BottomRight,
+    ];
+
+    /// Returns the cells following `pos` in the direction together with their indices.
+    fn cells(self, board: &Board, pos: GridIndex) -> Vec<(GridIndex, Cell)> {
+        let cells: Box<dyn Iterator<Item = (GridIndex, &Cell)>> = match self {
+            Diagonal::TopLeft => Box::new(board.top_left_iter(pos).indexed()),
+            Diagonal::TopRight => Box::new(board.top_right_iter(pos).indexed()),
+            Diagonal::BottomLeft => Box::new(board.bottom_left_iter(pos).indexed()),
+            Diagonal::BottomRight => Box::new(board.bottom_right_iter(pos).indexed()),
+        };
+        cells
+            .skip(1)
+            .take(2)
+            .map(|(pos, &cell)| (pos, cell))
+            .collect()
+    }
+}
+
+/// English draughts: men move diagonally forward on the dark cells of an 8×8 board,
+/// captures are mandatory and a player without legal moves loses.
+#[derive(Clone, Debug)]
+pub struct Checkers {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Board,
+    history: History<Snapshot<Self>>,
+}
+
+impl Default for Checkers {
+    fn default() -> Self {
+        let mut board = Board::default();
+        let size = Size::to_usize();
+        for row in 0..size {
+            let owner = match row {
+                _ if row < STARTING_ROWS => 1,
+                _ if row >= size - STARTING_ROWS => 0,
+                _ => continue,
+            };
+            for col in (0..size).filter(|col| (row + col) % 2 == 1) {
+                board[GridIndex::new(row, col)] = Piece::man(owner).into();
+            }
+        }
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            board,
+            history: History::default(),
+        }
+    }
+}
+
+impl Game for Checkers {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = TurnData;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Board;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if self.is_finished() {
+            return Err(GameError::GameIsFinished);
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        let size = Size::to_usize();
+        if let Some(pos) = data
+            .path
+            .iter()
+            .find(|pos| pos.row() >= size || pos.col() >= size)
+        {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                pos
+            )));
+        }
+        let (&from, &to) = match data.path.as_slice() {
+            [from, .., to] => (from, to),
+            _ => {
+                return Err(GameError::invalid_move(
+                    "path has to contain at least two cells".to_string(),
+                ))
+            }
+        };
+        let piece = self.board[from].ok_or(GameError::cell_is_empty(from.row(), from.col()))?;
+        if piece.owner != id {
+            return Err(GameError::unauthorized_move(piece.owner, id));
+        }
+
+        let legal_actions = self.legal_actions();
+        if !legal_actions.contains(&data) {
+            let reason = if legal_actions
+                .iter()
+                .any(|turn| turn.path.starts_with(&data.path))
+            {
+                "jump sequence has to be continued".to_string()
+            } else if !data.is_capture() && legal_actions.iter().any(TurnData::is_capture) {
+                "capture is mandatory".to_string()
+            } else {
+                format!("unable to move {} to {}", from, to)
+            };
+            return Err(GameError::invalid_move(reason));
+        }
+
+        self.history.push(Snapshot::take(self, ()));
+        if data.is_capture() {
+            for step in data.path.windows(2) {
+                let (from, to) = (step[0], step[1]);
+                let jumped =
+                    GridIndex::new((from.row() + to.row()) / 2, (from.col() + to.col()) / 2);
+                self.board[jumped] = BoardCell(None);
+            }
+        }
+        self.board[from] = BoardCell(None);
+        self.board[to] = if piece.is_promoted_on(to) {
+            Piece::king(id).into()
+        } else {
+            piece.into()
+        };
+
+        self.switch_player()?;
+        if self.legal_actions().is_empty() {
+            return Ok(self.set_winner(id, TerminationReason::NoLegalMoves));
+        }
+        Ok(self.state)
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        let GameState::Turn(player) = self.state else {
+            return vec![];
+        };
+        let pieces: Vec<_> = self
+            .board
+            .all_indexed()
+            .filter_map(|(pos, cell)| {
+                cell.filter(|piece| piece.owner == player)
+                    .map(|piece| (pos, piece))
+            })
+            .collect();
+
+        let mut board = self.board.clone();
+        let mut captures = vec![];
+        for &(pos, piece) in &pieces {
+            // the piece leaves its cell, so a king may pass through it during the chain
+            board[pos] = BoardCell(None);
+            Self::collect_jumps(&mut board, piece, &mut vec![pos], &mut captures);
+            board[pos] = piece.into();
+        }
+        if !captures.is_empty() {
+            return captures;
+        }
+
+        pieces
+            .iter()
+            .flat_map(|&(pos, piece)| {
+                piece.directions().iter().filter_map(move |&diagonal| {
+                    match diagonal.cells(&self.board, pos).first() {
+                        Some(&(to, BoardCell(None))) => Some(TurnData::new(vec![pos, to])),
+                        _ => None,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+}
+
+impl Checkers {
+    /// Extends the jump chain ending in the last cell of `path` in every possible way
+    /// and collects the complete chains, a man reaching the farthest row ends the turn.
+    fn collect_jumps(
+        board: &mut Board,
+        piece: Piece,
+        path: &mut Vec<GridIndex>,
+        captures: &mut Vec<TurnData>,
+    ) {
+        let Some(&from) = path.last() else {
+            return;
+        };
+        let mut extended = false;
+        for &diagonal in piece.directions() {
+            let (jumped, to) = match diagonal.cells(board, from).as_slice() {
+                [(jumped, BoardCell(Some(enemy))), (to, BoardCell(None))]
+                    if enemy.owner != piece.owner =>
+                {
+                    (*jumped, *to)
+                }
+                _ => continue,
+            };
+            extended = true;
+            // the captured piece is removed right away, so it can't be jumped over twice
+            let captured = std::mem::take(&mut board[jumped]);
+            path.push(to);
+            if piece.is_promoted_on(to) {
+                captures.push(TurnData::new(path.clone()));
+            } else {
+                Self::collect_jumps(board, piece, path, captures);
+            }
+            path.pop();
+            board[jumped] = captured;
+        }
+        if !extended && path.len() > 1 {
+            captures.push(TurnData::new(path.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{FinishedState, WithCells};
+
+    fn turn(path: &[(usize, usize)]) -> TurnData {
+        TurnData::new(path.iter().map(|&pos| pos.into()).collect())
+    }
+
+    #[test]
+    fn test_initial_moves() {
+        let mut game = Checkers::new();
+        assert_eq!(game.legal_actions().len(), 7);
+        assert_eq!(
+            game.update(0, turn(&[(5, 0), (4, 1)])).unwrap(),
+            GameState::Turn(1)
+        );
+        assert_eq!(
+            game.update(1, turn(&[(2, 1), (1, 0)])).unwrap_err(),
+            GameError::invalid_move("unable to move C1,R2 to C0,R1".to_string())
+        );
+        assert_eq!(
+            game.update(1, turn(&[(5, 2), (4, 3)])).unwrap_err(),
+            GameError::unauthorized_move(0, 1)
+        );
+        assert_eq!(
+            game.update(1, turn(&[(2, 1), (3, 2), (4, 3)])).unwrap_err(),
+            GameError::invalid_move("unable to move C1,R2 to C3,R4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mandatory_multi_jump() {
+        let mut game = Checkers::with_cells(&[
+            ((6, 1), Piece::man(0)),
+            ((6, 5), Piece::man(0)),
+            ((5, 2), Piece::man(1)),
+            ((3, 4), Piece::man(1)),
+            ((0, 7), Piece::man(1)),
+        ]);
+        assert_eq!(game.legal_actions(), vec![turn(&[(6, 1), (4, 3), (2, 5)])]);
+        assert_eq!(
+            game.update(0, turn(&[(6, 5), (5, 6)])).unwrap_err(),
+            GameError::invalid_move("capture is mandatory".to_string())
+        );
+        assert_eq!(
+            game.update(0, turn(&[(6, 1), (4, 3)])).unwrap_err(),
+            GameError::invalid_move("jump sequence has to be continued".to_string())
+        );
+
+        game.update(0, turn(&[(6, 1), (4, 3), (2, 5)])).unwrap();
+        assert_eq!(game.board()[(5, 2).into()], BoardCell(None));
+        assert_eq!(game.board()[(3, 4).into()], BoardCell(None));
+        assert_eq!(game.board()[(2, 5).into()], BoardCell(Some(Piece::man(0))));
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(0));
+        assert_eq!(game.board()[(5, 2).into()], BoardCell(Some(Piece::man(1))));
+        assert_eq!(game.board()[(6, 1).into()], BoardCell(Some(Piece::man(0))));
+    }
+
+    #[test]
+    fn test_promotion_ends_turn() {
+        let mut game = Checkers::with_cells(&[
+            ((2, 1), Piece::man(0)),
+            ((1, 2), Piece::man(1)),
+            ((1, 4), Piece::man(1)),
+            ((7, 0), Piece::man(1)),
+        ]);
+        // a king could continue jumping over (1, 4), but the man is only crowned
+        assert_eq!(game.legal_actions(), vec![turn(&[(2, 1), (0, 3)])]);
+        game.update(0, turn(&[(2, 1), (0, 3)])).unwrap();
+        assert_eq!(game.board()[(0, 3).into()], BoardCell(Some(Piece::king(0))));
+
+        // kings move backwards as well
+        game.update(1, turn(&[(1, 4), (2, 5)])).unwrap();
+        assert!(game.legal_actions().contains(&turn(&[(0, 3), (1, 2)])));
+        assert!(game.legal_actions().contains(&turn(&[(0, 3), (1, 4)])));
+    }
+
+    #[test]
+    fn test_no_legal_moves() {
+        // the last free piece of the second player is captured, the other one is blocked
+        let mut game = Checkers::with_cells(&[
+            ((5, 4), Piece::man(0)),
+            ((7, 1), Piece::man(0)),
+            ((4, 3), Piece::man(1)),
+            ((6, 0), Piece::man(1)),
+        ]);
+        assert_eq!(
+            game.update(0, turn(&[(5, 4), (3, 2)])).unwrap(),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::NoLegalMoves))
+        );
+        assert!(game.legal_actions().is_empty());
+        assert_eq!(
+            game.update(1, turn(&[(6, 0), (7, 1)])).unwrap_err(),
+            GameError::GameIsFinished
+        );
+    }
+
+    #[test]
+    fn test_protobuf() {
+        let data = turn(&[(5, 0), (3, 2), (1, 4)]);
+        assert_eq!(
+            TurnData::from_protobuf(&data.clone().to_protobuf().unwrap()).unwrap(),
+            data
+        );
+    }
+}
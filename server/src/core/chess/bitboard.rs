@@ -0,0 +1,305 @@
+use generic_array::typenum;
+
+use super::types::{Piece, PieceKind, Team};
+use crate::core::grid::{Grid, GridIndex, GridLayout};
+use crate::core::{BoardCell, PlayerPosition};
+
+/// Set of cells of the chess board, bit `row * 8 + col` stands for the cell at `(row, col)`.
+pub type Bitboard = u64;
+
+/// Steps as (row, column) offsets: four straight lines followed by four diagonals.
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+const KNIGHT_OFFSETS: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+/// White pawns move up the board (towards row 0) and black pawns move down.
+const WHITE_PAWN_OFFSETS: [(isize, isize); 2] = [(-1, -1), (-1, 1)];
+const BLACK_PAWN_OFFSETS: [(isize, isize); 2] = [(1, -1), (1, 1)];
+
+const KNIGHT_ATTACKS: [Bitboard; 64] = leaper_attacks(&KNIGHT_OFFSETS);
+const KING_ATTACKS: [Bitboard; 64] = leaper_attacks(&DIRECTIONS);
+const PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    leaper_attacks(&WHITE_PAWN_OFFSETS),
+    leaper_attacks(&BLACK_PAWN_OFFSETS),
+];
+/// Cells from the given one to the edge of the board (exclusive) for each direction.
+const RAYS: [[Bitboard; 64]; 8] = rays();
+
+const fn offset(square: usize, (row, col): (isize, isize)) -> Option<usize> {
+    let row = (square / 8) as isize + row;
+    let col = (square % 8) as isize + col;
+    if row < 0 || row >= 8 || col < 0 || col >= 8 {
+        return None;
+    }
+    Some((row * 8 + col) as usize)
+}
+
+const fn leaper_attacks(offsets: &[(isize, isize)]) -> [Bitboard; 64] {
+    let mut attacks = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let mut i = 0;
+        while i < offsets.len() {
+            if let Some(target) = offset(square, offsets[i]) {
+                attacks[square] |= 1 << target;
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    attacks
+}
+
+const fn rays() -> [[Bitboard; 64]; 8] {
+    let mut rays = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let mut square = 0;
+        while square < 64 {
+            let mut current = square;
+            while let Some(next) = offset(current, DIRECTIONS[direction]) {
+                rays[direction][square] |= 1 << next;
+                current = next;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+    rays
+}
+
+fn square(pos: GridIndex) -> usize {
+    pos.row() * 8 + pos.col()
+}
+
+pub fn bit(pos: GridIndex) -> Bitboard {
+    1 << square(pos)
+}
+
+/// Returns positions of all cells of the set in the order of rows and columns.
+pub fn cells(mut bitboard: Bitboard) -> impl Iterator<Item = GridIndex> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let square = bitboard.trailing_zeros() as usize;
+        bitboard &= bitboard - 1;
+        Some(GridIndex::new(square / 8, square % 8))
+    })
+}
+
+fn team_index(team: Team) -> usize {
+    match team {
+        Team::White => 0,
+        Team::Black => 1,
+    }
+}
+
+fn kind_index(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::Pawn => 0,
+        PieceKind::Bishop => 1,
+        PieceKind::Knight => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    }
+}
+
+pub fn knight_attacks(pos: GridIndex) -> Bitboard {
+    KNIGHT_ATTACKS[square(pos)]
+}
+
+pub fn king_attacks(pos: GridIndex) -> Bitboard {
+    KING_ATTACKS[square(pos)]
+}
+
+/// Returns cells that pawn of the `team` attacks diagonally.
+pub fn pawn_attacks(pos: GridIndex, team: Team) -> Bitboard {
+    PAWN_ATTACKS[team_index(team)][square(pos)]
+}
+
+/// Returns cells along the directions up to and including the first occupied cell.
+fn slider_attacks(pos: GridIndex, occupied: Bitboard, directions: &[usize]) -> Bitboard {
+    let square = square(pos);
+    directions.iter().fold(0, |attacks, &direction| {
+        let ray = RAYS[direction][square];
+        let blockers = ray & occupied;
+        if blockers == 0 {
+            return attacks | ray;
+        }
+        // rays going up or left have decreasing cell indices, so the nearest blocker is the last bit
+        let (row, col) = DIRECTIONS[direction];
+        let blocker = if row * 8 + col > 0 {
+            blockers.trailing_zeros() as usize
+        } else {
+            63 - blockers.leading_zeros() as usize
+        };
+        attacks | (ray ^ RAYS[direction][blocker])
+    })
+}
+
+pub fn rook_attacks(pos: GridIndex, occupied: Bitboard) -> Bitboard {
+    slider_attacks(pos, occupied, &[0, 1, 2, 3])
+}
+
+pub fn bishop_attacks(pos: GridIndex, occupied: Bitboard) -> Bitboard {
+    slider_attacks(pos, occupied, &[4, 5, 6, 7])
+}
+
+pub fn queen_attacks(pos: GridIndex, occupied: Bitboard) -> Bitboard {
+    rook_attacks(pos, occupied) | bishop_attacks(pos, occupied)
+}
+
+/// Pieces placement of both players as a set of bitboards, one per player and piece kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bitboards {
+    pieces: [[Bitboard; 6]; 2],
+    occupied: [Bitboard; 2],
+}
+
+impl Bitboards {
+    pub fn from_grid(grid: &Grid<BoardCell<Piece>, typenum::U8, typenum::U8>) -> Self {
+        let mut bitboards = Self::default();
+        for (pos, cell) in grid.all_indexed() {
+            if let BoardCell(Some(piece)) = cell {
+                bitboards.put(pos, *piece);
+            }
+        }
+        bitboards
+    }
+
+    pub fn put(&mut self, pos: GridIndex, piece: Piece) {
+        self.pieces[piece.owner as usize][kind_index(piece.kind)] |= bit(pos);
+        self.occupied[piece.owner as usize] |= bit(pos);
+    }
+
+    pub fn remove(&mut self, pos: GridIndex, piece: Piece) {
+        self.pieces[piece.owner as usize][kind_index(piece.kind)] &= !bit(pos);
+        self.occupied[piece.owner as usize] &= !bit(pos);
+    }
+
+    pub fn occupied_by(&self, owner: PlayerPosition) -> Bitboard {
+        self.occupied[owner as usize]
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.occupied[0] | self.occupied[1]
+    }
+
+    /// Returns enemy pieces attacking the cell at `pos` of the player `id` playing for `team`.
+    pub fn attackers(&self, pos: GridIndex, id: PlayerPosition, team: Team) -> Bitboard {
+        let occupied = self.occupied();
+        let enemy = |kind| {
+            (0..self.pieces.len())
+                .filter(|&owner| owner != id as usize)
+                .fold(0, |pieces, owner| {
+                    pieces | self.pieces[owner][kind_index(kind)]
+                })
+        };
+        let queens = enemy(PieceKind::Queen);
+        (pawn_attacks(pos, team) & enemy(PieceKind::Pawn))
+            | (knight_attacks(pos) & enemy(PieceKind::Knight))
+            | (king_attacks(pos) & enemy(PieceKind::King))
+            | (bishop_attacks(pos, occupied) & (enemy(PieceKind::Bishop) | queens))
+            | (rook_attacks(pos, occupied) & (enemy(PieceKind::Rook) | queens))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(cells: &[(usize, usize)]) -> Bitboard {
+        cells.iter().fold(0, |bitboard, &(row, col)| {
+            bitboard | bit(GridIndex::new(row, col))
+        })
+    }
+
+    #[test]
+    fn test_leaper_attacks() {
+        assert_eq!(
+            knight_attacks(GridIndex::new(7, 6)),
+            set(&[(5, 5), (5, 7), (6, 4)])
+        );
+        assert_eq!(
+            king_attacks(GridIndex::new(0, 0)),
+            set(&[(0, 1), (1, 0), (1, 1)])
+        );
+        assert_eq!(
+            pawn_attacks(GridIndex::new(6, 0), Team::White),
+            set(&[(5, 1)])
+        );
+        assert_eq!(
+            pawn_attacks(GridIndex::new(1, 4), Team::Black),
+            set(&[(2, 3), (2, 5)])
+        );
+    }
+
+    #[test]
+    fn test_slider_attacks() {
+        let pos = GridIndex::new(4, 4);
+        let occupied = set(&[(2, 4), (4, 1), (6, 6), (1, 1)]);
+        assert_eq!(
+            rook_attacks(pos, occupied),
+            set(&[
+                (3, 4),
+                (2, 4),
+                (5, 4),
+                (6, 4),
+                (7, 4),
+                (4, 3),
+                (4, 2),
+                (4, 1),
+                (4, 5),
+                (4, 6),
+                (4, 7),
+            ])
+        );
+        assert_eq!(
+            bishop_attacks(pos, occupied),
+            set(&[
+                (3, 3),
+                (2, 2),
+                (1, 1),
+                (3, 5),
+                (2, 6),
+                (1, 7),
+                (5, 3),
+                (6, 2),
+                (7, 1),
+                (5, 5),
+                (6, 6),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cells() {
+        let cells: Vec<_> = super::cells(set(&[(7, 7), (0, 3), (2, 1)])).collect();
+        assert_eq!(
+            cells,
+            [
+                GridIndex::new(0, 3),
+                GridIndex::new(2, 1),
+                GridIndex::new(7, 7)
+            ]
+        );
+    }
+}
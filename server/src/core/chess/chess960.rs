@@ -0,0 +1,326 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::{Deref, DerefMut};
+
+use super::game::Chess;
+use super::turn_data::TurnData;
+use super::types::PieceKind;
+use crate::core::{Game, GameResult, GameState, PlayerPosition};
+
+/// Number of distinct Chess960 starting positions.
+pub const POSITIONS_NUMBER: u16 = 960;
+/// Number of the Chess960 starting position that is the same as the standard chess one.
+pub const STANDARD_POSITION_INDEX: u16 = 518;
+
+/// Columns of both knights among the five cells left after bishops and queen are placed.
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Returns pieces of the first row of the starting position with the given Scharnagl number.
+/// Bishops are placed on cells of different colors and the king is placed between the rooks.
+pub fn back_rank(index: u16) -> Option<[PieceKind; 8]> {
+    if index >= POSITIONS_NUMBER {
+        return None;
+    }
+    let mut n = usize::from(index);
+    let mut back_rank = [None; 8];
+    back_rank[(n % 4) * 2 + 1] = Some(PieceKind::Bishop);
+    n /= 4;
+    back_rank[(n % 4) * 2] = Some(PieceKind::Bishop);
+    n /= 4;
+    let empty = |back_rank: &[Option<PieceKind>; 8]| {
+        (0..8)
+            .filter(|&col| back_rank[col].is_none())
+            .collect::<Vec<_>>()
+    };
+    back_rank[empty(&back_rank)[n % 6]] = Some(PieceKind::Queen);
+    n /= 6;
+    let (first, second) = KNIGHT_PLACEMENTS[n];
+    let cols = empty(&back_rank);
+    back_rank[cols[first]] = Some(PieceKind::Knight);
+    back_rank[cols[second]] = Some(PieceKind::Knight);
+    // the last three cells are taken by the king surrounded with the rooks
+    let cols = empty(&back_rank);
+    for (col, kind) in cols
+        .into_iter()
+        .zip([PieceKind::Rook, PieceKind::King, PieceKind::Rook])
+    {
+        back_rank[col] = Some(kind);
+    }
+    Some(back_rank.map(|kind| kind.unwrap_or(PieceKind::Pawn)))
+}
+
+/// Fischer Random chess: the pieces of the first row are shuffled
+/// and castling moves the king to the cell of the rook.
+#[derive(Clone, Debug)]
+pub struct Chess960 {
+    chess: Chess,
+    index: u16,
+}
+
+impl Chess960 {
+    /// Creates a game with the starting position of the given number, [`None`] if it's 960 or more.
+    pub fn from_index(index: u16) -> Option<Self> {
+        let chess = Chess::from_back_rank(back_rank(index)?, true);
+        Some(Self { chess, index })
+    }
+
+    /// Creates a game with the starting position picked by the seed, equal seeds give equal positions.
+    pub fn from_seed(seed: u64) -> Self {
+        let index = (seed % u64::from(POSITIONS_NUMBER)) as u16;
+        Self::from_index(index).expect("index is less than the number of positions")
+    }
+
+    /// Returns number of the starting position.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+}
+
+impl Deref for Chess960 {
+    type Target = Chess;
+
+    fn deref(&self) -> &Self::Target {
+        &self.chess
+    }
+}
+
+impl Game for Chess960 {
+    const NUM_PLAYERS: u8 = Chess::NUM_PLAYERS;
+    type TurnData = TurnData;
+    type Players = <Chess as Game>::Players;
+    type Board = <Chess as Game>::Board;
+
+    /// Creates a game with a random starting position.
+    fn new() -> Self {
+        Self::from_seed(RandomState::new().build_hasher().finish())
+    }
+
+    /// Starting positions are numbered by Scharnagl, a random one is picked for [`None`].
+    fn from_start_position(position: Option<u32>) -> Option<Self> {
+        match position {
+            Some(index) => Self::from_index(index.try_into().ok()?),
+            None => Some(Self::new()),
+        }
+    }
+
+    fn start_position(&self) -> Option<u32> {
+        Some(self.index.into())
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        self.chess.update(id, data)
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        self.chess.legal_actions()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.chess.undo()
+    }
+
+    fn board(&self) -> &Self::Board {
+        self.chess.board()
+    }
+
+    fn board_mut(&mut self) -> impl DerefMut<Target = Self::Board> {
+        self.chess.board_mut()
+    }
+
+    fn set_board(&mut self, board: Self::Board) {
+        self.chess.set_board(board)
+    }
+
+    fn players(&self) -> &Self::Players {
+        self.chess.players()
+    }
+
+    fn players_mut(&mut self) -> &mut Self::Players {
+        self.chess.players_mut()
+    }
+
+    fn state(&self) -> GameState {
+        self.chess.state()
+    }
+
+    fn set_state(&mut self, state: GameState) {
+        self.chess.set_state(state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::core::chess::fen;
+    use crate::core::chess::types::{Piece, Team};
+    use crate::core::{BoardCell, GridIndex};
+
+    const FIRST_PLAYER: PlayerPosition = 0;
+    const SECOND_PLAYER: PlayerPosition = 1;
+
+    #[test]
+    fn test_back_rank() {
+        assert_eq!(
+            back_rank(STANDARD_POSITION_INDEX).unwrap(),
+            [
+                PieceKind::Rook,
+                PieceKind::Knight,
+                PieceKind::Bishop,
+                PieceKind::Queen,
+                PieceKind::King,
+                PieceKind::Bishop,
+                PieceKind::Knight,
+                PieceKind::Rook,
+            ]
+        );
+        assert_eq!(back_rank(POSITIONS_NUMBER), None);
+
+        let positions: Vec<_> = (0..POSITIONS_NUMBER)
+            .map(|index| back_rank(index).unwrap())
+            .collect();
+        let unique: HashSet<_> = positions
+            .iter()
+            .map(|position| position.map(|kind| fen::piece_to_char(kind, Team::White)))
+            .collect();
+        assert_eq!(unique.len(), usize::from(POSITIONS_NUMBER));
+        for position in positions {
+            let cols = |kind| (0..8).filter(move |&col| position[col] == kind);
+            let bishops: Vec<_> = cols(PieceKind::Bishop).collect();
+            let rooks: Vec<_> = cols(PieceKind::Rook).collect();
+            let king = cols(PieceKind::King).next().unwrap();
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            assert!(rooks[0] < king && king < rooks[1]);
+            assert_eq!(cols(PieceKind::Knight).count(), 2);
+            assert_eq!(cols(PieceKind::Queen).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_creation() {
+        let chess = Chess960::from_index(0).unwrap();
+        assert_eq!(chess.index(), 0);
+        assert_eq!(
+            chess.to_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1"
+        );
+        assert_eq!(
+            Chess960::from_seed(960 + 518).index(),
+            STANDARD_POSITION_INDEX
+        );
+        assert!(Chess960::from_index(POSITIONS_NUMBER).is_none());
+        assert!(Chess960::new().index() < POSITIONS_NUMBER);
+    }
+
+    #[test]
+    fn test_start_position() {
+        let chess = Chess960::from_start_position(Some(518)).unwrap();
+        assert_eq!(chess.start_position(), Some(518));
+        assert_eq!(chess.index(), STANDARD_POSITION_INDEX);
+        assert!(Chess960::from_start_position(Some(960)).is_none());
+        assert!(Chess960::from_start_position(Some(u32::MAX)).is_none());
+        let random = Chess960::from_start_position(None).unwrap();
+        assert!(random.start_position().unwrap() < 960);
+        assert!(Chess::from_start_position(Some(0)).is_none());
+        assert_eq!(Chess::new().start_position(), None);
+    }
+
+    #[test]
+    fn test_castling() {
+        // king on b1 and the rooks on a1 and h1 with nothing in between
+        let mut chess =
+            Chess::from_fen("rk5r/pppppppp/8/8/8/8/PPPPPPPP/RK5R w AHah - 0 1").unwrap();
+        let b1 = GridIndex::new(7, 1);
+        let c1 = GridIndex::new(7, 2);
+        let h1 = GridIndex::new(7, 7);
+        // moving next to the king is a regular move, castling is made by moving to the rook
+        let moves = chess.get_moves(b1).unwrap();
+        assert!(moves.contains(&c1));
+        assert!(moves.contains(&GridIndex::new(7, 0)));
+        assert!(moves.contains(&h1));
+
+        chess.update(FIRST_PLAYER, TurnData::new(b1, h1)).unwrap();
+        let rank = |row| {
+            (0..8)
+                .map(|col| *chess.board()[GridIndex::new(row, col)])
+                .collect::<Vec<_>>()
+        };
+        let white = |kind| {
+            Some(Piece {
+                kind,
+                owner: FIRST_PLAYER,
+            })
+        };
+        assert_eq!(
+            rank(7),
+            [
+                white(PieceKind::Rook),
+                None,
+                None,
+                None,
+                None,
+                white(PieceKind::Rook),
+                white(PieceKind::King),
+                None,
+            ]
+        );
+        assert_eq!(
+            chess.to_fen(),
+            "rk5r/pppppppp/8/8/8/8/PPPPPPPP/R4RK1 b ha - 1 1"
+        );
+
+        // king on b8 castles to the left with the rook on a8: they swap their cells
+        let b8 = GridIndex::new(0, 1);
+        let a8 = GridIndex::new(0, 0);
+        chess.update(SECOND_PLAYER, TurnData::new(b8, a8)).unwrap();
+        assert_eq!(
+            chess.board()[GridIndex::new(0, 2)],
+            BoardCell(Some(Piece::create_king(SECOND_PLAYER)))
+        );
+        assert_eq!(
+            chess.board()[GridIndex::new(0, 3)],
+            BoardCell(Some(Piece::create_rook(SECOND_PLAYER)))
+        );
+        assert_eq!(chess.board()[a8], BoardCell(None));
+        assert_eq!(chess.board()[b8], BoardCell(None));
+    }
+
+    #[test]
+    fn test_castling_rook_shields_king() {
+        // after castling to the left the king on c1 would be attacked by the queen on a1
+        let chess = Chess::from_fen("4k3/8/8/8/8/8/8/qR2K3 w B - 0 1").unwrap();
+        let e1 = GridIndex::new(7, 4);
+        let b1 = GridIndex::new(7, 1);
+        assert!(!chess.get_moves(e1).unwrap().contains(&b1));
+        // without the queen castling is allowed
+        let chess = Chess::from_fen("4k3/8/8/8/8/8/8/1R2K3 w B - 0 1").unwrap();
+        assert!(chess.get_moves(e1).unwrap().contains(&b1));
+    }
+
+    #[test]
+    fn test_lobby_game() {
+        let mut chess = Chess960::from_index(STANDARD_POSITION_INDEX).unwrap();
+        let board = chess.board().clone();
+        let e2 = GridIndex::new(6, 4);
+        let e4 = GridIndex::new(4, 4);
+        assert_eq!(
+            chess.update(FIRST_PLAYER, TurnData::new(e2, e4)).unwrap(),
+            GameState::Turn(SECOND_PLAYER)
+        );
+        assert_eq!(chess.board()[e2], BoardCell(None));
+        chess.set_board(board);
+        assert!(chess.board()[e2].is_some());
+    }
+}
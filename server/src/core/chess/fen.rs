@@ -0,0 +1,102 @@
+use super::types::{PieceKind, Team};
+use crate::core::GridIndex;
+
+/// Position of the standard chess game start.
+pub const INITIAL_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FenError {
+    #[error("invalid number of fields (expected: 4 or 6, found: {0})")]
+    InvalidFieldsNumber(usize),
+    #[error("invalid piece placement: {0}")]
+    InvalidPiecePlacement(String),
+    #[error("invalid active color: {0}")]
+    InvalidActiveColor(String),
+    #[error("invalid castling availability: {0}")]
+    InvalidCastling(String),
+    #[error("invalid en passant target square: {0}")]
+    InvalidEnPassant(String),
+    #[error("invalid move counter: {0}")]
+    InvalidMoveCounter(String),
+}
+
+impl FenError {
+    pub fn invalid_piece_placement(reason: impl Into<String>) -> Self {
+        Self::InvalidPiecePlacement(reason.into())
+    }
+}
+
+/// Returns FEN letter of the piece: uppercase for white and lowercase for black.
+pub fn piece_to_char(kind: PieceKind, team: Team) -> char {
+    let c = match kind {
+        PieceKind::Pawn => 'p',
+        PieceKind::Bishop => 'b',
+        PieceKind::Knight => 'n',
+        PieceKind::Rook => 'r',
+        PieceKind::Queen => 'q',
+        PieceKind::King => 'k',
+    };
+    match team {
+        Team::White => c.to_ascii_uppercase(),
+        Team::Black => c,
+    }
+}
+
+pub fn piece_from_char(c: char) -> Option<(PieceKind, Team)> {
+    let kind = match c.to_ascii_lowercase() {
+        'p' => PieceKind::Pawn,
+        'b' => PieceKind::Bishop,
+        'n' => PieceKind::Knight,
+        'r' => PieceKind::Rook,
+        'q' => PieceKind::Queen,
+        'k' => PieceKind::King,
+        _ => return None,
+    };
+    let team = if c.is_ascii_uppercase() {
+        Team::White
+    } else {
+        Team::Black
+    };
+    Some((kind, team))
+}
+
+/// Returns algebraic name of the cell, e.g. row 7 column 4 is "e1".
+pub fn square_name(pos: GridIndex) -> String {
+    format!("{}{}", (b'a' + pos.col() as u8) as char, 8 - pos.row())
+}
+
+/// Parses algebraic name of the cell, e.g. "e1" is row 7 column 4.
+pub fn parse_square(name: &str) -> Option<GridIndex> {
+    match name.as_bytes() {
+        &[file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Some(GridIndex::new(
+            usize::from(b'8' - rank),
+            usize::from(file - b'a'),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_piece_chars() {
+        assert_eq!(piece_to_char(PieceKind::Knight, Team::White), 'N');
+        assert_eq!(piece_to_char(PieceKind::Queen, Team::Black), 'q');
+        assert_eq!(piece_from_char('K'), Some((PieceKind::King, Team::White)));
+        assert_eq!(piece_from_char('p'), Some((PieceKind::Pawn, Team::Black)));
+        assert_eq!(piece_from_char('x'), None);
+    }
+
+    #[test]
+    fn test_squares() {
+        assert_eq!(square_name(GridIndex::new(7, 4)), "e1");
+        assert_eq!(square_name(GridIndex::new(0, 0)), "a8");
+        assert_eq!(parse_square("h1"), Some(GridIndex::new(7, 7)));
+        assert_eq!(parse_square("c6"), Some(GridIndex::new(2, 2)));
+        assert_eq!(parse_square("i1"), None);
+        assert_eq!(parse_square("a9"), None);
+        assert_eq!(parse_square("a"), None);
+    }
+}
@@ -1,17 +1,25 @@
-use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 
 use generic_array::typenum;
 
-use super::iterator::{while_empty, GridExt};
+use super::bitboard::{self, Bitboards};
+use super::fen::{self, FenError};
 use super::turn_data::TurnData;
 use super::types::{MoveType, Piece, PieceKind, Team};
-use crate::core::grid::{Grid, GridIndex};
+use super::zobrist;
+use crate::core::grid::{Grid, GridIndex, GridLayout};
 use crate::core::player_pool::{Player, PlayerDataQueue, PlayerQueue};
-use crate::core::{BoardCell, Game, GameError, GameResult, GameState, PlayerPosition};
+use crate::core::{
+    BoardCell, FinishedState, Game, GameError, GameResult, GameState, History, PlayerPosition,
+    TerminationReason,
+};
 
 type Cell = BoardCell<Piece>;
 
+/// Number of turns without captures and pawn moves (fifty for each player) that ends the game in a draw.
+const FIFTY_MOVE_RULE_TURNS: usize = 100;
+
 #[derive(Clone, Copy, Debug)]
 pub struct PlayerData {
     id: PlayerPosition,
@@ -32,38 +40,43 @@ impl Player for PlayerData {
     }
 }
 
+/// Pieces of the first row of the standard chess from left to right.
+const STANDARD_BACK_RANK: [PieceKind; 8] = [
+    PieceKind::Rook,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Queen,
+    PieceKind::King,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+    PieceKind::Rook,
+];
+
+/// Columns the king and the rook are moved to by castling to the left and to the right.
+const LEFT_CASTLING_COLUMNS: (usize, usize) = (2, 3);
+const RIGHT_CASTLING_COLUMNS: (usize, usize) = (6, 5);
+
+/// Creates board with pawns and the given pieces of the first row placed symmetrically for both players.
 fn initial_board(
     player1: PlayerPosition,
     player2: PlayerPosition,
+    back_rank: [PieceKind; 8],
 ) -> Grid<Cell, typenum::U8, typenum::U8> {
     let mut board = Grid::<Cell, _, _>::default();
-    // init pawns
-    for i in 0..8 {
-        *board[GridIndex::new(6, i)] = Piece::create_pawn(player1).into();
-        *board[GridIndex::new(1, i)] = Piece::create_pawn(player2).into();
-    }
-    // init rooks
-    *board[GridIndex::new(7, 0)] = Piece::create_rook(player1).into();
-    *board[GridIndex::new(7, 7)] = Piece::create_rook(player1).into();
-    *board[GridIndex::new(0, 0)] = Piece::create_rook(player2).into();
-    *board[GridIndex::new(0, 7)] = Piece::create_rook(player2).into();
-    // init knights
-    *board[GridIndex::new(7, 1)] = Piece::create_knight(player1).into();
-    *board[GridIndex::new(7, 6)] = Piece::create_knight(player1).into();
-    *board[GridIndex::new(0, 1)] = Piece::create_knight(player2).into();
-    *board[GridIndex::new(0, 6)] = Piece::create_knight(player2).into();
-    // init bishops
-    *board[GridIndex::new(7, 2)] = Piece::create_bishop(player1).into();
-    *board[GridIndex::new(7, 5)] = Piece::create_bishop(player1).into();
-    *board[GridIndex::new(0, 2)] = Piece::create_bishop(player2).into();
-    *board[GridIndex::new(0, 5)] = Piece::create_bishop(player2).into();
-    // init queens
-    *board[GridIndex::new(7, 3)] = Piece::create_queen(player1).into();
-    *board[GridIndex::new(0, 3)] = Piece::create_queen(player2).into();
-    // init kings
-    *board[GridIndex::new(7, 4)] = Piece::create_king(player1).into();
-    *board[GridIndex::new(0, 4)] = Piece::create_king(player2).into();
-
+    for (col, kind) in back_rank.into_iter().enumerate() {
+        *board[GridIndex::new(6, col)] = Piece::create_pawn(player1).into();
+        *board[GridIndex::new(1, col)] = Piece::create_pawn(player2).into();
+        *board[GridIndex::new(7, col)] = Piece {
+            kind,
+            owner: player1,
+        }
+        .into();
+        *board[GridIndex::new(0, col)] = Piece {
+            kind,
+            owner: player2,
+        }
+        .into();
+    }
     board
 }
 
@@ -95,17 +108,22 @@ impl Default for CastleOptions {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 struct AdditionalState {
     castle_options: CastleOptions,
     check: Vec<GridIndex>,
     king_pos: GridIndex,
+    /// Initial cells of the rooks that are used for castling.
+    left_rook_pos: GridIndex,
+    right_rook_pos: GridIndex,
 }
 
 impl AdditionalState {
-    pub fn new(king_pos: GridIndex) -> Self {
+    pub fn new(king_pos: GridIndex, left_rook_pos: GridIndex, right_rook_pos: GridIndex) -> Self {
         Self {
             king_pos,
+            left_rook_pos,
+            right_rook_pos,
             ..Default::default()
         }
     }
@@ -116,7 +134,38 @@ pub struct Chess {
     players: PlayerDataQueue<PlayerData, PlayerPosition>,
     state: GameState,
     board: Grid<Cell, typenum::U8, typenum::U8>,
+    /// Pieces placement that mirrors `board` and is used for move generation.
+    bitboards: Bitboards,
     player_state: HashMap<PlayerPosition, AdditionalState>,
+    /// Cell that pawn has passed over with its double move during the last turn.
+    en_passant: Option<GridIndex>,
+    /// Number of turns since the last capture or pawn move.
+    halfmove_clock: usize,
+    /// Keys of positions that have occurred since the last capture or pawn move.
+    position_history: Vec<u64>,
+    /// Number of the full move, starts at 1 and is incremented after black's move.
+    fullmove_number: usize,
+    /// Chess960 castling rules are used: the king castles by moving to the cell of its rook.
+    chess960: bool,
+    /// Draws by repetition, fifty-move rule and insufficient material end the game,
+    /// they are off while counting perft.
+    draw_adjudication: bool,
+    /// Snapshots taken before each turn, the last one is restored by undo.
+    history: History<Snapshot>,
+}
+
+/// Part of the game that is changed by a turn, kept to take the turn back.
+#[derive(Clone, Debug)]
+struct Snapshot {
+    players: PlayerDataQueue<PlayerData, PlayerPosition>,
+    state: GameState,
+    board: Grid<Cell, typenum::U8, typenum::U8>,
+    bitboards: Bitboards,
+    player_state: HashMap<PlayerPosition, AdditionalState>,
+    en_passant: Option<GridIndex>,
+    halfmove_clock: usize,
+    position_history: Vec<u64>,
+    fullmove_number: usize,
 }
 
 impl Game for Chess {
@@ -126,30 +175,7 @@ impl Game for Chess {
     type Board = Grid<Cell, typenum::U8, typenum::U8>;
 
     fn new() -> Self {
-        let [id1, id2]: [_; 2] = (0..Self::NUM_PLAYERS)
-            .map(|id| id.into())
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
-        let p1 = PlayerData::new(id1, Team::White);
-        let p2 = PlayerData::new(id2, Team::Black);
-        Self {
-            players: Self::Players::new([p1, p2].to_vec()),
-            state: GameState::Turn(0),
-            board: initial_board(id1, id2),
-            player_state: [
-                (
-                    p1.id,
-                    AdditionalState::new(p1.team.get_king_initial_position()),
-                ),
-                (
-                    p2.id,
-                    AdditionalState::new(p2.team.get_king_initial_position()),
-                ),
-            ]
-            .into_iter()
-            .collect(),
-        }
+        Self::from_back_rank(STANDARD_BACK_RANK, false)
     }
 
     fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
@@ -160,6 +186,15 @@ impl Game for Chess {
         if id != player.id {
             return Err(GameError::not_your_turn(self.get_current_player()?.id, id));
         }
+        if let Some(pos) = [data.from, data.to]
+            .into_iter()
+            .find(|&pos| !self.board.contains(pos))
+        {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                pos
+            )));
+        }
         let piece = self.board[data.from]
             .ok_or(GameError::cell_is_empty(data.from.row(), data.from.col()))?;
 
@@ -174,45 +209,60 @@ impl Game for Chess {
             )));
         }
 
-        match self.get_move_type(data) {
-            MoveType::LeftCastling => {
-                self.move_piece(
-                    player.team.get_left_rook_initial_position(),
-                    data.to.move_right(1),
-                )?;
-                self.disable_castling(id);
+        let move_type = self.get_move_type(data);
+        match (&move_type, data.promotion) {
+            (MoveType::Promotion, None) => {
+                return Err(GameError::invalid_move(format!(
+                    "promotion piece is required to move {} to {}",
+                    data.from, data.to
+                )));
             }
-            MoveType::RightCastling => {
-                self.move_piece(
-                    player.team.get_right_rook_initial_position(),
-                    data.to.move_left(1),
-                )?;
-                self.disable_castling(id);
+            (MoveType::Promotion, Some(kind)) if !kind.is_promotion_target() => {
+                return Err(GameError::invalid_move(format!(
+                    "unable to promote pawn to {:?}",
+                    kind
+                )));
             }
-            MoveType::KingMove => {
-                // castling is disabled inside of update_king_position
-                self.update_king_position(id, data.from);
-            }
-            MoveType::RookMove => {
-                if data.from == player.team.get_left_rook_initial_position() {
-                    self.disable_left_castling(id);
-                } else if data.from == player.team.get_right_rook_initial_position() {
-                    self.disable_right_castling(id);
-                }
+            (MoveType::Promotion, Some(_)) => {}
+            (_, Some(_)) => {
+                return Err(GameError::invalid_move(format!(
+                    "unable to promote piece moving {} to {}",
+                    data.from, data.to
+                )));
             }
-            MoveType::Other => {}
+            (_, None) => {}
         };
-        self.move_piece(data.from, data.to)?;
 
-        self.update_state()
+        let snapshot = self.snapshot();
+        match self.make_move(player, piece, data, move_type) {
+            Ok(state) => {
+                self.history.push(snapshot);
+                Ok(state)
+            }
+            Err(err) => {
+                // the turn is made in several steps, the ones done before the failure are reverted
+                self.restore(snapshot);
+                Err(err)
+            }
+        }
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        self.legal_moves()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        let snapshot = self.history.pop()?;
+        self.restore(snapshot);
+        Ok(self.state)
     }
 
     fn board(&self) -> &Self::Board {
         &self.board
     }
 
-    fn board_mut(&mut self) -> &mut Self::Board {
-        &mut self.board
+    fn board_mut(&mut self) -> impl DerefMut<Target = Self::Board> {
+        BoardGuard { chess: self }
     }
 
     fn players(&self) -> &Self::Players {
@@ -233,10 +283,541 @@ impl Game for Chess {
 
     fn set_board(&mut self, board: Self::Board) {
         self.board = board;
+        self.sync_with_board();
+    }
+}
+
+/// Mutable access to the board of [`Chess`], bitboards and king positions
+/// are brought in line with the board once it's dropped.
+struct BoardGuard<'a> {
+    chess: &'a mut Chess,
+}
+
+impl Deref for BoardGuard<'_> {
+    type Target = Grid<Cell, typenum::U8, typenum::U8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.chess.board
+    }
+}
+
+impl DerefMut for BoardGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.chess.board
+    }
+}
+
+impl Drop for BoardGuard<'_> {
+    fn drop(&mut self) {
+        self.chess.sync_with_board();
     }
 }
 
 impl Chess {
+    /// Creates a game that starts with the given pieces on the first row,
+    /// `chess960` enables castling of the king from any column with a rook on either side of it.
+    pub(super) fn from_back_rank(back_rank: [PieceKind; 8], chess960: bool) -> Self {
+        let [id1, id2]: [_; 2] = (0..Self::NUM_PLAYERS)
+            .map(|id| id.into())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let p1 = PlayerData::new(id1, Team::White);
+        let p2 = PlayerData::new(id2, Team::Black);
+        let board = initial_board(id1, id2, back_rank);
+        let col_of = |kind| back_rank.iter().position(|&piece| piece == kind);
+        let king_col = col_of(PieceKind::King).unwrap_or(4);
+        let left_rook_col = col_of(PieceKind::Rook).unwrap_or(0);
+        let right_rook_col = back_rank
+            .iter()
+            .rposition(|&piece| piece == PieceKind::Rook)
+            .unwrap_or(7);
+        let state = |team: Team| {
+            let row = team.get_king_initial_position().row();
+            AdditionalState::new(
+                GridIndex::new(row, king_col),
+                GridIndex::new(row, left_rook_col),
+                GridIndex::new(row, right_rook_col),
+            )
+        };
+        let mut chess = Self {
+            players: <Self as Game>::Players::new([p1, p2].to_vec()),
+            state: GameState::Turn(0),
+            bitboards: Bitboards::from_grid(&board),
+            board,
+            player_state: [(p1.id, state(p1.team)), (p2.id, state(p2.team))]
+                .into_iter()
+                .collect(),
+            en_passant: None,
+            halfmove_clock: 0,
+            position_history: vec![],
+            fullmove_number: 1,
+            chess960,
+            draw_adjudication: true,
+            history: History::default(),
+        };
+        chess.position_history.push(chess.position_key());
+        chess
+    }
+
+    /// Creates a game from the position in Forsyth–Edwards Notation.
+    /// Move counters are optional and default to "0 1".
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<_> = fen.split_whitespace().collect();
+        let (placement, active_color, castling, en_passant, counters) = match fields.as_slice() {
+            [placement, active_color, castling, en_passant, counters @ ..] => {
+                (*placement, *active_color, *castling, *en_passant, counters)
+            }
+            _ => return Err(FenError::InvalidFieldsNumber(fields.len())),
+        };
+        let parse_counter = |counter: &str| {
+            counter
+                .parse::<usize>()
+                .map_err(|_| FenError::InvalidMoveCounter(counter.to_string()))
+        };
+        let (halfmove_clock, fullmove_number) = match counters {
+            [] => (0, 1),
+            [halfmove, fullmove] => (parse_counter(halfmove)?, parse_counter(fullmove)?),
+            _ => return Err(FenError::InvalidFieldsNumber(fields.len())),
+        };
+        if fullmove_number == 0 {
+            return Err(FenError::InvalidMoveCounter(fullmove_number.to_string()));
+        }
+
+        let mut chess = Self::new();
+        let mut board = Grid::default();
+        let ranks: Vec<_> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::invalid_piece_placement(format!(
+                "expected 8 ranks, found {}",
+                ranks.len()
+            )));
+        }
+        for (row, rank) in ranks.into_iter().enumerate() {
+            let mut col = 0;
+            for c in rank.chars() {
+                if let Some(empty) = c.to_digit(10).filter(|n| (1..=8).contains(n)) {
+                    col += empty as usize;
+                    continue;
+                }
+                let (kind, team) = fen::piece_from_char(c).ok_or_else(|| {
+                    FenError::invalid_piece_placement(format!("unknown piece {}", c))
+                })?;
+                if col >= 8 {
+                    return Err(FenError::invalid_piece_placement(format!(
+                        "rank {} has more than 8 cells",
+                        8 - row
+                    )));
+                }
+                if kind == PieceKind::Pawn && (row == 0 || row == 7) {
+                    return Err(FenError::invalid_piece_placement(format!(
+                        "pawn on rank {}",
+                        8 - row
+                    )));
+                }
+                let owner = chess.get_player_by_team(team).id;
+                board[GridIndex::new(row, col)] = Piece { kind, owner }.into();
+                col += 1;
+            }
+            if col != 8 {
+                return Err(FenError::invalid_piece_placement(format!(
+                    "rank {} doesn't have 8 cells",
+                    8 - row
+                )));
+            }
+        }
+        chess.set_board(board);
+        for player in chess.players.as_slice().to_vec() {
+            let kings: Vec<_> = chess
+                .find_pieces_positions(player.id)
+                .into_iter()
+                .filter(|&pos| chess.board[pos].is_some_and(|piece| piece.is_king()))
+                .collect();
+            let [king_pos] = kings.as_slice() else {
+                return Err(FenError::invalid_piece_placement(format!(
+                    "{:?} must have exactly one king",
+                    player.team
+                )));
+            };
+            chess.update_king_position(player.id, *king_pos);
+        }
+
+        let active_team = match active_color {
+            "w" => Team::White,
+            "b" => Team::Black,
+            _ => return Err(FenError::InvalidActiveColor(active_color.to_string())),
+        };
+        if active_team == Team::Black {
+            if let Some(player) = chess.players.next() {
+                chess.state = GameState::Turn(player.id);
+            }
+        }
+
+        if castling != "-" {
+            for c in castling.chars() {
+                let invalid = || FenError::InvalidCastling(castling.to_string());
+                let team = if c.is_ascii_uppercase() {
+                    Team::White
+                } else {
+                    Team::Black
+                };
+                let player = chess.get_player_by_team(team);
+                let king_pos = chess
+                    .get_king_position(player.id)
+                    .filter(|pos| pos.row() == team.get_king_initial_position().row())
+                    .ok_or_else(invalid)?;
+                let rook = BoardCell(Some(Piece::create_rook(player.id)));
+                let mut rooks = (0..8)
+                    .map(|col| GridIndex::new(king_pos.row(), col))
+                    .filter(|&pos| chess.board[pos] == rook);
+                // "KQkq" stand for the outermost rooks, Shredder-FEN names rook columns
+                let rook_pos = match c.to_ascii_lowercase() {
+                    'k' => rooks.rfind(|pos| pos.col() > king_pos.col()),
+                    'q' => rooks.find(|pos| pos.col() < king_pos.col()),
+                    file @ 'a'..='h' => {
+                        let pos = GridIndex::new(king_pos.row(), usize::from(file as u8 - b'a'));
+                        (chess.board[pos] == rook).then_some(pos)
+                    }
+                    _ => None,
+                }
+                .ok_or_else(invalid)?;
+                if king_pos != team.get_king_initial_position()
+                    || (rook_pos != team.get_left_rook_initial_position()
+                        && rook_pos != team.get_right_rook_initial_position())
+                    || !matches!(c, 'K' | 'Q' | 'k' | 'q')
+                {
+                    chess.chess960 = true;
+                }
+                if let Some(state) = chess.player_state.get_mut(&player.id) {
+                    if rook_pos.col() < king_pos.col() {
+                        state.castle_options.left = true;
+                        state.left_rook_pos = rook_pos;
+                    } else {
+                        state.castle_options.right = true;
+                        state.right_rook_pos = rook_pos;
+                    }
+                }
+            }
+        }
+
+        let current_player = chess.get_player_by_team(active_team);
+        let enemy = chess.get_player_by_team(match active_team {
+            Team::White => Team::Black,
+            Team::Black => Team::White,
+        });
+        if en_passant != "-" {
+            // the cell right behind the pawn of the player that has just moved,
+            // rows of the cell, the pawn and the cell it has started from
+            let (expected_row, pawn_row, start_row) = match active_team {
+                Team::White => (2, 3, 1),
+                Team::Black => (5, 4, 6),
+            };
+            let pawn = BoardCell(Some(Piece::create_pawn(enemy.id)));
+            chess.en_passant = Some(
+                fen::parse_square(en_passant)
+                    .filter(|pos| {
+                        pos.row() == expected_row
+                            && chess.board[*pos].is_none()
+                            && chess.board[GridIndex::new(start_row, pos.col())].is_none()
+                            && chess.board[GridIndex::new(pawn_row, pos.col())] == pawn
+                    })
+                    .ok_or_else(|| FenError::InvalidEnPassant(en_passant.to_string()))?,
+            );
+        }
+
+        // the king of the player that has just moved can't be left in check
+        chess.update_check(&enemy);
+        if chess.is_in_check(enemy.id) {
+            return Err(FenError::invalid_piece_placement(format!(
+                "{:?} is in check while {:?} is to move",
+                enemy.team, active_team
+            )));
+        }
+        chess.update_check(&current_player);
+        chess.halfmove_clock = halfmove_clock;
+        chess.fullmove_number = fullmove_number;
+        chess.position_history = vec![chess.position_key()];
+        if chess.finish_if_no_moves(&enemy, &current_player).is_none() {
+            chess.adjudicate_draw();
+        }
+        Ok(chess)
+    }
+
+    /// Returns the current position in Forsyth–Edwards Notation.
+    pub fn to_fen(&self) -> String {
+        let team = |id| self.get_player_team(id).expect("each piece has an owner");
+        let placement = self
+            .board
+            .iter()
+            .map(|row| {
+                let mut rank = String::new();
+                let mut empty = 0;
+                for cell in row.iter() {
+                    match cell {
+                        BoardCell(Some(piece)) => {
+                            if empty > 0 {
+                                rank.push_str(&empty.to_string());
+                                empty = 0;
+                            }
+                            rank.push(fen::piece_to_char(piece.kind, team(piece.owner)));
+                        }
+                        BoardCell(None) => empty += 1,
+                    }
+                }
+                if empty > 0 {
+                    rank.push_str(&empty.to_string());
+                }
+                rank
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let active_color = match self.state {
+            GameState::Turn(id) if team(id) == Team::Black => "b",
+            _ => "w",
+        };
+
+        let mut castling = String::new();
+        for team in [Team::White, Team::Black] {
+            let player = self.get_player_by_team(team);
+            let options = self
+                .player_state
+                .get(&player.id)
+                .map(|state| state.castle_options)
+                .unwrap_or(CastleOptions::none());
+            // Chess960 castling is written in Shredder-FEN as columns of the rooks
+            let symbol = |left, kind| match self.get_castling_cells(player.id, left) {
+                Some((rook_pos, ..)) if self.chess960 => {
+                    let file = fen::square_name(rook_pos).remove(0);
+                    match team {
+                        Team::White => file.to_ascii_uppercase(),
+                        Team::Black => file,
+                    }
+                }
+                _ => fen::piece_to_char(kind, team),
+            };
+            if options.right {
+                castling.push(symbol(false, PieceKind::King));
+            }
+            if options.left {
+                castling.push(symbol(true, PieceKind::Queen));
+            }
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        let en_passant = self
+            .en_passant
+            .map(fen::square_name)
+            .unwrap_or("-".to_string());
+
+        format!(
+            "{} {} {} {} {} {}",
+            placement,
+            active_color,
+            castling,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    /// Returns all legal moves of the current player,
+    /// pawn move to the last row is listed once for each piece it can be promoted to.
+    pub fn legal_moves(&self) -> Vec<TurnData> {
+        let GameState::Turn(id) = self.state else {
+            return vec![];
+        };
+        let mut moves = vec![];
+        for from in self.find_pieces_positions(id) {
+            for to in self.get_moves(from).unwrap_or_default() {
+                let data = TurnData::new(from, to);
+                if self.get_move_type(data) == MoveType::Promotion {
+                    moves.extend(
+                        [
+                            PieceKind::Queen,
+                            PieceKind::Rook,
+                            PieceKind::Bishop,
+                            PieceKind::Knight,
+                        ]
+                        .map(|kind| TurnData::with_promotion(from, to, kind)),
+                    );
+                } else {
+                    moves.push(data);
+                }
+            }
+        }
+        moves
+    }
+
+    /// Counts leaf nodes of the tree of legal moves of the given depth.
+    /// Branch is cut off only by checkmate and stalemate, draws by repetition,
+    /// fifty-move rule and insufficient material are not adjudicated.
+    pub fn perft(&self, depth: usize) -> u64 {
+        match depth {
+            0 => 1,
+            _ => self
+                .perft_divide(depth)
+                .into_iter()
+                .map(|(_, nodes)| nodes)
+                .sum(),
+        }
+    }
+
+    /// Returns number of leaf nodes of the tree of the given depth for each legal move.
+    pub fn perft_divide(&self, depth: usize) -> Vec<(TurnData, u64)> {
+        let mut chess = self.without_draw_adjudication();
+        let GameState::Turn(id) = chess.state else {
+            return vec![];
+        };
+        if depth == 0 {
+            return vec![];
+        }
+        chess
+            .legal_moves()
+            .into_iter()
+            .map(|data| (data, chess.count_nodes(id, data, depth - 1)))
+            .collect()
+    }
+
+    /// Returns copy of the game that goes on after draws by repetition, fifty-move rule
+    /// and insufficient material, the game is resumed if it has already ended with one of them.
+    fn without_draw_adjudication(&self) -> Self {
+        let mut chess = self.clone();
+        chess.draw_adjudication = false;
+        if let GameState::Finished(FinishedState::Draw(
+            TerminationReason::ThreefoldRepetition
+            | TerminationReason::FiftyMoveRule
+            | TerminationReason::InsufficientMaterial,
+        )) = chess.state
+        {
+            if let Ok(player) = chess.get_current_player() {
+                chess.state = GameState::Turn(player.id);
+            }
+        }
+        chess
+    }
+
+    /// Makes the turn, counts leaf nodes of the tree of the given depth after it and takes it back.
+    fn count_nodes(&mut self, id: PlayerPosition, data: TurnData, depth: usize) -> u64 {
+        if self.update(id, data).is_err() {
+            return 0;
+        }
+        let nodes = match (depth, self.state) {
+            (0, _) => 1,
+            (1, _) => self.legal_moves().len() as u64,
+            (_, GameState::Turn(next)) => self
+                .legal_moves()
+                .into_iter()
+                .map(|data| self.count_nodes(next, data, depth - 1))
+                .sum(),
+            (_, GameState::Waiting(_) | GameState::Seeding(_) | GameState::Finished(_)) => 0,
+        };
+        self.undo().expect("turn has just been made");
+        nodes
+    }
+
+    /// Moves the piece for a turn that has already been validated.
+    fn make_move(
+        &mut self,
+        player: PlayerData,
+        piece: Piece,
+        data: TurnData,
+        move_type: MoveType,
+    ) -> GameResult<GameState> {
+        let id = player.id;
+        // king castling in Chess960 moves to the cell of its own rook, that's not a capture
+        let captured = self.board[data.to].filter(|target| target.is_enemy(id));
+        let is_capture = captured.is_some() || move_type == MoveType::EnPassant;
+        // rook captured on its initial cell can't be used for castling
+        if let Some(captured) = captured.filter(Piece::is_rook) {
+            self.disable_rook_castling(captured.owner, data.to);
+        }
+        match move_type {
+            MoveType::LeftCastling => self.castle(id, true)?,
+            MoveType::RightCastling => self.castle(id, false)?,
+            MoveType::KingMove => {
+                // castling is disabled inside of update_king_position
+                self.update_king_position(id, data.to);
+            }
+            MoveType::RookMove => self.disable_rook_castling(id, data.from),
+            MoveType::EnPassant => {
+                if let Some(captured) = self.get_en_passant_capture(data.from, data.to) {
+                    self.set_cell(captured, BoardCell(None));
+                }
+            }
+            MoveType::Promotion => {
+                // replace pawn in place, the promoted piece is moved below
+                if let Some(kind) = data.promotion {
+                    self.set_cell(data.from, Piece { kind, owner: id }.into());
+                }
+            }
+            MoveType::Other => {}
+        };
+        // castling has already moved both the king and the rook
+        if !matches!(move_type, MoveType::LeftCastling | MoveType::RightCastling) {
+            self.move_piece(data.from, data.to)?;
+        }
+        // pawn that has advanced by two rows can be captured en passant during the next turn
+        self.en_passant = (piece.is_pawn() && data.from.row().abs_diff(data.to.row()) == 2)
+            .then(|| GridIndex::new((data.from.row() + data.to.row()) / 2, data.from.col()));
+        // positions before capture or pawn move can't be repeated
+        if piece.is_pawn() || is_capture {
+            self.halfmove_clock = 0;
+            self.position_history.clear();
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if player.team == Team::Black {
+            self.fullmove_number += 1;
+        }
+
+        self.update_state()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            players: self.players.clone(),
+            state: self.state,
+            board: self.board.clone(),
+            bitboards: self.bitboards,
+            player_state: self.player_state.clone(),
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            position_history: self.position_history.clone(),
+            fullmove_number: self.fullmove_number,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.players = snapshot.players;
+        self.state = snapshot.state;
+        self.board = snapshot.board;
+        self.bitboards = snapshot.bitboards;
+        self.player_state = snapshot.player_state;
+        self.en_passant = snapshot.en_passant;
+        self.halfmove_clock = snapshot.halfmove_clock;
+        self.position_history = snapshot.position_history;
+        self.fullmove_number = snapshot.fullmove_number;
+    }
+
+    /// Returns number of the current full move as it's written in FEN.
+    pub(super) fn fullmove_number(&self) -> usize {
+        self.fullmove_number
+    }
+
+    pub(super) fn get_player_team(&self, id: PlayerPosition) -> Option<Team> {
+        self.players.find(id).map(|player| player.team)
+    }
+
+    fn get_player_by_team(&self, team: Team) -> PlayerData {
+        self.players
+            .find_if(|player| player.team == team)
+            .copied()
+            .expect("each team has a player")
+    }
+
     fn disable_castling(&mut self, id: PlayerPosition) {
         if let Some(state) = self.player_state.get_mut(&id) {
             state.castle_options = CastleOptions::none();
@@ -255,11 +836,78 @@ impl Chess {
         }
     }
 
+    /// Disables castling on the side of the rook if it has left its initial cell at `pos`.
+    fn disable_rook_castling(&mut self, id: PlayerPosition, pos: GridIndex) {
+        let Some(state) = self.player_state.get(&id) else {
+            return;
+        };
+        if pos == state.left_rook_pos {
+            self.disable_left_castling(id);
+        } else if pos == state.right_rook_pos {
+            self.disable_right_castling(id);
+        }
+    }
+
+    /// Returns initial cell of the rook and the cells the king and the rook are moved to by castling.
+    fn get_castling_cells(
+        &self,
+        id: PlayerPosition,
+        left: bool,
+    ) -> Option<(GridIndex, GridIndex, GridIndex)> {
+        let state = self.player_state.get(&id)?;
+        let row = state.king_pos.row();
+        let (rook_pos, (king_col, rook_col)) = if left {
+            (state.left_rook_pos, LEFT_CASTLING_COLUMNS)
+        } else {
+            (state.right_rook_pos, RIGHT_CASTLING_COLUMNS)
+        };
+        Some((
+            rook_pos,
+            GridIndex::new(row, king_col),
+            GridIndex::new(row, rook_col),
+        ))
+    }
+
+    /// Returns the cell the king of the player moves to in order to castle:
+    /// two cells towards the rook in the standard chess and the cell of the rook in Chess960.
+    pub(super) fn get_castling_target(&self, id: PlayerPosition, left: bool) -> Option<GridIndex> {
+        let (rook_pos, king_to, _) = self.get_castling_cells(id, left)?;
+        Some(if self.chess960 { rook_pos } else { king_to })
+    }
+
+    fn castle(&mut self, id: PlayerPosition, left: bool) -> GameResult<()> {
+        let king_pos = self
+            .get_king_position(id)
+            .ok_or(GameError::PlayerNotFound)?;
+        let (rook_pos, king_to, rook_to) = self
+            .get_castling_cells(id, left)
+            .ok_or(GameError::PlayerNotFound)?;
+        // in Chess960 the king and the rook may take each other's cells, so both are lifted first
+        let king = self.set_cell(king_pos, BoardCell(None));
+        let rook = self.set_cell(rook_pos, BoardCell(None));
+        self.set_cell(king_to, king);
+        self.set_cell(rook_to, rook);
+        self.update_king_position(id, king_to);
+        Ok(())
+    }
+
     fn update_king_position(&mut self, id: PlayerPosition, pos: GridIndex) {
         if let Some(state) = self.player_state.get_mut(&id) {
             state.king_pos = pos;
-            // castling is disabled once king has moved
-            state.castle_options = CastleOptions::none();
+        }
+        // castling is disabled once king has moved
+        self.disable_castling(id);
+    }
+
+    /// Rebuilds bitboards and king positions from the board after it has been changed directly.
+    fn sync_with_board(&mut self) {
+        self.bitboards = Bitboards::from_grid(&self.board);
+        for (pos, cell) in self.board.all_indexed() {
+            if let Some(king) = cell.filter(Piece::is_king) {
+                if let Some(state) = self.player_state.get_mut(&king.owner) {
+                    state.king_pos = pos;
+                }
+            }
         }
     }
 
@@ -273,51 +921,77 @@ impl Chess {
     }
 
     fn move_piece(&mut self, from: GridIndex, to: GridIndex) -> GameResult<Cell> {
-        let piece = self.board[from]
-            .take()
-            .ok_or(GameError::cell_is_empty(from.row(), from.col()))?;
-        let old_to = std::mem::replace(&mut self.board[to], piece.into());
-        Ok(old_to)
+        if self.board[from].is_none() {
+            return Err(GameError::cell_is_empty(from.row(), from.col()));
+        }
+        let piece = self.set_cell(from, BoardCell(None));
+        Ok(self.set_cell(to, piece))
     }
 
-    fn is_enemy(&self, position: GridIndex, player: PlayerPosition) -> bool {
-        self.board[position]
-            .filter(|target| target.is_enemy(player))
-            .is_some()
+    /// Replaces content of the cell keeping bitboards in sync, returns the previous content.
+    fn set_cell(&mut self, pos: GridIndex, cell: Cell) -> Cell {
+        if let BoardCell(Some(piece)) = self.board[pos] {
+            self.bitboards.remove(pos, piece);
+        }
+        if let BoardCell(Some(piece)) = cell {
+            self.bitboards.put(pos, piece);
+        }
+        std::mem::replace(&mut self.board[pos], cell)
     }
 
-    fn is_in_check(&self, id: PlayerPosition) -> bool {
+    pub(super) fn is_in_check(&self, id: PlayerPosition) -> bool {
         if let Some(threats) = self.player_state.get(&id).map(|state| &state.check) {
             return !threats.is_empty();
         }
         false
     }
 
-    fn get_king_position(&self, id: PlayerPosition) -> Option<GridIndex> {
+    pub(super) fn get_king_position(&self, id: PlayerPosition) -> Option<GridIndex> {
         self.player_state.get(&id).map(|state| state.king_pos)
     }
 
-    fn get_move_type(&self, TurnData { from, to }: TurnData) -> MoveType {
-        if self.board[from].filter(Piece::is_king).is_some() {
-            if (from == Team::Black.get_king_initial_position()
-                || from == Team::White.get_king_initial_position())
-                && from.row() == to.row()
-            {
-                match from.col().partial_cmp(&to.col()) {
-                    Some(Ordering::Less) if to.col() == from.col() + 2 => {
-                        return MoveType::RightCastling;
-                    }
-                    Some(Ordering::Greater) if from.col() == to.col() + 2 => {
-                        return MoveType::LeftCastling;
-                    }
-                    _ => {}
-                };
+    /// Returns position of the pawn that is captured en passant if piece at `from` moves to `to`,
+    /// otherwise [`None`].
+    fn get_en_passant_capture(&self, from: GridIndex, to: GridIndex) -> Option<GridIndex> {
+        if self.en_passant != Some(to) {
+            return None;
+        }
+        let piece = self.board[from].filter(Piece::is_pawn)?;
+        let captured = GridIndex::new(from.row(), to.col());
+        self.board[captured]
+            .filter(|target| target.is_pawn() && target.is_enemy(piece.owner))
+            .map(|_| captured)
+    }
+
+    pub(super) fn get_move_type(&self, TurnData { from, to, .. }: TurnData) -> MoveType {
+        if let Some(king) = self.board[from].filter(Piece::is_king) {
+            // castling is possible only while the king stays on its initial cell
+            let options = self
+                .player_state
+                .get(&king.owner)
+                .map(|state| state.castle_options)
+                .unwrap_or(CastleOptions::none());
+            if options.left && self.get_castling_target(king.owner, true) == Some(to) {
+                return MoveType::LeftCastling;
+            }
+            if options.right && self.get_castling_target(king.owner, false) == Some(to) {
+                return MoveType::RightCastling;
             }
             return MoveType::KingMove;
         }
         if self.board[from].filter(Piece::is_rook).is_some() {
             return MoveType::RookMove;
         }
+        if self.get_en_passant_capture(from, to).is_some() {
+            return MoveType::EnPassant;
+        }
+        // pawn can't move backwards so reaching either of the last rows means promotion
+        if self.board[from].filter(Piece::is_pawn).is_some()
+            && (to.row() == Team::White.get_pawn_promotion_row()
+                || to.row() == Team::Black.get_pawn_promotion_row())
+        {
+            return MoveType::Promotion;
+        }
         MoveType::Other
     }
 
@@ -327,263 +1001,214 @@ impl Chess {
             .get(&id)
             .ok_or(GameError::PlayerNotFound)?;
         let player = self.players.find(id).ok_or(GameError::PlayerNotFound)?;
-        let empty_not_threatened = |(pos, cell): (GridIndex, &Cell)| {
-            cell.is_none() && self.get_attack_threats(pos, player).is_empty()
-        };
+        // king can't castle out of check
+        if !player_state.check.is_empty() {
+            return Ok(CastleOptions::none());
+        }
         let mut castle_options = player_state.castle_options;
-        if player_state.check.is_empty() {
-            let king_pos = player.team.get_king_initial_position();
-            if castle_options.left {
-                let mut left_it = self.board.left_move_iter(king_pos).take(2);
-                castle_options.left = left_it.all(empty_not_threatened);
-                if castle_options.left {
-                    castle_options.left = self.board[king_pos.move_left(3)].is_none();
-                }
-            }
-            if castle_options.right {
-                let mut right_it = self.board.right_move_iter(king_pos).take(2);
-                castle_options.right = right_it.all(empty_not_threatened);
-            }
+        if castle_options.left {
+            castle_options.left = self.is_castling_allowed(player, true);
+        }
+        if castle_options.right {
+            castle_options.right = self.is_castling_allowed(player, false);
         }
         Ok(castle_options)
     }
 
-    fn get_attack_threats(&self, pos: GridIndex, player: &PlayerData) -> Vec<GridIndex> {
-        let get_occupied = |(pos, cell): (_, &Cell)| {
-            if let BoardCell(Some(piece)) = cell {
-                return Some((pos, *piece));
-            }
-            None
+    /// Checks that the king and the rook are on their cells, all cells they pass through are empty
+    /// and none of the cells the king passes through is attacked.
+    fn is_castling_allowed(&self, player: &PlayerData, left: bool) -> bool {
+        let (Some(king_pos), Some((rook_pos, king_to, rook_to))) = (
+            self.get_king_position(player.id),
+            self.get_castling_cells(player.id, left),
+        ) else {
+            return false;
         };
-        let occupied_tl = self.board.up_left_move_iter(pos).find_map(get_occupied);
-        let occupied_tr = self.board.up_right_move_iter(pos).find_map(get_occupied);
-        let occupied_br = self.board.down_right_move_iter(pos).find_map(get_occupied);
-        let occupied_bl = self.board.down_left_move_iter(pos).find_map(get_occupied);
-        let occupied_right = self.board.right_move_iter(pos).find_map(get_occupied);
-        let occupied_left = self.board.left_move_iter(pos).find_map(get_occupied);
-        let occupied_up = self.board.up_move_iter(pos).find_map(get_occupied);
-        let occupied_down = self.board.down_move_iter(pos).find_map(get_occupied);
-
-        // get first occupied cell which is enemy (if any) for each diagonal
-        let threats = occupied_tl
-            .into_iter()
-            .chain(occupied_tr.into_iter())
-            .chain(occupied_br.into_iter())
-            .chain(occupied_bl.into_iter())
-            // filter pieces that can attack diagonally
-            .filter(|&(enemy_pos, piece)| {
-                if !piece.is_enemy(player.id) {
-                    return false;
-                }
-                match piece.kind {
-                    PieceKind::Bishop | PieceKind::Queen => true,
-                    PieceKind::King => enemy_pos.is_adjacent(&pos),
-                    PieceKind::Pawn => {
-                        enemy_pos.is_adjacent(&pos)
-                            && match player.team {
-                                Team::White => enemy_pos.row() < pos.row(),
-                                Team::Black => enemy_pos.row() > pos.row(),
-                            }
-                    }
-                    _ => false,
-                }
-            })
-            .chain(
-                // get first occupied cell which is enemy (if any) for each horizontal and vertical line
-                occupied_right
-                    .into_iter()
-                    .chain(occupied_left.into_iter())
-                    .chain(occupied_up.into_iter())
-                    .chain(occupied_down.into_iter())
-                    // filter pieces that can attack horizontally or vertically
-                    .filter(|&(enemy_pos, piece)| {
-                        if !piece.is_enemy(player.id) {
-                            return false;
-                        }
-                        match piece.kind {
-                            PieceKind::Rook | PieceKind::Queen => true,
-                            PieceKind::King => enemy_pos.is_adjacent(&pos),
-                            _ => false,
-                        }
-                    }),
-            )
-            .map(|(index, _)| index)
-            .chain(
-                // check all possible knight positions
-                self.board.knight_move_iter(pos).filter_map(|(pos, _)| {
-                    if self.board[pos]
-                        .filter(|p| p.is_enemy(player.id) && p.kind == PieceKind::Knight)
-                        .is_some()
-                    {
-                        return Some(pos);
-                    }
-                    None
-                }),
-            )
-            .collect();
+        let king = Piece::create_king(player.id);
+        let rook = Piece::create_rook(player.id);
+        if self.board[king_pos] != BoardCell(Some(king))
+            || self.board[rook_pos] != BoardCell(Some(rook))
+        {
+            return false;
+        }
+        // the king and the rook don't block each other, and the rook doesn't shield the king
+        let mut bitboards = self.bitboards;
+        bitboards.remove(king_pos, king);
+        bitboards.remove(rook_pos, rook);
+        let path = |from: GridIndex, to: GridIndex| {
+            (from.col().min(to.col())..=from.col().max(to.col()))
+                .map(move |col| GridIndex::new(from.row(), col))
+        };
+        path(king_pos, king_to)
+            .chain(path(rook_pos, rook_to))
+            .all(|pos| bitboards.occupied() & bitboard::bit(pos) == 0)
+            && path(king_pos, king_to)
+                .all(|pos| bitboards.attackers(pos, player.id, player.team) == 0)
+    }
 
-        threats
+    fn get_attack_threats(&self, pos: GridIndex, player: &PlayerData) -> Vec<GridIndex> {
+        bitboard::cells(self.bitboards.attackers(pos, player.id, player.team)).collect()
     }
 
-    fn get_moves(&mut self, pos: GridIndex) -> GameResult<Vec<GridIndex>> {
+    pub(super) fn get_moves(&self, pos: GridIndex) -> GameResult<Vec<GridIndex>> {
         let piece = self.board[pos].ok_or(GameError::cell_is_empty(pos.row(), pos.col()))?;
         let player = *self
             .players
             .find(piece.owner)
             .ok_or(GameError::PlayerNotFound)?;
-        let mut res = vec![];
-        let empty_or_enemy = |(index, cell): (GridIndex, &Cell)| {
-            if cell.is_none() || matches!(cell, BoardCell(Some(p)) if p.is_enemy(piece.owner)) {
-                return Some(index);
-            }
-            None
-        };
-        match piece.kind {
+        let occupied = self.bitboards.occupied();
+        let own = self.bitboards.occupied_by(piece.owner);
+        let mut targets = match piece.kind {
             PieceKind::Pawn => {
-                let advance = |pos| match player.team {
-                    Team::White => self.board.up_move_iter(pos).next(),
-                    Team::Black => self.board.down_move_iter(pos).next(),
+                let advance = |pos: GridIndex| match player.team {
+                    Team::White => (pos.row() > 0).then(|| pos.move_up(1)),
+                    Team::Black => (pos.row() < 7).then(|| pos.move_down(1)),
                 };
-                if let Some((idx, cell)) = advance(pos) {
-                    if cell.is_none() {
-                        res.push(idx);
-                        // if pawn didn't move it can advance one more row
-                        if pos.row() == player.team.get_pawn_initial_row() {
-                            if let Some((idx, cell)) = advance(idx) {
-                                if cell.is_none() {
-                                    res.push(idx);
-                                }
-                            }
+                let mut targets = 0;
+                if let Some(next) = advance(pos).filter(|&next| self.board[next].is_none()) {
+                    targets |= bitboard::bit(next);
+                    // if pawn didn't move it can advance one more row
+                    if pos.row() == player.team.get_pawn_initial_row() {
+                        if let Some(next) = advance(next).filter(|&next| self.board[next].is_none())
+                        {
+                            targets |= bitboard::bit(next);
                         }
                     }
-                    let left_it = self.board.right_move_iter(idx).take(1);
-                    let right_it = self.board.left_move_iter(idx).take(1);
-                    res.extend(left_it.chain(right_it).filter_map(|(index, _)| {
-                        if self.is_enemy(index, piece.owner) {
-                            return Some(index);
-                        }
-                        None
-                    }));
                 }
-            }
-            PieceKind::Bishop => {
-                let diag_tl = while_empty(self.board.up_left_move_iter(pos));
-                let diag_tr = while_empty(self.board.up_right_move_iter(pos));
-                let diag_br = while_empty(self.board.down_right_move_iter(pos));
-                let diag_bl = while_empty(self.board.down_left_move_iter(pos));
-                res = diag_tl
-                    .chain(diag_tr)
-                    .chain(diag_br)
-                    .chain(diag_bl)
-                    .filter_map(empty_or_enemy)
-                    .collect();
-            }
-            PieceKind::Knight => {
-                res.extend(self.board.knight_move_iter(pos).filter_map(empty_or_enemy));
-            }
-            PieceKind::Rook => {
-                let right = while_empty(self.board.right_move_iter(pos));
-                let left = while_empty(self.board.left_move_iter(pos));
-                let top = while_empty(self.board.up_move_iter(pos));
-                let bot = while_empty(self.board.down_move_iter(pos));
-                res = right
-                    .chain(left)
-                    .chain(top)
-                    .chain(bot)
-                    .filter_map(empty_or_enemy)
-                    .collect();
-            }
-            PieceKind::Queen => {
-                let diag_tl = while_empty(self.board.up_left_move_iter(pos));
-                let diag_tr = while_empty(self.board.up_right_move_iter(pos));
-                let diag_br = while_empty(self.board.down_right_move_iter(pos));
-                let diag_bl = while_empty(self.board.down_left_move_iter(pos));
-                let right = while_empty(self.board.right_move_iter(pos));
-                let left = while_empty(self.board.left_move_iter(pos));
-                let top = while_empty(self.board.up_move_iter(pos));
-                let bot = while_empty(self.board.down_move_iter(pos));
-                res = diag_tl
-                    .chain(diag_tr)
-                    .chain(diag_br)
-                    .chain(diag_bl)
-                    .chain(right)
-                    .chain(left)
-                    .chain(top)
-                    .chain(bot)
-                    .filter_map(empty_or_enemy)
-                    .collect();
-            }
-            PieceKind::King => {
-                res = [
-                    self.board.up_left_move_iter(pos).next(),
-                    self.board.up_right_move_iter(pos).next(),
-                    self.board.down_right_move_iter(pos).next(),
-                    self.board.down_left_move_iter(pos).next(),
-                    self.board.right_move_iter(pos).next(),
-                    self.board.left_move_iter(pos).next(),
-                    self.board.up_move_iter(pos).next(),
-                    self.board.down_move_iter(pos).next(),
-                ]
-                .into_iter()
-                .flatten()
-                .filter_map(empty_or_enemy)
-                .collect();
-
-                let castle_options = self.can_castle(piece.owner)?;
-                if castle_options.left {
-                    res.push(pos.move_left(2));
-                }
-                if castle_options.right {
-                    res.push(pos.move_right(2));
+                let captures = bitboard::pawn_attacks(pos, player.team);
+                targets |= captures & occupied & !own;
+                if let Some(en_passant) = self
+                    .en_passant
+                    .filter(|&to| self.get_en_passant_capture(pos, to).is_some())
+                {
+                    targets |= captures & bitboard::bit(en_passant);
                 }
+                targets
             }
+            PieceKind::Bishop => bitboard::bishop_attacks(pos, occupied) & !own,
+            PieceKind::Knight => bitboard::knight_attacks(pos) & !own,
+            PieceKind::Rook => bitboard::rook_attacks(pos, occupied) & !own,
+            PieceKind::Queen => bitboard::queen_attacks(pos, occupied) & !own,
+            PieceKind::King => bitboard::king_attacks(pos) & !own,
         };
+        // castling is checked for safety of the king separately
+        let mut castling = 0;
+        if piece.is_king() {
+            let castle_options = self.can_castle(piece.owner)?;
+            for (left, allowed) in [(true, castle_options.left), (false, castle_options.right)] {
+                if let Some(to) = self
+                    .get_castling_target(piece.owner, left)
+                    .filter(|_| allowed)
+                {
+                    castling |= bitboard::bit(to);
+                }
+            }
+        }
 
         // exclude moves that lead to check
         let king_pos = self
             .get_king_position(player.id)
             .ok_or(GameError::PlayerNotFound)?;
-        // TODO: handle errors inside of retain
-        res.retain(|&index| {
-            let backup = match self.move_piece(pos, index) {
-                Ok(cell) => cell,
-                Err(_) => return false,
-            };
+        for to in bitboard::cells(targets) {
+            let mut bitboards = self.bitboards;
+            if let BoardCell(Some(captured)) = self.board[to] {
+                bitboards.remove(to, captured);
+            }
+            // pawn captured en passant is not on the target cell so it's removed separately
+            if let Some(captured) = self.get_en_passant_capture(pos, to) {
+                if let BoardCell(Some(captured_piece)) = self.board[captured] {
+                    bitboards.remove(captured, captured_piece);
+                }
+            }
+            bitboards.remove(pos, piece);
+            bitboards.put(to, piece);
             // if king has moved use it's updated position
-            let king_pos = if piece.is_king() { index } else { king_pos };
-            let king_safe = self.get_attack_threats(king_pos, &player).is_empty();
-            if let Err(_) = self.move_piece(index, pos) {
-                return false;
+            let king_pos = if piece.is_king() { to } else { king_pos };
+            if bitboards.attackers(king_pos, player.id, player.team) != 0 {
+                targets &= !bitboard::bit(to);
             }
-            self.board[index] = backup;
-            king_safe
-        });
-        Ok(res)
+        }
+        Ok(bitboard::cells(targets | castling).collect())
     }
 
-    fn find_pieces_positions(&self, id: PlayerPosition) -> Vec<GridIndex> {
-        let mut pieces = vec![];
-        for row in 0..8 {
-            for col in 0..8 {
-                if let BoardCell(Some(piece)) = self.board[GridIndex::new(row, col)] {
-                    if !piece.is_enemy(id) {
-                        pieces.push(GridIndex::new(row, col));
-                    }
-                }
+    /// Returns Zobrist hash of the current position.
+    /// It takes into account pieces placement, side to move, castling options and en passant cell
+    /// if the pawn there can actually be captured.
+    fn position_key(&self) -> u64 {
+        let mut key = self
+            .board
+            .all_indexed()
+            .fold(0, |key, (pos, cell)| match cell {
+                BoardCell(Some(piece)) => key ^ zobrist::piece_key(*piece, pos),
+                BoardCell(None) => key,
+            });
+        if let GameState::Turn(id) = self.state {
+            key ^= zobrist::side_key(id);
+        }
+        for (&id, state) in self.player_state.iter() {
+            if state.castle_options.left {
+                key ^= zobrist::left_castling_key(id);
+            }
+            if state.castle_options.right {
+                key ^= zobrist::right_castling_key(id);
             }
         }
-        pieces
+        if let Some(pos) = self.en_passant.filter(|_| self.can_capture_en_passant()) {
+            key ^= zobrist::en_passant_key(pos);
+        }
+        key
     }
 
-    fn update_state(&mut self) -> GameResult<GameState> {
-        let current_player = *self.get_current_player()?;
-        // player cannot finish its turn in check, so just clear check for current player
-        if let Some(state) = self.player_state.get_mut(&current_player.id) {
-            state.check.clear();
+    /// Checks if the player to move has a pawn able to capture en passant without exposing its king.
+    fn can_capture_en_passant(&self) -> bool {
+        let (Some(to), GameState::Turn(id)) = (self.en_passant, self.state) else {
+            return false;
+        };
+        self.find_pieces_positions(id)
+            .into_iter()
+            .filter(|&from| self.get_en_passant_capture(from, to).is_some())
+            .any(|from| self.get_moves(from).is_ok_and(|moves| moves.contains(&to)))
+    }
+
+    fn is_threefold_repetition(&self) -> bool {
+        let Some(current) = self.position_history.last() else {
+            return false;
+        };
+        self.position_history
+            .iter()
+            .filter(|&key| key == current)
+            .count()
+            >= 3
+    }
+
+    pub(super) fn find_pieces_positions(&self, id: PlayerPosition) -> Vec<GridIndex> {
+        bitboard::cells(self.bitboards.occupied_by(id)).collect()
+    }
+
+    /// Checks if neither player is able to checkmate with the pieces left on the board:
+    /// king against king, king and minor piece against king,
+    /// or kings and bishops that are all on the same cell color.
+    fn is_insufficient_material(&self, player: PlayerPosition, enemy: PlayerPosition) -> bool {
+        let pieces: Vec<(GridIndex, Piece)> = self
+            .find_pieces_positions(player)
+            .into_iter()
+            .chain(self.find_pieces_positions(enemy))
+            .filter_map(|pos| self.board[pos].map(|piece| (pos, piece)))
+            .filter(|(_, piece)| piece.kind != PieceKind::King)
+            .collect();
+        match pieces.as_slice() {
+            [] => true,
+            [(_, piece)] => matches!(piece.kind, PieceKind::Bishop | PieceKind::Knight),
+            [(first, _), ..] => pieces.iter().all(|(pos, piece)| {
+                piece.kind == PieceKind::Bishop
+                    && (pos.row() + pos.col()) % 2 == (first.row() + first.col()) % 2
+            }),
         }
-        let enemy = *self.get_enemy_player()?;
-        self.update_check(&enemy);
+    }
 
+    /// Finishes the game if `enemy`, who is to move, has no legal moves left.
+    /// Check of `enemy` has to be up to date.
+    fn finish_if_no_moves(&mut self, player: &PlayerData, enemy: &PlayerData) -> Option<GameState> {
         let enemy_pieces = self.find_pieces_positions(enemy.id);
         if enemy_pieces.into_iter().all(|index| {
             if let Ok(moves) = self.get_moves(index) {
@@ -592,14 +1217,49 @@ impl Chess {
             true
         }) {
             return if self.is_in_check(enemy.id) {
-                Ok(self.set_winner(current_player.id))
+                Some(self.set_winner(player.id, TerminationReason::Checkmate))
             } else {
-                // stalemate
-                Ok(self.set_draw())
+                Some(self.set_draw(TerminationReason::Stalemate))
             };
         }
+        None
+    }
+
+    /// Finishes the game in a draw by insufficient material, threefold repetition
+    /// or fifty-move rule, unless draws aren't adjudicated.
+    fn adjudicate_draw(&mut self) -> Option<GameState> {
+        if !self.draw_adjudication {
+            return None;
+        }
+        let player = self.get_current_player().ok()?.id;
+        let enemy = self.get_enemy_player().ok()?.id;
+        if self.is_insufficient_material(player, enemy) {
+            return Some(self.set_draw(TerminationReason::InsufficientMaterial));
+        }
+        if self.is_threefold_repetition() {
+            return Some(self.set_draw(TerminationReason::ThreefoldRepetition));
+        }
+        if self.halfmove_clock >= FIFTY_MOVE_RULE_TURNS {
+            return Some(self.set_draw(TerminationReason::FiftyMoveRule));
+        }
+        None
+    }
+
+    fn update_state(&mut self) -> GameResult<GameState> {
+        let current_player = *self.get_current_player()?;
+        // player cannot finish its turn in check, so just clear check for current player
+        if let Some(state) = self.player_state.get_mut(&current_player.id) {
+            state.check.clear();
+        }
+        let enemy = *self.get_enemy_player()?;
+        self.update_check(&enemy);
+        if let Some(state) = self.finish_if_no_moves(&current_player, &enemy) {
+            return Ok(state);
+        }
 
-        self.switch_player()
+        let state = self.switch_player()?;
+        self.position_history.push(self.position_key());
+        Ok(self.adjudicate_draw().unwrap_or(state))
     }
 }
 
@@ -608,9 +1268,10 @@ mod test {
     use super::*;
 
     use itertools::Itertools;
+    use proptest::prelude::*;
 
     use crate::core::grid::WithGridIndex;
-    use crate::core::{FinishedState, PlayerPosition};
+    use crate::core::PlayerPosition;
 
     const FIRST_PLAYER: PlayerPosition = 0;
     const SECOND_PLAYER: PlayerPosition = 1;
@@ -665,11 +1326,11 @@ mod test {
         let mut chess = Chess::new();
         for row in 0..8 {
             for col in 0..8 {
-                chess.board[GridIndex::new(row, col)].take();
+                chess.set_cell(GridIndex::new(row, col), BoardCell(None));
             }
         }
         for &(idx, piece) in pieces {
-            chess.board[idx] = piece.into();
+            chess.set_cell(idx, piece.into());
         }
         chess
     }
@@ -792,6 +1453,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_legal_actions() {
+        let chess = Chess::new();
+        assert_eq!(chess.legal_actions().len(), 20);
+
+        // pawn move to the last row is listed for each promotion piece
+        let chess = Chess::from_fen("8/P6k/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let actions = chess.legal_actions();
+        assert_eq!(actions.len(), 7);
+        let a7 = GridIndex::new(1, 0);
+        let a8 = GridIndex::new(0, 0);
+        for kind in [
+            PieceKind::Queen,
+            PieceKind::Rook,
+            PieceKind::Bishop,
+            PieceKind::Knight,
+        ] {
+            assert!(actions.contains(&TurnData::with_promotion(a7, a8, kind)));
+        }
+
+        // nothing to do after checkmate
+        let chess = Chess::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(chess.legal_actions().is_empty());
+    }
+
     #[test]
     fn test_players_switch_turns() {
         let mut chess = Chess::new();
@@ -809,13 +1495,6 @@ mod test {
         assert_eq!(chess.get_enemy_player().unwrap().id, FIRST_PLAYER);
     }
 
-    #[test]
-    fn test_is_enemy() {
-        let chess = Chess::new();
-        assert!(chess.is_enemy(Team::White.get_king_initial_position(), SECOND_PLAYER));
-        assert!(chess.is_enemy(Team::Black.get_king_initial_position(), FIRST_PLAYER));
-    }
-
     #[test]
     fn test_get_move_type() {
         let [a1, b1, c1, d1, e1, _, g1, _]: [_; 8] = row_indices(7).try_into().unwrap();
@@ -827,11 +1506,11 @@ mod test {
         let mut chess = Chess::new();
         // clear space for black to castle right
         for idx in [f8, g8] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
         // clear space for white to castle left
         for idx in [b1, c1, d1] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         assert_eq!(
@@ -862,6 +1541,20 @@ mod test {
         assert_eq!(chess.get_move_type(TurnData::new(g1, f3)), MoveType::Other);
         assert_eq!(chess.get_move_type(TurnData::new(a7, a6)), MoveType::Other);
         assert_eq!(chess.get_move_type(TurnData::new(b8, a6)), MoveType::Other);
+
+        // pawns reaching the last row are promoted
+        let b7 = GridIndex::new(1, 1);
+        let b2 = GridIndex::new(6, 1);
+        chess.set_cell(b7, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(b2, Piece::create_pawn(SECOND_PLAYER).into());
+        assert_eq!(
+            chess.get_move_type(TurnData::new(b7, b8)),
+            MoveType::Promotion
+        );
+        assert_eq!(
+            chess.get_move_type(TurnData::new(b2, b1)),
+            MoveType::Promotion
+        );
     }
 
     #[test]
@@ -876,31 +1569,201 @@ mod test {
         let b1 = GridIndex::new(7, 1);
         let mut chess = Chess::new();
 
-        // white pawn has two options to move from initial position
-        itertools::assert_equal(sorted(chess.get_moves(a2).unwrap()), [a4, a3]);
-        // advance pawn by 1
-        chess.update(FIRST_PLAYER, TurnData::new(a2, a3)).unwrap();
+        // white pawn has two options to move from initial position
+        itertools::assert_equal(sorted(chess.get_moves(a2).unwrap()), [a4, a3]);
+        // advance pawn by 1
+        chess.update(FIRST_PLAYER, TurnData::new(a2, a3)).unwrap();
+
+        // black pawn has two options to move from initial position
+        itertools::assert_equal(sorted(chess.get_moves(b7).unwrap()), [b6, b5]);
+        // advance pawn by 2
+        chess.update(SECOND_PLAYER, TurnData::new(b7, b5)).unwrap();
+
+        // after pawn has moved it can only advance by one
+        itertools::assert_equal(chess.get_moves(a3).unwrap(), [a4]);
+        // advance pawn by 1
+        chess.update(FIRST_PLAYER, TurnData::new(a3, a4)).unwrap();
+
+        // black pawn now can capture white pawn diagonally in addition to moving forward
+        itertools::assert_equal(sorted(chess.get_moves(b5).unwrap()), [a4, b4]);
+        // capture white pawn
+        chess.update(SECOND_PLAYER, TurnData::new(b5, a4)).unwrap();
+
+        // black pawn still can advance
+        itertools::assert_equal(chess.get_moves(a4).unwrap(), [a3]);
+        // create obstacles and check that there is no options for the pawn to move
+        chess.update(FIRST_PLAYER, TurnData::new(b1, a3)).unwrap();
+        assert!(chess.get_moves(a4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pawn_promotion() {
+        let [a8, b8, ..]: [_; 8] = row_indices(0).try_into().unwrap();
+        let [_, b7, ..]: [_; 8] = row_indices(1).try_into().unwrap();
+        let [.., g2, _]: [_; 8] = row_indices(6).try_into().unwrap();
+        let [.., g1, h1]: [_; 8] = row_indices(7).try_into().unwrap();
+        let mut chess = create_board_kings_and_rooks_only();
+        chess.set_cell(b7, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(g2, Piece::create_pawn(SECOND_PLAYER).into());
+
+        // white pawn can advance or capture on the last row
+        itertools::assert_equal(sorted(chess.get_moves(b7).unwrap()), [a8, b8]);
+        // capture rook and promote to knight
+        chess
+            .update(
+                FIRST_PLAYER,
+                TurnData::with_promotion(b7, a8, PieceKind::Knight),
+            )
+            .unwrap();
+        assert_eq!(chess.board[b7], BoardCell(None));
+        assert_eq!(chess.board[a8], Piece::create_knight(FIRST_PLAYER).into());
+
+        // black pawn can advance or capture on the last row
+        itertools::assert_equal(sorted(chess.get_moves(g2).unwrap()), [g1, h1]);
+        // capture rook and promote to queen
+        chess
+            .update(
+                SECOND_PLAYER,
+                TurnData::with_promotion(g2, h1, PieceKind::Queen),
+            )
+            .unwrap();
+        assert_eq!(chess.board[g2], BoardCell(None));
+        assert_eq!(chess.board[h1], Piece::create_queen(SECOND_PLAYER).into());
+        // promoted queen puts white king in check
+        assert!(chess.is_in_check(FIRST_PLAYER));
+    }
+
+    #[test]
+    fn test_pawn_promotion_errors() {
+        let [_, b8, ..]: [_; 8] = row_indices(0).try_into().unwrap();
+        let [_, b7, ..]: [_; 8] = row_indices(1).try_into().unwrap();
+        let [_, b6, ..]: [_; 8] = row_indices(2).try_into().unwrap();
+        let mut chess = create_board_kings_and_rooks_only();
+        chess.set_cell(b6, Piece::create_pawn(FIRST_PLAYER).into());
+
+        // pawn cannot be promoted before reaching the last row
+        assert_eq!(
+            chess
+                .update(
+                    FIRST_PLAYER,
+                    TurnData::with_promotion(b6, b7, PieceKind::Queen)
+                )
+                .unwrap_err(),
+            GameError::invalid_move(format!("unable to promote piece moving {} to {}", b6, b7))
+        );
+        chess.move_piece(b6, b7).unwrap();
+
+        // pawn cannot stay a pawn on the last row
+        assert_eq!(
+            chess
+                .update(FIRST_PLAYER, TurnData::new(b7, b8))
+                .unwrap_err(),
+            GameError::invalid_move(format!(
+                "promotion piece is required to move {} to {}",
+                b7, b8
+            ))
+        );
+        // pawn cannot be promoted to king or pawn
+        for kind in [PieceKind::King, PieceKind::Pawn] {
+            assert_eq!(
+                chess
+                    .update(FIRST_PLAYER, TurnData::with_promotion(b7, b8, kind))
+                    .unwrap_err(),
+                GameError::invalid_move(format!("unable to promote pawn to {:?}", kind))
+            );
+        }
+        // failed attempts don't change the board
+        assert_eq!(chess.board[b7], Piece::create_pawn(FIRST_PLAYER).into());
+        assert_eq!(chess.board[b8], BoardCell(None));
+
+        // pieces other than pawn cannot be promoted
+        let rook_pos = Team::White.get_left_rook_initial_position();
+        assert_eq!(
+            chess
+                .update(
+                    FIRST_PLAYER,
+                    TurnData::with_promotion(rook_pos, rook_pos.move_up(1), PieceKind::Queen)
+                )
+                .unwrap_err(),
+            GameError::invalid_move(format!(
+                "unable to promote piece moving {} to {}",
+                rook_pos,
+                rook_pos.move_up(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_en_passant() {
+        let [a7, _, _, d7, ..]: [_; 8] = row_indices(1).try_into().unwrap();
+        let [a6, _, _, d6, e6, ..]: [_; 8] = row_indices(2).try_into().unwrap();
+        let [a5, _, _, d5, e5, ..]: [_; 8] = row_indices(3).try_into().unwrap();
+        let [_, _, _, _, e4, ..]: [_; 8] = row_indices(4).try_into().unwrap();
+        let [a2, _, _, _, e2, ..]: [_; 8] = row_indices(6).try_into().unwrap();
+        let a3 = a2.move_up(1);
+        let mut chess = Chess::new();
+
+        chess.update(FIRST_PLAYER, TurnData::new(e2, e4)).unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(a7, a6)).unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(e4, e5)).unwrap();
+        // black pawn passes over the cell attacked by white pawn
+        chess.update(SECOND_PLAYER, TurnData::new(d7, d5)).unwrap();
+        assert_eq!(chess.en_passant, Some(d6));
 
-        // black pawn has two options to move from initial position
-        itertools::assert_equal(sorted(chess.get_moves(b7).unwrap()), [b6, b5]);
-        // advance pawn by 2
-        chess.update(SECOND_PLAYER, TurnData::new(b7, b5)).unwrap();
+        // white pawn can capture black pawn en passant
+        itertools::assert_equal(sorted(chess.get_moves(e5).unwrap()), [d6, e6]);
+        assert_eq!(
+            chess.get_move_type(TurnData::new(e5, d6)),
+            MoveType::EnPassant
+        );
+        {
+            let mut chess = chess.clone();
+            chess.update(FIRST_PLAYER, TurnData::new(e5, d6)).unwrap();
+            assert_eq!(chess.board[d6], Piece::create_pawn(FIRST_PLAYER).into());
+            assert_eq!(chess.board[d5], BoardCell(None));
+            assert_eq!(chess.board[e5], BoardCell(None));
+            assert_eq!(chess.en_passant, None);
+        }
 
-        // after pawn has moved it can only advance by one
-        itertools::assert_equal(chess.get_moves(a3).unwrap(), [a4]);
-        // advance pawn by 1
-        chess.update(FIRST_PLAYER, TurnData::new(a3, a4)).unwrap();
+        // capture has to be made right after the double move
+        chess.update(FIRST_PLAYER, TurnData::new(a2, a3)).unwrap();
+        assert_eq!(chess.en_passant, None);
+        chess.update(SECOND_PLAYER, TurnData::new(a6, a5)).unwrap();
+        itertools::assert_equal(chess.get_moves(e5).unwrap(), [e6]);
+        assert_eq!(
+            chess
+                .update(FIRST_PLAYER, TurnData::new(e5, d6))
+                .unwrap_err(),
+            GameError::invalid_move(format!("unable to move {} to {}", e5, d6))
+        );
+    }
 
-        // black pawn now can capture white pawn diagonally in addition to moving forward
-        itertools::assert_equal(sorted(chess.get_moves(b5).unwrap()), [a4, b4]);
-        // capture white pawn
-        chess.update(SECOND_PLAYER, TurnData::new(b5, a4)).unwrap();
+    #[test]
+    fn test_en_passant_exposing_king_is_forbidden() {
+        let [a5, b5, c5, _, _, _, _, h5]: [_; 8] = row_indices(3).try_into().unwrap();
+        let [_, b6, c6, ..]: [_; 8] = row_indices(2).try_into().unwrap();
+        let [_, _, c7, ..]: [_; 8] = row_indices(1).try_into().unwrap();
+        let e8 = GridIndex::new(0, 4);
+        let mut chess = create_custom_board(&[
+            (a5, Piece::create_king(FIRST_PLAYER)),
+            (b5, Piece::create_pawn(FIRST_PLAYER)),
+            (c7, Piece::create_pawn(SECOND_PLAYER)),
+            (h5, Piece::create_rook(SECOND_PLAYER)),
+            (e8, Piece::create_king(SECOND_PLAYER)),
+        ]);
+        chess.update_king_position(FIRST_PLAYER, a5);
+        chess.switch_player().unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(c7, c5)).unwrap();
 
-        // black pawn still can advance
-        itertools::assert_equal(chess.get_moves(a4).unwrap(), [a3]);
-        // create obstacles and check that there is no options for the pawn to move
-        chess.update(FIRST_PLAYER, TurnData::new(b1, a3)).unwrap();
-        assert!(chess.get_moves(a4).unwrap().is_empty());
+        // capturing en passant would leave white king attacked by the rook
+        itertools::assert_equal(chess.get_moves(b5).unwrap(), [b6]);
+        assert_eq!(
+            chess
+                .update(FIRST_PLAYER, TurnData::new(b5, c6))
+                .unwrap_err(),
+            GameError::invalid_move(format!("unable to move {} to {}", b5, c6))
+        );
+        assert_eq!(chess.board[c5], Piece::create_pawn(SECOND_PLAYER).into());
     }
 
     /// - pawn protecting from side cannot move
@@ -918,36 +1781,36 @@ mod test {
         let mut chess = create_board_kings_and_rooks_only();
 
         // add threatening rook
-        chess.board[a1] = Piece::create_rook(SECOND_PLAYER).into();
+        chess.set_cell(a1, Piece::create_rook(SECOND_PLAYER).into());
 
         // add protecting pawn
-        chess.board[c1] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(c1, Piece::create_pawn(FIRST_PLAYER).into());
         // white pawn cannot move because it would put king in check
         assert!(chess.get_moves(c1).unwrap().is_empty());
 
         // add protecting knight
-        chess.board[c1] = Piece::create_knight(FIRST_PLAYER).into();
+        chess.set_cell(c1, Piece::create_knight(FIRST_PLAYER).into());
         // white knight cannot move because it would put king in check
         assert!(chess.get_moves(c1).unwrap().is_empty());
 
         // add protecting rook
-        chess.board[c1] = Piece::create_rook(FIRST_PLAYER).into();
+        chess.set_cell(c1, Piece::create_rook(FIRST_PLAYER).into());
         // white rook can move only on the threat line
         itertools::assert_equal(sorted(chess.get_moves(c1).unwrap()), [a1, b1, d1]);
 
         // cleanup
-        chess.board[c1].take();
-        chess.board[a1].take();
+        chess.set_cell(c1, BoardCell(None));
+        chess.set_cell(a1, BoardCell(None));
         // new threatening bishop
-        chess.board[a5] = Piece::create_bishop(SECOND_PLAYER).into();
+        chess.set_cell(a5, Piece::create_bishop(SECOND_PLAYER).into());
 
         // add protecting bishop
-        chess.board[c3] = Piece::create_bishop(FIRST_PLAYER).into();
+        chess.set_cell(c3, Piece::create_bishop(FIRST_PLAYER).into());
         // white bishop can move only on the threat line
         itertools::assert_equal(sorted(chess.get_moves(c3).unwrap()), [a5, b4, d2]);
 
         // add protecting queen
-        chess.board[c3] = Piece::create_queen(FIRST_PLAYER).into();
+        chess.set_cell(c3, Piece::create_queen(FIRST_PLAYER).into());
         // white bishop can move only on the threat line
         itertools::assert_equal(sorted(chess.get_moves(c3).unwrap()), [a5, b4, d2]);
     }
@@ -988,9 +1851,9 @@ mod test {
         // white king has 3 options to move
         itertools::assert_equal(sorted(chess.get_moves(g2).unwrap()), [f2, f1, g1]);
         // create obstacles and check that there is no options for the king to move
-        chess.board[f2] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[f1] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[g1] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(f2, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(f1, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(g1, Piece::create_pawn(FIRST_PLAYER).into());
         assert!(chess.get_moves(g2).unwrap().is_empty());
     }
 
@@ -1001,7 +1864,7 @@ mod test {
         let [_, _, _, _, e8, f8, g8, _]: [_; 8] = row_indices(0).try_into().unwrap();
         let [_, _, _, _, e7, f7, _, _]: [_; 8] = row_indices(1).try_into().unwrap();
         let mut chess = create_board_kings_and_rooks_only();
-        chess.board[g1] = Piece::create_knight(FIRST_PLAYER).into();
+        chess.set_cell(g1, Piece::create_knight(FIRST_PLAYER).into());
 
         // white king has 5 options to move and 1 options for castling
         // because g1 is occupied by knight
@@ -1027,7 +1890,7 @@ mod test {
         let [_, _, c4, d4, e4, _, _, h4]: [_; 8] = row_indices(4).try_into().unwrap();
         let [a5, _, c5, _, e5, _, g5, _]: [_; 8] = row_indices(3).try_into().unwrap();
         let mut chess = create_board_kings_and_rooks_only();
-        chess.board[g1] = Piece::create_knight(FIRST_PLAYER).into();
+        chess.set_cell(g1, Piece::create_knight(FIRST_PLAYER).into());
 
         itertools::assert_equal(sorted(chess.get_moves(g1).unwrap()), [f3, h3, e2]);
         chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
@@ -1055,11 +1918,11 @@ mod test {
 
         itertools::assert_equal(sorted(chess.get_moves(b3).unwrap()), [a5, c5, d4, d2, c1]);
         // create obstacles and check that there is no options for the knight to move
-        chess.board[a5] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[c5] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d4] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d2] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[c1] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(a5, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(c5, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d4, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d2, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(c1, Piece::create_pawn(FIRST_PLAYER).into());
         assert!(chess.get_moves(b3).unwrap().is_empty());
     }
 
@@ -1070,7 +1933,7 @@ mod test {
         let e6 = GridIndex::new(2, 4);
         let c8 = GridIndex::new(0, 2);
         let mut chess = create_board_kings_and_rooks_only();
-        chess.board[f1] = Piece::create_bishop(FIRST_PLAYER).into();
+        chess.set_cell(f1, Piece::create_bishop(FIRST_PLAYER).into());
 
         itertools::assert_equal(
             sorted(chess.get_moves(f1).unwrap()),
@@ -1104,10 +1967,22 @@ mod test {
             sorted(diagonal_moves(e6)),
         );
         // create obstacles and check that there is no options for the bishop to move
-        chess.board[e6.move_up(1).move_left(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[e6.move_up(1).move_right(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[e6.move_down(1).move_left(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[e6.move_down(1).move_right(1)] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(
+            e6.move_up(1).move_left(1),
+            Piece::create_pawn(FIRST_PLAYER).into(),
+        );
+        chess.set_cell(
+            e6.move_up(1).move_right(1),
+            Piece::create_pawn(FIRST_PLAYER).into(),
+        );
+        chess.set_cell(
+            e6.move_down(1).move_left(1),
+            Piece::create_pawn(FIRST_PLAYER).into(),
+        );
+        chess.set_cell(
+            e6.move_down(1).move_right(1),
+            Piece::create_pawn(FIRST_PLAYER).into(),
+        );
         assert!(chess.get_moves(e6).unwrap().is_empty());
     }
 
@@ -1143,10 +2018,10 @@ mod test {
             sorted(orthogonal_moves(d4)),
         );
         // create obstacles and check that there is no options for the rook to move
-        chess.board[d4.move_up(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d4.move_down(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d4.move_right(1)] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d4.move_left(1)] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(d4.move_up(1), Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d4.move_down(1), Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d4.move_right(1), Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d4.move_left(1), Piece::create_pawn(FIRST_PLAYER).into());
         assert!(chess.get_moves(d4).unwrap().is_empty());
     }
 
@@ -1156,7 +2031,7 @@ mod test {
         let [_, b2, c2, d2, ..]: [_; 8] = row_indices(6).try_into().unwrap();
         let [_, b3, c3, d3, ..]: [_; 8] = row_indices(5).try_into().unwrap();
         let mut chess = create_board_kings_and_rooks_only();
-        chess.board[d1] = Piece::create_queen(FIRST_PLAYER).into();
+        chess.set_cell(d1, Piece::create_queen(FIRST_PLAYER).into());
 
         itertools::assert_equal(
             sorted(chess.get_moves(d1).unwrap()),
@@ -1171,16 +2046,16 @@ mod test {
             sorted(orthogonal_moves(c2).into_iter().chain(diagonal_moves(c2))),
         );
         // create obstacles and check that there is only 3 options left for the queen to move
-        chess.board[b2] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[b3] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[c3] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d3] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d2] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(b2, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(b3, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(c3, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d3, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d2, Piece::create_pawn(FIRST_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_moves(c2).unwrap()), [b1, c1, d1]);
         // close rest of the options
-        chess.board[b1] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[c1] = Piece::create_pawn(FIRST_PLAYER).into();
-        chess.board[d1] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(b1, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(c1, Piece::create_pawn(FIRST_PLAYER).into());
+        chess.set_cell(d1, Piece::create_pawn(FIRST_PLAYER).into());
         assert!(chess.get_moves(c2).unwrap().is_empty());
     }
 
@@ -1208,6 +2083,7 @@ mod test {
                     .castle_options,
                 CastleOptions::none()
             );
+            assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(turn.to));
         }
         {
             // left castling disables castling
@@ -1231,6 +2107,7 @@ mod test {
                     .castle_options,
                 CastleOptions::none()
             );
+            assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(turn.to));
         }
         {
             // right castling disables castling
@@ -1254,6 +2131,7 @@ mod test {
                     .castle_options,
                 CastleOptions::none()
             );
+            assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(turn.to));
         }
     }
 
@@ -1314,6 +2192,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_board_mut_updates_bitboards() {
+        let e1 = GridIndex::new(7, 4);
+        let d2 = GridIndex::new(6, 3);
+        let d3 = GridIndex::new(5, 3);
+        let mut chess = create_board_kings_and_rooks_only();
+        {
+            let mut board = chess.board_mut();
+            board[d2] = board[e1];
+            board[e1] = BoardCell(None);
+            board[d3] = Piece::create_rook(SECOND_PLAYER).into();
+        }
+        assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(d2));
+        let player = *chess.players.find(FIRST_PLAYER).unwrap();
+        chess.update_check(&player);
+        assert_eq!(chess.player_state[&FIRST_PLAYER].check, vec![d3]);
+        assert!(chess.get_moves(d2).unwrap().contains(&d3));
+    }
+
+    #[test]
+    fn test_rook_capture_disables_castling() {
+        // capture the rook on its initial cell and check that castling on its side is disabled
+        let mut chess = create_board_kings_and_rooks_only();
+        let turn = TurnData::new(
+            Team::White.get_left_rook_initial_position(),
+            Team::Black.get_left_rook_initial_position(),
+        );
+        chess.update(FIRST_PLAYER, turn).unwrap();
+        assert_eq!(
+            chess
+                .player_state
+                .get(&SECOND_PLAYER)
+                .unwrap()
+                .castle_options,
+            CastleOptions {
+                left: false,
+                right: true
+            }
+        );
+    }
+
     #[test]
     fn test_castling() {
         let [_, b1, c1, d1, e1, f1, g1, _]: [_; 8] = row_indices(7).try_into().unwrap();
@@ -1323,7 +2242,7 @@ mod test {
             let mut chess = Chess::new();
             // clear space between kings and right rooks
             for idx in [f1, g1, f8, g8] {
-                chess.board[idx].take();
+                chess.set_cell(idx, BoardCell(None));
             }
 
             chess.update(FIRST_PLAYER, TurnData::new(e1, g1)).unwrap();
@@ -1339,7 +2258,7 @@ mod test {
             let mut chess = Chess::new();
             // clear space between kings and left rooks
             for idx in [b1, c1, d1, b8, c8, d8] {
-                chess.board[idx].take();
+                chess.set_cell(idx, BoardCell(None));
             }
 
             chess.update(FIRST_PLAYER, TurnData::new(e1, c1)).unwrap();
@@ -1366,6 +2285,7 @@ mod test {
     fn test_can_castle() {
         let [_, b1, _, _, _, _, g1, _]: [_; 8] = row_indices(7).try_into().unwrap();
         let [a8, _, c8, _, _, _, g8, h8]: [_; 8] = row_indices(0).try_into().unwrap();
+        let e4 = GridIndex::new(4, 4);
         let mut chess = create_board_kings_and_rooks_only();
 
         // castling enabled
@@ -1410,11 +2330,11 @@ mod test {
         );
 
         // cleanup
-        chess.board[c8].take();
-        chess.board[g8].take();
+        chess.set_cell(c8, BoardCell(None));
+        chess.set_cell(g8, BoardCell(None));
 
         // white knight at b1 forbids left castling for white king
-        chess.board[b1] = Piece::create_knight(FIRST_PLAYER).into();
+        chess.set_cell(b1, Piece::create_knight(FIRST_PLAYER).into());
         assert_eq!(
             chess.can_castle(FIRST_PLAYER).unwrap(),
             CastleOptions {
@@ -1434,15 +2354,28 @@ mod test {
         );
 
         // white knights at b1 and g1 forbid castling for both sides for white king
-        chess.board[b1] = Piece::create_knight(FIRST_PLAYER).into();
+        chess.set_cell(b1, Piece::create_knight(FIRST_PLAYER).into());
+        assert_eq!(
+            chess.can_castle(FIRST_PLAYER).unwrap(),
+            CastleOptions::none()
+        );
+
+        // cleanup
+        chess.set_cell(b1, BoardCell(None));
+        chess.set_cell(g1, BoardCell(None));
+
+        // king in check can't castle
+        chess.set_cell(e4, Piece::create_rook(SECOND_PLAYER).into());
+        let player = *chess.players.find(FIRST_PLAYER).unwrap();
+        chess.update_check(&player);
         assert_eq!(
             chess.can_castle(FIRST_PLAYER).unwrap(),
             CastleOptions::none()
         );
 
         // cleanup
-        chess.board[b1].take();
-        chess.board[g1].take();
+        chess.set_cell(e4, BoardCell(None));
+        chess.update_check(&player);
 
         // castling is still enabled
         assert_eq!(
@@ -1476,7 +2409,7 @@ mod test {
         let a8 = GridIndex::new(0, 0);
         let d8 = GridIndex::new(0, 3);
         let mut chess = create_board_kings_and_rooks_only();
-        chess.board[d8] = Piece::create_queen(SECOND_PLAYER).into();
+        chess.set_cell(d8, Piece::create_queen(SECOND_PLAYER).into());
 
         // white king is not in check
         chess.update_check(&PlayerData::new(FIRST_PLAYER, Team::White));
@@ -1544,44 +2477,44 @@ mod test {
         let player2 = PlayerData::new(SECOND_PLAYER, Team::Black);
 
         // pawn on the same column is not a threat
-        chess.board[e2] = Piece::create_pawn(SECOND_PLAYER).into();
+        chess.set_cell(e2, Piece::create_pawn(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
         // pawn on the adjacent column is a threat
-        chess.board[d2] = Piece::create_pawn(SECOND_PLAYER).into();
+        chess.set_cell(d2, Piece::create_pawn(SECOND_PLAYER).into());
         itertools::assert_equal(chess.get_attack_threats(e1, &player1), [d2]);
         // add another threatening pawn
-        chess.board[f2] = Piece::create_pawn(SECOND_PLAYER).into();
+        chess.set_cell(f2, Piece::create_pawn(SECOND_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e1, &player1)), [d2, f2]);
 
         // pawn on the same column is not a threat
-        chess.board[e7] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(e7, Piece::create_pawn(FIRST_PLAYER).into());
         assert!(chess.get_attack_threats(e8, &player2).is_empty());
         // pawn on the adjacent column is a threat
-        chess.board[d7] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(d7, Piece::create_pawn(FIRST_PLAYER).into());
         itertools::assert_equal(chess.get_attack_threats(e8, &player2), [d7]);
         // add another threatening pawn
-        chess.board[f7] = Piece::create_pawn(FIRST_PLAYER).into();
+        chess.set_cell(f7, Piece::create_pawn(FIRST_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e8, &player2)), [d7, f7]);
 
         // cleanup
         for idx in [e2, d2, f2, e7, d7, f7] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         // create knights in front of a white king and check that none of them is a threat
-        chess.board[e2] = Piece::create_knight(SECOND_PLAYER).into();
-        chess.board[d2] = Piece::create_knight(SECOND_PLAYER).into();
-        chess.board[f2] = Piece::create_knight(SECOND_PLAYER).into();
+        chess.set_cell(e2, Piece::create_knight(SECOND_PLAYER).into());
+        chess.set_cell(d2, Piece::create_knight(SECOND_PLAYER).into());
+        chess.set_cell(f2, Piece::create_knight(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
 
         // knight on c2 is a threat
-        chess.board[c2] = Piece::create_knight(SECOND_PLAYER).into();
+        chess.set_cell(c2, Piece::create_knight(SECOND_PLAYER).into());
         itertools::assert_equal(chess.get_attack_threats(e1, &player1), [c2]);
 
         // more knights to the god of knights
-        chess.board[d3] = Piece::create_knight(SECOND_PLAYER).into();
-        chess.board[f3] = Piece::create_knight(SECOND_PLAYER).into();
-        chess.board[g2] = Piece::create_knight(SECOND_PLAYER).into();
+        chess.set_cell(d3, Piece::create_knight(SECOND_PLAYER).into());
+        chess.set_cell(f3, Piece::create_knight(SECOND_PLAYER).into());
+        chess.set_cell(g2, Piece::create_knight(SECOND_PLAYER).into());
         itertools::assert_equal(
             sorted(chess.get_attack_threats(e1, &player1)),
             [d3, f3, c2, g2],
@@ -1589,19 +2522,19 @@ mod test {
 
         // cleanup
         for idx in [c2, d2, e2, f2, g2, d3, f3] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         // create black king that is far from the white one and is not a threat
-        chess.board[e3] = Piece::create_king(SECOND_PLAYER).into();
+        chess.set_cell(e3, Piece::create_king(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
 
         // create threatening black kings in every possible position
-        chess.board[d1] = Piece::create_king(SECOND_PLAYER).into();
-        chess.board[d2] = Piece::create_king(SECOND_PLAYER).into();
-        chess.board[e2] = Piece::create_king(SECOND_PLAYER).into();
-        chess.board[f2] = Piece::create_king(SECOND_PLAYER).into();
-        chess.board[f1] = Piece::create_king(SECOND_PLAYER).into();
+        chess.set_cell(d1, Piece::create_king(SECOND_PLAYER).into());
+        chess.set_cell(d2, Piece::create_king(SECOND_PLAYER).into());
+        chess.set_cell(e2, Piece::create_king(SECOND_PLAYER).into());
+        chess.set_cell(f2, Piece::create_king(SECOND_PLAYER).into());
+        chess.set_cell(f1, Piece::create_king(SECOND_PLAYER).into());
         itertools::assert_equal(
             sorted(chess.get_attack_threats(e1, &player1)),
             [d2, e2, f2, d1, f1],
@@ -1609,70 +2542,70 @@ mod test {
 
         // cleanup
         for idx in [d1, f1, d2, e2, f2, d3] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         // create bishops that are positioned orthogonally and aren't threatening white king
-        chess.board[d1] = Piece::create_bishop(SECOND_PLAYER).into();
-        chess.board[e2] = Piece::create_bishop(SECOND_PLAYER).into();
-        chess.board[f1] = Piece::create_bishop(SECOND_PLAYER).into();
+        chess.set_cell(d1, Piece::create_bishop(SECOND_PLAYER).into());
+        chess.set_cell(e2, Piece::create_bishop(SECOND_PLAYER).into());
+        chess.set_cell(f1, Piece::create_bishop(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
 
         // create two threatening black bishops
-        chess.board[c3] = Piece::create_bishop(SECOND_PLAYER).into();
-        chess.board[g3] = Piece::create_bishop(SECOND_PLAYER).into();
+        chess.set_cell(c3, Piece::create_bishop(SECOND_PLAYER).into());
+        chess.set_cell(g3, Piece::create_bishop(SECOND_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e1, &player1)), [c3, g3]);
 
         // create one threatening bishop closer to king that one of the other two
-        chess.board[d2] = Piece::create_bishop(SECOND_PLAYER).into();
+        chess.set_cell(d2, Piece::create_bishop(SECOND_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e1, &player1)), [g3, d2]);
 
         // cleanup
         for idx in [d1, f1, d2, e2, c3, g3] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         // create rooks that are positioned diagonally and aren't threatening white king
-        chess.board[d2] = Piece::create_rook(SECOND_PLAYER).into();
-        chess.board[f2] = Piece::create_rook(SECOND_PLAYER).into();
+        chess.set_cell(d2, Piece::create_rook(SECOND_PLAYER).into());
+        chess.set_cell(f2, Piece::create_rook(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
 
         // create three threatening black rooks
-        chess.board[c1] = Piece::create_rook(SECOND_PLAYER).into();
-        chess.board[g1] = Piece::create_rook(SECOND_PLAYER).into();
-        chess.board[e3] = Piece::create_rook(SECOND_PLAYER).into();
+        chess.set_cell(c1, Piece::create_rook(SECOND_PLAYER).into());
+        chess.set_cell(g1, Piece::create_rook(SECOND_PLAYER).into());
+        chess.set_cell(e3, Piece::create_rook(SECOND_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e1, &player1)), [e3, c1, g1]);
 
         // create one threatening rook closer to king that one of the other three
-        chess.board[d1] = Piece::create_rook(SECOND_PLAYER).into();
+        chess.set_cell(d1, Piece::create_rook(SECOND_PLAYER).into());
         itertools::assert_equal(sorted(chess.get_attack_threats(e1, &player1)), [e3, d1, g1]);
 
         // cleanup
         for idx in [c1, d1, g1, d2, f2, e3] {
-            chess.board[idx].take();
+            chess.set_cell(idx, BoardCell(None));
         }
 
         // create 4 non-threatening queens
-        chess.board[c2] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[g2] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[d3] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[f3] = Piece::create_queen(SECOND_PLAYER).into();
+        chess.set_cell(c2, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(g2, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(d3, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(f3, Piece::create_queen(SECOND_PLAYER).into());
         assert!(chess.get_attack_threats(e1, &player1).is_empty());
 
         // create 5 threatening black queens
-        chess.board[c3] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[g3] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[c1] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[g1] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[e3] = Piece::create_queen(SECOND_PLAYER).into();
+        chess.set_cell(c3, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(g3, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(c1, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(g1, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(e3, Piece::create_queen(SECOND_PLAYER).into());
         itertools::assert_equal(
             sorted(chess.get_attack_threats(e1, &player1)),
             [c3, e3, g3, c1, g1],
         );
 
         // create two threatening queens closer to king that two of the other five
-        chess.board[f1] = Piece::create_queen(SECOND_PLAYER).into();
-        chess.board[e2] = Piece::create_queen(SECOND_PLAYER).into();
+        chess.set_cell(f1, Piece::create_queen(SECOND_PLAYER).into());
+        chess.set_cell(e2, Piece::create_queen(SECOND_PLAYER).into());
         itertools::assert_equal(
             sorted(chess.get_attack_threats(e1, &player1)),
             [c3, g3, e2, c1, f1],
@@ -1702,7 +2635,7 @@ mod test {
 
         for row in 0..8 {
             for col in 0..8 {
-                chess.board[GridIndex::new(row, col)].take();
+                chess.set_cell(GridIndex::new(row, col), BoardCell(None));
             }
         }
         itertools::assert_equal(chess.find_pieces_positions(FIRST_PLAYER), []);
@@ -1740,7 +2673,10 @@ mod test {
         chess.move_piece(a1, a7).unwrap();
         assert_eq!(
             chess.update_state().unwrap(),
-            GameState::Finished(FinishedState::Win(FIRST_PLAYER))
+            GameState::Finished(FinishedState::Win(
+                FIRST_PLAYER,
+                TerminationReason::Checkmate
+            ))
         );
     }
 
@@ -1756,7 +2692,7 @@ mod test {
         let mut chess = create_board_kings_and_rooks_only();
 
         // move king to the corner and delete all other black pieces
-        chess.board[a8].take();
+        chess.set_cell(a8, BoardCell(None));
         chess.move_piece(e8, h8).unwrap();
         chess.update_king_position(SECOND_PLAYER, h8);
         // rooks leave black king no option to move, but it's not in check -> stalemate
@@ -1764,8 +2700,374 @@ mod test {
         chess.move_piece(a1, a7).unwrap();
         assert_eq!(
             chess.update_state().unwrap(),
-            GameState::Finished(FinishedState::Draw)
+            GameState::Finished(FinishedState::Draw(TerminationReason::Stalemate))
+        );
+    }
+
+    #[test]
+    fn test_update_state_sets_draw_on_insufficient_material() {
+        let a1 = GridIndex::new(7, 0);
+        let b1 = GridIndex::new(7, 1);
+        let c1 = GridIndex::new(7, 2);
+        let e1 = GridIndex::new(7, 4);
+        let f1 = GridIndex::new(7, 5);
+        let a2 = GridIndex::new(6, 0);
+        let a3 = GridIndex::new(5, 0);
+        let c8 = GridIndex::new(0, 2);
+        let e8 = GridIndex::new(0, 4);
+        let f8 = GridIndex::new(0, 5);
+        let g8 = GridIndex::new(0, 6);
+        let kings = [
+            (e1, Piece::create_king(FIRST_PLAYER)),
+            (e8, Piece::create_king(SECOND_PLAYER)),
+        ];
+        let draw =
+            GameState::Finished(FinishedState::Draw(TerminationReason::InsufficientMaterial));
+
+        let dead_positions = [
+            // king against king
+            vec![],
+            // king and bishop against king
+            vec![(c1, Piece::create_bishop(FIRST_PLAYER))],
+            // king and knight against king
+            vec![(b1, Piece::create_knight(FIRST_PLAYER))],
+            // king against king and bishop
+            vec![(f8, Piece::create_bishop(SECOND_PLAYER))],
+            // king against king and knight
+            vec![(g8, Piece::create_knight(SECOND_PLAYER))],
+            // bishops on the same cell color
+            vec![
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+                (f8, Piece::create_bishop(SECOND_PLAYER)),
+            ],
+            // bishops of one side on the same cell color
+            vec![
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+                (a3, Piece::create_bishop(FIRST_PLAYER)),
+            ],
+            vec![
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+                (a3, Piece::create_bishop(FIRST_PLAYER)),
+                (f8, Piece::create_bishop(SECOND_PLAYER)),
+            ],
+        ];
+        for pieces in dead_positions {
+            let mut chess = create_custom_board(&[kings.as_slice(), &pieces].concat());
+            assert_eq!(chess.update_state().unwrap(), draw);
+        }
+
+        let alive_positions = [
+            // bishops on different cell colors
+            vec![
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+                (c8, Piece::create_bishop(SECOND_PLAYER)),
+            ],
+            // bishops of one side on different cell colors
+            vec![
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+                (f1, Piece::create_bishop(FIRST_PLAYER)),
+            ],
+            vec![(a1, Piece::create_rook(FIRST_PLAYER))],
+            vec![(a2, Piece::create_pawn(FIRST_PLAYER))],
+            vec![
+                (b1, Piece::create_knight(FIRST_PLAYER)),
+                (c1, Piece::create_bishop(FIRST_PLAYER)),
+            ],
+        ];
+        for pieces in alive_positions {
+            let mut chess = create_custom_board(&[kings.as_slice(), &pieces].concat());
+            assert_eq!(
+                chess.update_state().unwrap(),
+                GameState::Turn(SECOND_PLAYER)
+            );
+        }
+    }
+
+    #[test]
+    fn test_threefold_repetition() {
+        let f3 = GridIndex::new(5, 5);
+        let g1 = GridIndex::new(7, 6);
+        let f6 = GridIndex::new(2, 5);
+        let g8 = GridIndex::new(0, 6);
+        let mut chess = Chess::new();
+
+        // knights go forth and back twice, initial position occurs for the third time
+        for _ in 0..2 {
+            assert!(!chess.is_finished());
+            chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+            chess.update(SECOND_PLAYER, TurnData::new(g8, f6)).unwrap();
+            chess.update(FIRST_PLAYER, TurnData::new(f3, g1)).unwrap();
+            chess.update(SECOND_PLAYER, TurnData::new(f6, g8)).unwrap();
+        }
+        assert_eq!(
+            chess.state(),
+            GameState::Finished(FinishedState::Draw(TerminationReason::ThreefoldRepetition))
+        );
+    }
+
+    #[test]
+    fn test_repetition_after_double_pawn_push() {
+        let e2 = GridIndex::new(6, 4);
+        let e4 = GridIndex::new(4, 4);
+        let e7 = GridIndex::new(1, 4);
+        let e5 = GridIndex::new(3, 4);
+        let f3 = GridIndex::new(5, 5);
+        let g1 = GridIndex::new(7, 6);
+        let c6 = GridIndex::new(2, 2);
+        let b8 = GridIndex::new(0, 1);
+        let mut chess = Chess::new();
+
+        chess.update(FIRST_PLAYER, TurnData::new(e2, e4)).unwrap();
+        // e5 can't be captured en passant, so the position is the same once knights return
+        chess.update(SECOND_PLAYER, TurnData::new(e7, e5)).unwrap();
+        for _ in 0..2 {
+            assert!(!chess.is_finished());
+            chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+            chess.update(SECOND_PLAYER, TurnData::new(b8, c6)).unwrap();
+            chess.update(FIRST_PLAYER, TurnData::new(f3, g1)).unwrap();
+            chess.update(SECOND_PLAYER, TurnData::new(c6, b8)).unwrap();
+        }
+        assert_eq!(
+            chess.state(),
+            GameState::Finished(FinishedState::Draw(TerminationReason::ThreefoldRepetition))
+        );
+    }
+
+    #[test]
+    fn test_pawn_move_resets_repetitions() {
+        let f3 = GridIndex::new(5, 5);
+        let g1 = GridIndex::new(7, 6);
+        let f6 = GridIndex::new(2, 5);
+        let g8 = GridIndex::new(0, 6);
+        let a2 = GridIndex::new(6, 0);
+        let a3 = GridIndex::new(5, 0);
+        let mut chess = Chess::new();
+
+        chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(g8, f6)).unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(f3, g1)).unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(f6, g8)).unwrap();
+        // pawn move makes previous positions unreachable
+        chess.update(FIRST_PLAYER, TurnData::new(a2, a3)).unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(g8, f6)).unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+        chess.update(SECOND_PLAYER, TurnData::new(f6, g8)).unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(f3, g1)).unwrap();
+        assert_eq!(chess.state(), GameState::Turn(SECOND_PLAYER));
+    }
+
+    #[test]
+    fn test_fifty_move_rule() {
+        let e2 = GridIndex::new(6, 4);
+        let e3 = GridIndex::new(5, 4);
+        let f3 = GridIndex::new(5, 5);
+        let g1 = GridIndex::new(7, 6);
+        let mut chess = Chess::new();
+
+        // pawn move resets the counter
+        chess.halfmove_clock = FIFTY_MOVE_RULE_TURNS - 1;
+        let mut clone = chess.clone();
+        clone.update(FIRST_PLAYER, TurnData::new(e2, e3)).unwrap();
+        assert_eq!(clone.halfmove_clock, 0);
+        assert_eq!(clone.state(), GameState::Turn(SECOND_PLAYER));
+
+        // hundredth turn without captures and pawn moves ends the game
+        chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+        assert_eq!(
+            chess.state(),
+            GameState::Finished(FinishedState::Draw(TerminationReason::FiftyMoveRule))
+        );
+    }
+
+    #[test]
+    fn test_bitboards_follow_board() {
+        let e5 = GridIndex::new(3, 4);
+        let d6 = GridIndex::new(2, 3);
+        let a7 = GridIndex::new(1, 0);
+        let b8 = GridIndex::new(0, 1);
+        let e1 = GridIndex::new(7, 4);
+        let c1 = GridIndex::new(7, 2);
+        let e8 = GridIndex::new(0, 4);
+        let f8 = GridIndex::new(0, 5);
+
+        // en passant, promotion with capture, castling and king move
+        let mut chess = Chess::from_fen("rn2k3/P7/8/3pP3/8/8/8/R3K3 w Q d6 0 1").unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(e5, d6)).unwrap();
+        assert_eq!(chess.bitboards, Bitboards::from_grid(&chess.board));
+        chess.update(SECOND_PLAYER, TurnData::new(e8, f8)).unwrap();
+        assert_eq!(chess.bitboards, Bitboards::from_grid(&chess.board));
+        chess
+            .update(
+                FIRST_PLAYER,
+                TurnData::with_promotion(a7, b8, PieceKind::Queen),
+            )
+            .unwrap();
+        assert_eq!(chess.bitboards, Bitboards::from_grid(&chess.board));
+        chess.switch_player().unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(e1, c1)).unwrap();
+        assert_eq!(chess.bitboards, Bitboards::from_grid(&chess.board));
+    }
+
+    #[test]
+    fn test_fen_initial_position() {
+        assert_eq!(Chess::new().to_fen(), fen::INITIAL_POSITION);
+        let chess = Chess::from_fen(fen::INITIAL_POSITION).unwrap();
+        assert_eq!(chess.get_board_content(), Chess::new().get_board_content());
+        assert_eq!(chess.state(), GameState::Turn(FIRST_PLAYER));
+        assert_eq!(chess.to_fen(), fen::INITIAL_POSITION);
+    }
+
+    #[test]
+    fn test_to_fen_follows_the_game() {
+        let e2 = GridIndex::new(6, 4);
+        let e4 = GridIndex::new(4, 4);
+        let c7 = GridIndex::new(1, 2);
+        let c5 = GridIndex::new(3, 2);
+        let g1 = GridIndex::new(7, 6);
+        let f3 = GridIndex::new(5, 5);
+        let mut chess = Chess::new();
+
+        chess.update(FIRST_PLAYER, TurnData::new(e2, e4)).unwrap();
+        assert_eq!(
+            chess.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        chess.update(SECOND_PLAYER, TurnData::new(c7, c5)).unwrap();
+        assert_eq!(
+            chess.to_fen(),
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"
         );
+        chess.update(FIRST_PLAYER, TurnData::new(g1, f3)).unwrap();
+        assert_eq!(
+            chess.to_fen(),
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+        );
+    }
+
+    #[test]
+    fn test_from_fen() {
+        let e1 = GridIndex::new(7, 4);
+        let e2 = GridIndex::new(6, 4);
+        let d5 = GridIndex::new(3, 3);
+        let e5 = GridIndex::new(3, 4);
+        let d6 = GridIndex::new(2, 3);
+        let e8 = GridIndex::new(0, 4);
+
+        let positions = [
+            "4k3/8/8/8/8/8/8/4K2R w K - 0 1",
+            "r3k3/8/8/8/8/8/8/4K3 b q - 12 40",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2",
+        ];
+        for position in positions {
+            assert_eq!(Chess::from_fen(position).unwrap().to_fen(), position);
+        }
+
+        // counters are optional
+        let chess = Chess::from_fen("r3k3/8/8/8/8/8/8/4K3 b q -").unwrap();
+        assert_eq!(chess.state(), GameState::Turn(SECOND_PLAYER));
+        assert_eq!(chess.get_king_position(SECOND_PLAYER), Some(e8));
+        assert_eq!(chess.to_fen(), "r3k3/8/8/8/8/8/8/4K3 b q - 0 1");
+
+        // en passant is available
+        let mut chess = Chess::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2").unwrap();
+        chess.update(FIRST_PLAYER, TurnData::new(e5, d6)).unwrap();
+        assert!(chess.board[d5].is_none());
+
+        // check is detected
+        let chess = Chess::from_fen("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+        assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(e1));
+        assert!(chess.is_in_check(FIRST_PLAYER));
+        assert_eq!(chess.player_state[&FIRST_PLAYER].check, vec![e2]);
+
+        // finished positions are evaluated right away
+        let states = [
+            (
+                "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
+                FinishedState::Win(SECOND_PLAYER, TerminationReason::Checkmate),
+            ),
+            (
+                "7k/5Q2/6K1/8/8/8/8/8 b - - 0 1",
+                FinishedState::Draw(TerminationReason::Stalemate),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+                FinishedState::Draw(TerminationReason::InsufficientMaterial),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K2R w - - 100 80",
+                FinishedState::Draw(TerminationReason::FiftyMoveRule),
+            ),
+        ];
+        for (position, state) in states {
+            let chess = Chess::from_fen(position).unwrap();
+            assert_eq!(chess.state(), GameState::Finished(state), "{}", position);
+            assert!(chess.legal_moves().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_from_fen_errors() {
+        let errors = [
+            (
+                "8/8/8/8/8/8/8/8 w - -",
+                FenError::invalid_piece_placement("White must have exactly one king"),
+            ),
+            (
+                "4k3/8/8/8/8/8/8 w - - 0 1",
+                FenError::invalid_piece_placement("expected 8 ranks, found 7"),
+            ),
+            (
+                "4k3/9/8/8/8/8/8/4K3 w - - 0 1",
+                FenError::invalid_piece_placement("unknown piece 9"),
+            ),
+            (
+                "4k4/8/8/8/8/8/8/4K3 w - - 0 1",
+                FenError::invalid_piece_placement("rank 8 doesn't have 8 cells"),
+            ),
+            (
+                "4k2nn/8/8/8/8/8/8/4K3 w - - 0 1",
+                FenError::invalid_piece_placement("rank 8 has more than 8 cells"),
+            ),
+            (
+                "4k2p/8/8/8/8/8/8/4K3 w - - 0 1",
+                FenError::invalid_piece_placement("pawn on rank 8"),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 x - - 0 1",
+                FenError::InvalidActiveColor("x".to_string()),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w K - 0 1",
+                FenError::InvalidCastling("K".to_string()),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w - e3 0 1",
+                FenError::InvalidEnPassant("e3".to_string()),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w - d6 0 1",
+                FenError::InvalidEnPassant("d6".to_string()),
+            ),
+            (
+                "4k3/8/8/3P4/8/8/8/4K3 w - d6 0 1",
+                FenError::InvalidEnPassant("d6".to_string()),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4R1K1 w - - 0 1",
+                FenError::invalid_piece_placement("Black is in check while White is to move"),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w - - x 1",
+                FenError::InvalidMoveCounter("x".to_string()),
+            ),
+            (
+                "4k3/8/8/8/8/8/8/4K3 w - - 0",
+                FenError::InvalidFieldsNumber(5),
+            ),
+        ];
+        for (position, error) in errors {
+            assert_eq!(Chess::from_fen(position).unwrap_err(), error);
+        }
     }
 
     #[test]
@@ -1778,7 +3080,7 @@ mod test {
         let mut chess = Chess::new();
 
         // cannot update finished game
-        chess.set_draw();
+        chess.set_draw(TerminationReason::Stalemate);
         assert_eq!(
             chess
                 .update(FIRST_PLAYER, TurnData::new(e2, e4))
@@ -1821,4 +3123,103 @@ mod test {
             GameError::invalid_move(format!("unable to move {} to {}", e2, e5))
         );
     }
+
+    fn assert_same_game(chess: &mut Chess, expected: &mut Chess) {
+        assert_eq!(chess.get_board_content(), expected.get_board_content());
+        assert_eq!(chess.bitboards, expected.bitboards);
+        assert_eq!(chess.player_state, expected.player_state);
+        assert_eq!(chess.en_passant, expected.en_passant);
+        assert_eq!(chess.halfmove_clock, expected.halfmove_clock);
+        assert_eq!(chess.position_history, expected.position_history);
+        assert_eq!(chess.fullmove_number, expected.fullmove_number);
+        assert_eq!(chess.state, expected.state);
+        assert_eq!(
+            chess.get_current_player().unwrap().id,
+            expected.get_current_player().unwrap().id
+        );
+    }
+
+    #[test]
+    fn test_failed_update_is_reverted() {
+        // the enemy player is missing, so the turn fails after the pawn has been moved
+        let mut chess = Chess::new();
+        let white = *chess.get_current_player().unwrap();
+        chess.players = PlayerDataQueue::new(vec![white]);
+        let mut initial = chess.clone();
+        let e2 = GridIndex::new(6, 4);
+        let e4 = GridIndex::new(4, 4);
+        assert!(chess.update(FIRST_PLAYER, TurnData::new(e2, e4)).is_err());
+        assert_same_game(&mut chess, &mut initial);
+        assert_eq!(chess.undo().unwrap_err(), GameError::NothingToUndo);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut chess = Chess::new();
+        assert_eq!(chess.undo().unwrap_err(), GameError::NothingToUndo);
+
+        // white castles and then black captures the rook
+        let mut chess = Chess::from_fen("4k3/8/8/8/8/8/8/R3K2r w Q - 0 1").unwrap();
+        let mut initial = chess.clone();
+        let e1 = GridIndex::new(7, 4);
+        let c1 = GridIndex::new(7, 2);
+        let d1 = GridIndex::new(7, 3);
+        let h1 = GridIndex::new(7, 7);
+        assert!(chess.is_in_check(FIRST_PLAYER));
+        assert!(chess.update(FIRST_PLAYER, TurnData::new(e1, c1)).is_err());
+        chess
+            .update(FIRST_PLAYER, TurnData::new(e1, GridIndex::new(6, 4)))
+            .unwrap();
+        let mut after_king_move = chess.clone();
+        chess
+            .update(SECOND_PLAYER, TurnData::new(h1, GridIndex::new(7, 0)))
+            .unwrap();
+        assert_eq!(chess.undo().unwrap(), GameState::Turn(SECOND_PLAYER));
+        assert_same_game(&mut chess, &mut after_king_move);
+        assert_eq!(chess.undo().unwrap(), GameState::Turn(FIRST_PLAYER));
+        assert_same_game(&mut chess, &mut initial);
+        assert!(chess.is_in_check(FIRST_PLAYER));
+        assert_eq!(chess.get_king_position(FIRST_PLAYER), Some(e1));
+        assert_eq!(chess.undo().unwrap_err(), GameError::NothingToUndo);
+        // the same turn can be made again after undo
+        assert!(chess.update(FIRST_PLAYER, TurnData::new(e1, d1)).is_err());
+        chess
+            .update(FIRST_PLAYER, TurnData::new(e1, GridIndex::new(6, 4)))
+            .unwrap();
+        assert_same_game(&mut chess, &mut after_king_move);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// Random game followed by undo of every turn goes through the same positions backwards.
+        #[test]
+        fn test_undo_random_game(
+            fen in prop::sample::select(vec![
+                fen::INITIAL_POSITION,
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            ]),
+            choices in prop::collection::vec(any::<usize>(), 0..60),
+        ) {
+            let mut initial = Chess::from_fen(fen).unwrap();
+            let mut chess = initial.clone();
+            let mut fens = vec![];
+            for choice in choices {
+                let GameState::Turn(id) = chess.state() else {
+                    break;
+                };
+                let actions = chess.legal_actions();
+                fens.push(chess.to_fen());
+                chess.update(id, actions[choice % actions.len()]).unwrap();
+            }
+            while let Some(fen) = fens.pop() {
+                chess.undo().unwrap();
+                prop_assert_eq!(chess.to_fen(), fen);
+            }
+            prop_assert_eq!(chess.undo(), Err(GameError::NothingToUndo));
+            assert_same_game(&mut chess, &mut initial);
+        }
+    }
 }
@@ -1,8 +1,14 @@
+pub mod pgn;
 pub mod types;
 
+mod bitboard;
+mod chess960;
+mod fen;
 mod game;
-mod iterator;
 mod turn_data;
+mod zobrist;
 
+pub use chess960::{Chess960, POSITIONS_NUMBER, STANDARD_POSITION_INDEX};
+pub use fen::{FenError, INITIAL_POSITION};
 pub use game::Chess;
 pub use turn_data::TurnData;
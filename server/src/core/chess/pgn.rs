@@ -0,0 +1,522 @@
+use std::fmt::Write;
+
+use super::fen::{self, FenError};
+use super::game::Chess;
+use super::turn_data::TurnData;
+use super::types::{MoveType, PieceKind, Team};
+use crate::core::{
+    FinishedState, Game, GameError, GameState, GridIndex, PlayerPosition, TerminationReason,
+};
+
+/// Maximum length of a movetext line in exported PGN.
+const LINE_LENGTH: usize = 80;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum PgnError {
+    #[error("invalid tag pair: {0}")]
+    InvalidTag(String),
+    #[error("invalid move notation: {0}")]
+    InvalidNotation(String),
+    #[error("illegal move: {0}")]
+    IllegalMove(String),
+    #[error("ambiguous move: {0}")]
+    AmbiguousMove(String),
+    #[error(transparent)]
+    Fen(#[from] FenError),
+    #[error(transparent)]
+    Game(#[from] GameError),
+}
+
+/// Record of a chess game in Portable Game Notation.
+#[derive(Clone, Debug, Default)]
+pub struct Pgn {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<TurnData>,
+}
+
+impl Pgn {
+    pub fn new(tags: Vec<(String, String)>, moves: Vec<TurnData>) -> Self {
+        Self { tags, moves }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns position from "FEN" tag if it's present, otherwise the initial position.
+    pub fn starting_position(&self) -> Result<Chess, PgnError> {
+        match self.tag("FEN") {
+            Some(fen) => Ok(Chess::from_fen(fen)?),
+            None => Ok(Chess::new()),
+        }
+    }
+
+    /// Replays all moves from the starting position.
+    pub fn replay(&self) -> Result<Chess, PgnError> {
+        let mut chess = self.starting_position()?;
+        for &data in self.moves.iter() {
+            let id = current_player(&chess)?;
+            chess.update(id, data)?;
+        }
+        Ok(chess)
+    }
+
+    /// Parses tag pairs and movetext. Comments, variations and annotations are skipped.
+    pub fn parse(pgn: &str) -> Result<Self, PgnError> {
+        let mut tags = vec![];
+        let mut movetext = String::new();
+        for line in pgn.lines().map(str::trim) {
+            if line.starts_with('[') {
+                tags.push(parse_tag(line)?);
+            } else {
+                movetext.push_str(line);
+                movetext.push('\n');
+            }
+        }
+
+        let mut pgn = Self::new(tags, vec![]);
+        let mut chess = pgn.starting_position()?;
+        for token in movetext_tokens(&movetext) {
+            let data = from_san(&chess, &token)?;
+            chess.update(current_player(&chess)?, data)?;
+            pgn.moves.push(data);
+        }
+        Ok(pgn)
+    }
+
+    /// Writes tag pairs and movetext with moves in Standard Algebraic Notation.
+    /// Game result is taken from "Result" tag or from the state of the game after the last move.
+    pub fn write(&self) -> Result<String, PgnError> {
+        let mut output = String::new();
+        for (name, value) in self.tags.iter() {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(output, "[{} \"{}\"]", name, value);
+        }
+        if !self.tags.is_empty() {
+            output.push('\n');
+        }
+
+        let mut tokens = vec![];
+        let mut chess = self.starting_position()?;
+        for (i, &data) in self.moves.iter().enumerate() {
+            let id = current_player(&chess)?;
+            match chess.get_player_team(id) {
+                Some(Team::White) => tokens.push(format!("{}.", chess.fullmove_number())),
+                _ if i == 0 => tokens.push(format!("{}...", chess.fullmove_number())),
+                _ => {}
+            }
+            tokens.push(to_san(&chess, data)?);
+            chess.update(id, data)?;
+        }
+        let result = match (self.tag("Result"), chess.state()) {
+            (Some(result), _) => result,
+            (None, GameState::Finished(FinishedState::Win(id, _))) => {
+                match chess.get_player_team(id) {
+                    Some(Team::Black) => "0-1",
+                    _ => "1-0",
+                }
+            }
+            (None, GameState::Finished(FinishedState::Draw(_))) => "1/2-1/2",
+            (None, GameState::Turn(_) | GameState::Waiting(_) | GameState::Seeding(_)) => "*",
+        };
+        tokens.push(result.to_string());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > LINE_LENGTH {
+                output.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                output.push(' ');
+                line_length += 1;
+            }
+            output.push_str(&token);
+            line_length += token.len();
+        }
+        output.push('\n');
+        Ok(output)
+    }
+}
+
+/// Returns move in Standard Algebraic Notation, e.g. "Nbd2", "exd6", "a8=Q+" or "O-O".
+pub fn to_san(chess: &Chess, data: TurnData) -> Result<String, PgnError> {
+    let mut chess = chess.clone();
+    let id = current_player(&chess)?;
+    let piece = chess.board()[data.from]
+        .ok_or(GameError::cell_is_empty(data.from.row(), data.from.col()))?;
+    let mut san = match chess.get_move_type(data) {
+        MoveType::LeftCastling => "O-O-O".to_string(),
+        MoveType::RightCastling => "O-O".to_string(),
+        move_type => {
+            let is_capture = chess.board()[data.to].is_some() || move_type == MoveType::EnPassant;
+            let mut san = String::new();
+            if piece.is_pawn() {
+                if is_capture {
+                    san.push_str(&fen::square_name(data.from)[..1]);
+                }
+            } else {
+                san.push(fen::piece_to_char(piece.kind, Team::White));
+                let rivals = find_pieces_moving_to(&chess, id, piece.kind, data.to)
+                    .into_iter()
+                    .filter(|&pos| pos != data.from)
+                    .collect::<Vec<_>>();
+                let square = fen::square_name(data.from);
+                if rivals.iter().all(|pos| pos.col() != data.from.col()) {
+                    san.push_str(if rivals.is_empty() { "" } else { &square[..1] });
+                } else if rivals.iter().all(|pos| pos.row() != data.from.row()) {
+                    san.push_str(&square[1..]);
+                } else {
+                    san.push_str(&square);
+                }
+            }
+            if is_capture {
+                san.push('x');
+            }
+            san.push_str(&fen::square_name(data.to));
+            if let Some(kind) = data.promotion {
+                san.push('=');
+                san.push(fen::piece_to_char(kind, Team::White));
+            }
+            san
+        }
+    };
+
+    let enemy = chess
+        .get_player_ids()
+        .into_iter()
+        .find(|&player| player != id);
+    chess.update(id, data)?;
+    match chess.state() {
+        GameState::Finished(FinishedState::Win(_, TerminationReason::Checkmate)) => san.push('#'),
+        _ if enemy.is_some_and(|enemy| chess.is_in_check(enemy)) => san.push('+'),
+        _ => {}
+    }
+    Ok(san)
+}
+
+/// Finds legal move of the current player described in Standard Algebraic Notation.
+pub fn from_san(chess: &Chess, san: &str) -> Result<TurnData, PgnError> {
+    let id = current_player(chess)?;
+    let invalid = || PgnError::InvalidNotation(san.to_string());
+    let notation = san.trim_end_matches(['+', '#', '!', '?']);
+    if !notation.is_ascii() {
+        return Err(invalid());
+    }
+
+    if let castling @ ("O-O" | "O-O-O" | "0-0" | "0-0-0") = notation {
+        let king = chess
+            .get_king_position(id)
+            .ok_or(GameError::PlayerNotFound)?;
+        let to = chess
+            .get_castling_target(id, castling.len() == 5)
+            .ok_or(GameError::PlayerNotFound)?;
+        if !chess.get_moves(king)?.contains(&to) {
+            return Err(PgnError::IllegalMove(san.to_string()));
+        }
+        return Ok(TurnData::new(king, to));
+    }
+
+    let (notation, promotion) = match notation.split_once('=') {
+        Some((notation, piece)) => {
+            let kind = match piece.chars().collect::<Vec<_>>().as_slice() {
+                &[c] if c.is_ascii_uppercase() => fen::piece_from_char(c)
+                    .map(|(kind, _)| kind)
+                    .filter(PieceKind::is_promotion_target),
+                _ => None,
+            };
+            (notation, Some(kind.ok_or_else(invalid)?))
+        }
+        None => (notation, None),
+    };
+    let (kind, notation) = match notation.chars().next() {
+        Some(c @ ('K' | 'Q' | 'R' | 'B' | 'N')) => (
+            fen::piece_from_char(c)
+                .map(|(kind, _)| kind)
+                .ok_or_else(invalid)?,
+            &notation[1..],
+        ),
+        _ => (PieceKind::Pawn, notation),
+    };
+    let notation = notation.replacen('x', "", 1);
+    if notation.len() < 2 || notation.len() > 4 {
+        return Err(invalid());
+    }
+    let (hint, square) = notation.split_at(notation.len() - 2);
+    let to = fen::parse_square(square).ok_or_else(invalid)?;
+    let mut col = None;
+    let mut row = None;
+    for c in hint.chars() {
+        match c {
+            'a'..='h' if col.is_none() && row.is_none() => col = Some(c as usize - 'a' as usize),
+            '1'..='8' if row.is_none() => row = Some('8' as usize - c as usize),
+            _ => return Err(invalid()),
+        }
+    }
+
+    let candidates: Vec<_> = find_pieces_moving_to(chess, id, kind, to)
+        .into_iter()
+        .filter(|pos| col.is_none_or(|col| pos.col() == col))
+        .filter(|pos| row.is_none_or(|row| pos.row() == row))
+        .collect();
+    match candidates.as_slice() {
+        [from] => Ok(TurnData {
+            from: *from,
+            to,
+            promotion,
+        }),
+        [] => Err(PgnError::IllegalMove(san.to_string())),
+        _ => Err(PgnError::AmbiguousMove(san.to_string())),
+    }
+}
+
+fn current_player(chess: &Chess) -> Result<PlayerPosition, PgnError> {
+    match chess.state() {
+        GameState::Turn(id) => Ok(id),
+        GameState::Waiting(_) | GameState::Seeding(_) => Err(GameError::PlayerPoolCorrupted.into()),
+        GameState::Finished(_) => Err(GameError::GameIsFinished.into()),
+    }
+}
+
+/// Returns positions of player's pieces of the given kind that are able to move to `to`.
+fn find_pieces_moving_to(
+    chess: &Chess,
+    id: PlayerPosition,
+    kind: PieceKind,
+    to: GridIndex,
+) -> Vec<GridIndex> {
+    chess
+        .find_pieces_positions(id)
+        .into_iter()
+        .filter(|&pos| chess.board()[pos].is_some_and(|piece| piece.kind == kind))
+        .filter(|&pos| chess.get_moves(pos).is_ok_and(|moves| moves.contains(&to)))
+        .collect()
+}
+
+fn parse_tag(line: &str) -> Result<(String, String), PgnError> {
+    let invalid = || PgnError::InvalidTag(line.to_string());
+    let (name, value) = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .and_then(|line| line.split_once(char::is_whitespace))
+        .ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    if name.is_empty() {
+        return Err(invalid());
+    }
+    let value = value.replace("\\\"", "\"").replace("\\\\", "\\");
+    Ok((name.to_string(), value))
+}
+
+/// Splits movetext into moves dropping move numbers, comments, variations,
+/// numeric annotation glyphs and game termination marker.
+fn movetext_tokens(movetext: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut chars = movetext.chars();
+    let mut variation_depth = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                chars.by_ref().find(|&c| c == '}');
+            }
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '(' => variation_depth += 1,
+            ')' => variation_depth -= 1,
+            _ if variation_depth > 0 => continue,
+            c if c.is_whitespace() => {}
+            c => {
+                token.push(c);
+                continue;
+            }
+        }
+        tokens.push(std::mem::take(&mut token));
+    }
+    tokens.push(token);
+
+    tokens
+        .into_iter()
+        .map(|token| match token.rfind('.') {
+            // move number can be attached to the move, e.g. "1.e4"
+            Some(dot) if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                token[dot + 1..].to_string()
+            }
+            _ => token,
+        })
+        .filter(|token| {
+            !token.is_empty()
+                && !token.starts_with('$')
+                && !matches!(token.as_str(), "1-0" | "0-1" | "1/2-1/2" | "*")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FIRST_PLAYER: PlayerPosition = 0;
+
+    const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 {This is a weak move already.} 4. dxe5 Bxf3 5. Qxf3
+dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 b5 $6 (9... Qb4+ 10. Qxb4 Bxb4) 10. Nxb5
+cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6 15. Bxd7+ Nxd7
+16. Qb8+ ; the final sacrifice
+Nxb8 17. Rd8# 1-0
+"#;
+
+    fn square(name: &str) -> GridIndex {
+        fen::parse_square(name).unwrap()
+    }
+
+    fn turn(from: &str, to: &str) -> TurnData {
+        TurnData::new(square(from), square(to))
+    }
+
+    #[test]
+    fn test_san() {
+        let cases = [
+            // file disambiguation
+            ("k7/8/8/8/8/8/8/1N1K1N2 w - - 0 1", turn("b1", "d2"), "Nbd2"),
+            // rank disambiguation
+            ("k7/8/8/8/8/1N6/8/1N1K4 w - - 0 1", turn("b1", "d2"), "N1d2"),
+            // full square disambiguation and check
+            (
+                "8/8/8/8/8/Q7/7k/Q1Q1K3 w - - 0 1",
+                turn("a1", "b2"),
+                "Qa1b2+",
+            ),
+            // disambiguation is not needed when the other piece is pinned
+            ("k7/8/8/8/b7/8/2N5/3K1N2 w - - 0 1", turn("f1", "e3"), "Ne3"),
+            (
+                "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2",
+                turn("e5", "d6"),
+                "exd6",
+            ),
+            (
+                "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+                TurnData::with_promotion(square("a7"), square("b8"), PieceKind::Queen),
+                "axb8=Q+",
+            ),
+            (
+                "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+                turn("e1", "g1"),
+                "O-O",
+            ),
+            (
+                "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+                turn("e1", "c1"),
+                "O-O-O",
+            ),
+            (
+                "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2",
+                turn("d8", "h4"),
+                "Qh4#",
+            ),
+        ];
+        for (position, data, san) in cases {
+            let chess = Chess::from_fen(position).unwrap();
+            assert_eq!(to_san(&chess, data).unwrap(), san);
+            let parsed = from_san(&chess, san).unwrap();
+            assert_eq!(
+                (parsed.from, parsed.to, parsed.promotion),
+                (data.from, data.to, data.promotion)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_san_errors() {
+        let chess = Chess::from_fen("k7/8/8/8/8/8/8/1N1K1N2 w - - 0 1").unwrap();
+        assert_eq!(
+            from_san(&chess, "Nd2").unwrap_err(),
+            PgnError::AmbiguousMove("Nd2".to_string())
+        );
+        assert_eq!(
+            from_san(&chess, "Nd4").unwrap_err(),
+            PgnError::IllegalMove("Nd4".to_string())
+        );
+        assert_eq!(
+            from_san(&chess, "O-O").unwrap_err(),
+            PgnError::IllegalMove("O-O".to_string())
+        );
+        for san in ["Zd2", "Nj2", "N", "b1=K", "Nbbd2"] {
+            assert_eq!(
+                from_san(&chess, san).unwrap_err(),
+                PgnError::InvalidNotation(san.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let pgn = Pgn::parse(OPERA_GAME).unwrap();
+        assert_eq!(pgn.tags.len(), 7);
+        assert_eq!(pgn.tag("Black"), Some("Duke Karl / Count Isouard"));
+        assert_eq!(pgn.moves.len(), 33);
+        assert_eq!(
+            pgn.replay().unwrap().state(),
+            GameState::Finished(FinishedState::Win(
+                FIRST_PLAYER,
+                TerminationReason::Checkmate
+            ))
+        );
+
+        assert_eq!(
+            Pgn::parse("[Event \"Paris]").unwrap_err(),
+            PgnError::InvalidTag("[Event \"Paris]".to_string())
+        );
+        assert_eq!(
+            Pgn::parse("1. e4 e4").unwrap_err(),
+            PgnError::IllegalMove("e4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let pgn = Pgn::parse(OPERA_GAME).unwrap();
+        let expected = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 4. dxe5 Bxf3 5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8.
+Nc3 c6 9. Bg5 b5 10. Nxb5 cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14.
+Rd1 Qe6 15. Bxd7+ Nxd7 16. Qb8+ Nxb8 17. Rd8# 1-0
+"#;
+        assert_eq!(pgn.write().unwrap(), expected);
+        assert_eq!(Pgn::parse(expected).unwrap().moves.len(), pgn.moves.len());
+
+        // result is taken from the game when there is no tag, game can start from black's move
+        let pgn = Pgn::new(
+            vec![(
+                "FEN".to_string(),
+                "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2".to_string(),
+            )],
+            vec![turn("d8", "h4")],
+        );
+        assert_eq!(
+            pgn.write().unwrap(),
+            "[FEN \"rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2\"]\n\n2... Qh4# 0-1\n"
+        );
+        assert_eq!(Pgn::default().write().unwrap(), "*\n");
+    }
+}
@@ -1,17 +1,52 @@
+use std::fmt::{Display, Formatter};
+
 use prost::Message;
 
-use crate::core::{FromProtobuf, GridIndex, ProtobufError, ProtobufResult, ToProtobuf};
-use crate::proto::PositionPair;
+use super::fen;
+use super::types::{PieceKind, Team};
+use crate::core::{
+    CheckBounds, FromProtobuf, GridIndex, ProtobufError, ProtobufResult, ToProtobuf,
+};
+use crate::proto::{ChessPieceKind, PositionPair};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TurnData {
     pub from: GridIndex,
     pub to: GridIndex,
+    pub promotion: Option<PieceKind>,
 }
 
 impl TurnData {
     pub fn new(from: GridIndex, to: GridIndex) -> Self {
-        Self { from, to }
+        Self {
+            from,
+            to,
+            promotion: None,
+        }
+    }
+
+    pub fn with_promotion(from: GridIndex, to: GridIndex, promotion: PieceKind) -> Self {
+        Self {
+            from,
+            to,
+            promotion: Some(promotion),
+        }
+    }
+}
+
+/// Move in long algebraic notation, e.g. "e2e4" or "a7a8q".
+impl Display for TurnData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            fen::square_name(self.from),
+            fen::square_name(self.to)
+        )?;
+        if let Some(kind) = self.promotion {
+            write!(f, "{}", fen::piece_to_char(kind, Team::Black))?;
+        }
+        Ok(())
     }
 }
 
@@ -26,14 +61,30 @@ impl FromProtobuf for TurnData {
             .ok_or_else(|| ProtobufError::MessageDataMissing {
                 missing_field: "second".to_string(),
             })?;
-        let turn_data = TurnData::new(
-            GridIndex::new(usize::try_from(first.row)?, usize::try_from(first.col)?),
-            GridIndex::new(usize::try_from(second.row)?, usize::try_from(second.col)?),
-        );
+        let promotion = pos
+            .promotion
+            .map(|kind| {
+                ChessPieceKind::try_from(kind)
+                    .map_err(|_| ProtobufError::InvalidPieceKind(kind))?
+                    .try_into()
+            })
+            .transpose()?;
+        let turn_data = TurnData {
+            from: GridIndex::new(usize::try_from(first.row)?, usize::try_from(first.col)?),
+            to: GridIndex::new(usize::try_from(second.row)?, usize::try_from(second.col)?),
+            promotion,
+        };
         Ok(turn_data)
     }
 }
 
+impl CheckBounds for TurnData {
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        self.from.check_bounds(rows, cols)?;
+        self.to.check_bounds(rows, cols)
+    }
+}
+
 impl ToProtobuf for TurnData {
     fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
         PositionPair::try_from(self)?.to_protobuf()
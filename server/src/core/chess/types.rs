@@ -9,6 +9,8 @@ pub enum MoveType {
     RightCastling,
     KingMove,
     RookMove,
+    EnPassant,
+    Promotion,
     Other,
 }
 
@@ -46,6 +48,13 @@ impl Team {
             Team::Black => 1,
         }
     }
+
+    pub fn get_pawn_promotion_row(&self) -> usize {
+        match self {
+            Team::White => 0,
+            Team::Black => 7,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -58,6 +67,16 @@ pub enum PieceKind {
     King,
 }
 
+impl PieceKind {
+    /// Returns `true` if a pawn is allowed to be promoted to this kind of piece.
+    pub fn is_promotion_target(&self) -> bool {
+        matches!(
+            self,
+            PieceKind::Bishop | PieceKind::Knight | PieceKind::Rook | PieceKind::Queen
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Piece {
     pub kind: PieceKind,
@@ -113,7 +132,6 @@ impl Piece {
         }
     }
 
-    #[allow(dead_code)]
     pub fn is_pawn(&self) -> bool {
         self.kind == PieceKind::Pawn
     }
@@ -0,0 +1,84 @@
+use super::types::{Piece, PieceKind};
+use crate::core::random::split_mix;
+use crate::core::{GridIndex, PlayerPosition};
+
+/// Random keys used to build Zobrist hash of a chess position.
+/// Hash of a position is a XOR of keys of all of its features,
+/// so it's cheap to compute and identical positions always have identical hashes.
+struct Keys {
+    pieces: [[[u64; 64]; 6]; 2],
+    side: [u64; 2],
+    castling: [[u64; 2]; 2],
+    en_passant: [u64; 8],
+}
+
+const fn generate_keys() -> Keys {
+    let mut keys = Keys {
+        pieces: [[[0; 64]; 6]; 2],
+        side: [0; 2],
+        castling: [[0; 2]; 2],
+        en_passant: [0; 8],
+    };
+    let mut state = 0;
+    let mut key;
+    let mut player = 0;
+    while player < 2 {
+        let mut kind = 0;
+        while kind < 6 {
+            let mut cell = 0;
+            while cell < 64 {
+                (state, key) = split_mix(state);
+                keys.pieces[player][kind][cell] = key;
+                cell += 1;
+            }
+            kind += 1;
+        }
+        (state, key) = split_mix(state);
+        keys.side[player] = key;
+        (state, key) = split_mix(state);
+        keys.castling[player][0] = key;
+        (state, key) = split_mix(state);
+        keys.castling[player][1] = key;
+        player += 1;
+    }
+    let mut col = 0;
+    while col < 8 {
+        (state, key) = split_mix(state);
+        keys.en_passant[col] = key;
+        col += 1;
+    }
+    keys
+}
+
+const KEYS: Keys = generate_keys();
+
+fn kind_index(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::Pawn => 0,
+        PieceKind::Bishop => 1,
+        PieceKind::Knight => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    }
+}
+
+pub fn piece_key(piece: Piece, pos: GridIndex) -> u64 {
+    KEYS.pieces[piece.owner as usize][kind_index(piece.kind)][pos.row() * 8 + pos.col()]
+}
+
+pub fn side_key(player: PlayerPosition) -> u64 {
+    KEYS.side[player as usize]
+}
+
+pub fn left_castling_key(player: PlayerPosition) -> u64 {
+    KEYS.castling[player as usize][0]
+}
+
+pub fn right_castling_key(player: PlayerPosition) -> u64 {
+    KEYS.castling[player as usize][1]
+}
+
+pub fn en_passant_key(pos: GridIndex) -> u64 {
+    KEYS.en_passant[pos.col()]
+}
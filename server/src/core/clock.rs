@@ -0,0 +1,319 @@
+use std::time::{Duration, Instant};
+
+use super::{PlayerPosition, PlayerSet};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Time players are given to make their turns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeControl {
+    /// `base` time for the whole game, `increment` is added to the clock after each turn
+    /// and the clock starts running only after `delay` has passed since the start of the turn.
+    Clock {
+        base: Duration,
+        increment: Duration,
+        delay: Duration,
+    },
+    /// Each turn has to be made within the given number of days, unused time is not kept.
+    Correspondence { days_per_move: u32 },
+}
+
+impl TimeControl {
+    /// Longest time a player can be given for the game or a single turn.
+    pub const MAX_TIME: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+    pub fn with_increment(base: Duration, increment: Duration) -> Self {
+        Self::Clock {
+            base,
+            increment,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn with_delay(base: Duration, delay: Duration) -> Self {
+        Self::Clock {
+            base,
+            increment: Duration::ZERO,
+            delay,
+        }
+    }
+
+    /// Checks that the players are given some time and none of the durations exceed
+    /// [`TimeControl::MAX_TIME`].
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            TimeControl::Clock {
+                base,
+                increment,
+                delay,
+            } => {
+                !base.is_zero()
+                    && [base, increment, delay]
+                        .into_iter()
+                        .all(|time| time <= Self::MAX_TIME)
+            }
+            TimeControl::Correspondence { days_per_move } => {
+                days_per_move > 0 && self.time_per_turn() <= Self::MAX_TIME
+            }
+        }
+    }
+
+    fn time_per_turn(&self) -> Duration {
+        match *self {
+            TimeControl::Clock { base, .. } => base,
+            TimeControl::Correspondence { days_per_move } => DAY * days_per_move,
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        match *self {
+            TimeControl::Clock { delay, .. } => delay,
+            TimeControl::Correspondence { .. } => Duration::ZERO,
+        }
+    }
+}
+
+/// Remaining time of each player, only the clocks of the players to move are running.
+#[derive(Clone, Debug)]
+pub struct Clocks {
+    control: TimeControl,
+    remaining: Vec<Duration>,
+    /// Players whose clocks are running and the start of their turns.
+    running: Vec<(PlayerPosition, Instant)>,
+}
+
+impl Clocks {
+    /// Creates stopped clocks with the full time for each of the players.
+    pub fn new(control: TimeControl, players: usize) -> Self {
+        Self {
+            control,
+            remaining: vec![control.time_per_turn(); players],
+            running: vec![],
+        }
+    }
+
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Starts the clock of the player, the clocks that were running are stopped.
+    pub fn start(&mut self, player: PlayerPosition, now: Instant) {
+        self.stop(now);
+        self.running.push((player, now));
+    }
+
+    /// Keeps the clocks of the players running and stops the others,
+    /// used when players move at once.
+    pub fn run(&mut self, players: PlayerSet, now: Instant) {
+        let stopped = self
+            .running
+            .iter()
+            .map(|&(player, _)| player)
+            .filter(|&player| !players.contains(player))
+            .collect::<Vec<_>>();
+        for player in stopped {
+            self.stop_player(player, now);
+        }
+        for player in players.iter() {
+            if self.running.iter().all(|&(running, _)| running != player) {
+                self.running.push((player, now));
+            }
+        }
+    }
+
+    /// Stops the running clocks charging the time spent on the turn,
+    /// the increment is added unless the time has run out.
+    pub fn stop(&mut self, now: Instant) {
+        while let Some(&(player, _)) = self.running.first() {
+            self.stop_player(player, now);
+        }
+    }
+
+    /// Stops the clock of the player the same way, the other clocks keep running.
+    pub fn stop_player(&mut self, player: PlayerPosition, now: Instant) {
+        let Some(index) = self
+            .running
+            .iter()
+            .position(|&(running, _)| running == player)
+        else {
+            return;
+        };
+        let remaining = self.remaining(player, now);
+        self.running.remove(index);
+        let Some(clock) = self.remaining.get_mut(player as usize) else {
+            return;
+        };
+        *clock = match self.control {
+            _ if remaining.is_zero() => Duration::ZERO,
+            TimeControl::Clock { increment, .. } => remaining.saturating_add(increment),
+            TimeControl::Correspondence { .. } => self.control.time_per_turn(),
+        };
+    }
+
+    /// Returns time left on the player's clock at `now`.
+    pub fn remaining(&self, player: PlayerPosition, now: Instant) -> Duration {
+        let Some(&remaining) = self.remaining.get(player as usize) else {
+            return Duration::ZERO;
+        };
+        match self.running.iter().find(|&&(running, _)| running == player) {
+            Some(&(_, start)) => {
+                let spent = now.saturating_duration_since(start);
+                remaining.saturating_sub(spent.saturating_sub(self.control.delay()))
+            }
+            None => remaining,
+        }
+    }
+
+    /// Returns time left on the clock of each player at `now`, ordered by player position.
+    pub fn remaining_all(&self, now: Instant) -> Vec<Duration> {
+        (0..self.remaining.len() as PlayerPosition)
+            .map(|player| self.remaining(player, now))
+            .collect()
+    }
+
+    /// Returns the moment the first of the running clocks reaches zero,
+    /// clocks that would run out beyond the range of [`Instant`] are ignored.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.running
+            .iter()
+            .filter_map(|&(player, start)| {
+                let remaining = self.remaining.get(player as usize)?;
+                start.checked_add(self.control.delay().saturating_add(*remaining))
+            })
+            .min()
+    }
+
+    /// Returns the player whose running clock has reached zero at `now`.
+    pub fn flagged(&self, now: Instant) -> Option<PlayerPosition> {
+        self.running
+            .iter()
+            .map(|&(player, _)| player)
+            .find(|&player| self.remaining(player, now).is_zero())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_increment() {
+        let control = TimeControl::with_increment(60 * SECOND, 2 * SECOND);
+        let mut clocks = Clocks::new(control, 2);
+        let now = Instant::now();
+        assert_eq!(clocks.remaining_all(now), vec![60 * SECOND; 2]);
+        assert_eq!(clocks.deadline(), None);
+
+        clocks.start(0, now);
+        assert_eq!(clocks.deadline(), Some(now + 60 * SECOND));
+        assert_eq!(clocks.remaining(0, now + 10 * SECOND), 50 * SECOND);
+        assert_eq!(clocks.remaining(1, now + 10 * SECOND), 60 * SECOND);
+
+        clocks.start(1, now + 10 * SECOND);
+        assert_eq!(
+            clocks.remaining_all(now + 15 * SECOND),
+            vec![52 * SECOND, 55 * SECOND]
+        );
+        assert_eq!(clocks.deadline(), Some(now + 70 * SECOND));
+        assert_eq!(clocks.flagged(now + 69 * SECOND), None);
+        assert_eq!(clocks.flagged(now + 70 * SECOND), Some(1));
+
+        // no increment is given once the time has run out
+        clocks.stop(now + 80 * SECOND);
+        assert_eq!(clocks.remaining(1, now + 80 * SECOND), Duration::ZERO);
+        assert_eq!(clocks.flagged(now + 80 * SECOND), None);
+    }
+
+    #[test]
+    fn test_delay() {
+        let control = TimeControl::with_delay(60 * SECOND, 5 * SECOND);
+        let mut clocks = Clocks::new(control, 2);
+        let now = Instant::now();
+        clocks.start(0, now);
+        assert_eq!(clocks.remaining(0, now + 3 * SECOND), 60 * SECOND);
+        assert_eq!(clocks.remaining(0, now + 8 * SECOND), 57 * SECOND);
+        assert_eq!(clocks.deadline(), Some(now + 65 * SECOND));
+
+        // turn made within the delay doesn't use the time
+        clocks.start(1, now + 3 * SECOND);
+        assert_eq!(clocks.remaining(0, now + 4 * SECOND), 60 * SECOND);
+    }
+
+    #[test]
+    fn test_correspondence() {
+        let control = TimeControl::Correspondence { days_per_move: 3 };
+        let mut clocks = Clocks::new(control, 2);
+        let now = Instant::now();
+        clocks.start(0, now);
+        assert_eq!(clocks.deadline(), Some(now + 3 * DAY));
+
+        // unused time is not carried over to the next turn
+        clocks.start(1, now + DAY);
+        assert_eq!(clocks.remaining(0, now + DAY), 3 * DAY);
+        assert_eq!(clocks.remaining(1, now + 2 * DAY), 2 * DAY);
+        assert_eq!(clocks.flagged(now + 4 * DAY), Some(1));
+    }
+
+    #[test]
+    fn test_validity() {
+        assert!(TimeControl::with_increment(60 * SECOND, 2 * SECOND).is_valid());
+        assert!(!TimeControl::with_increment(Duration::ZERO, 2 * SECOND).is_valid());
+        assert!(!TimeControl::with_increment(60 * SECOND, Duration::MAX).is_valid());
+        assert!(!TimeControl::with_delay(60 * SECOND, Duration::MAX).is_valid());
+        assert!(TimeControl::Correspondence { days_per_move: 3 }.is_valid());
+        assert!(!TimeControl::Correspondence { days_per_move: 0 }.is_valid());
+        assert!(!TimeControl::Correspondence {
+            days_per_move: u32::MAX
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let control = TimeControl::Clock {
+            base: Duration::MAX,
+            increment: Duration::MAX,
+            delay: Duration::MAX,
+        };
+        let mut clocks = Clocks::new(control, 2);
+        let now = Instant::now();
+        clocks.start(0, now);
+        assert_eq!(clocks.deadline(), None);
+        clocks.start(1, now + SECOND);
+        assert_eq!(clocks.remaining(0, now + SECOND), Duration::MAX);
+        assert_eq!(clocks.flagged(now + SECOND), None);
+    }
+
+    #[test]
+    fn test_simultaneous() {
+        let control = TimeControl::with_increment(60 * SECOND, 2 * SECOND);
+        let mut clocks = Clocks::new(control, 2);
+        let now = Instant::now();
+        clocks.run(PlayerSet::first(2), now);
+        assert_eq!(
+            clocks.remaining_all(now + 10 * SECOND),
+            vec![50 * SECOND; 2]
+        );
+        assert_eq!(clocks.deadline(), Some(now + 60 * SECOND));
+
+        // the first player has moved, only the clock of the second one is running
+        clocks.run([1].into_iter().collect(), now + 10 * SECOND);
+        assert_eq!(
+            clocks.remaining_all(now + 20 * SECOND),
+            vec![52 * SECOND, 40 * SECOND]
+        );
+        assert_eq!(clocks.flagged(now + 60 * SECOND), Some(1));
+
+        // the clock that is already running isn't restarted
+        clocks.run(PlayerSet::first(2), now + 30 * SECOND);
+        assert_eq!(clocks.deadline(), Some(now + 60 * SECOND));
+        clocks.stop(now + 40 * SECOND);
+        assert_eq!(
+            clocks.remaining_all(now + 50 * SECOND),
+            vec![44 * SECOND, 22 * SECOND]
+        );
+        assert_eq!(clocks.deadline(), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::ops::DerefMut;
+
+use generic_array::typenum::{self, Unsigned};
+use prost::Message;
+
+use super::grid::GridIndex;
+use super::mnk::MnkGame;
+use crate::core::{
+    CheckBounds, FromProtobuf, Game, GameError, GameResult, GameState, PlayerPosition,
+    ProtobufError, ProtobufResult, ToProtobuf,
+};
+use crate::proto::ConnectFourTurn;
+
+type Rows = typenum::U6;
+type Columns = typenum::U7;
+
+/// Column the player drops its disc into.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TurnData {
+    pub column: usize,
+}
+
+impl TurnData {
+    pub fn new(column: usize) -> Self {
+        Self { column }
+    }
+}
+
+impl FromProtobuf for TurnData {
+    fn from_protobuf(buf: &[u8]) -> Result<Self, ProtobufError> {
+        let turn = ConnectFourTurn::decode(buf)?;
+        Ok(Self::new(usize::try_from(turn.column)?))
+    }
+}
+
+impl CheckBounds for TurnData {
+    fn check_bounds(&self, _rows: usize, cols: usize) -> ProtobufResult<()> {
+        if self.column >= cols {
+            return Err(ProtobufError::InvalidGridCol {
+                max_expected: cols,
+                found: self.column.saturating_add(1),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ToProtobuf for TurnData {
+    fn to_protobuf(self) -> Result<Vec<u8>, ProtobufError> {
+        ConnectFourTurn {
+            column: self.column.try_into()?,
+        }
+        .to_protobuf()
+    }
+}
+
+/// Four in a row on a vertical 7×6 board: discs fall to the lowest empty cell of the column.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectFour {
+    game: MnkGame<Rows, Columns, typenum::U4>,
+}
+
+impl Game for ConnectFour {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = TurnData;
+    type Players = <MnkGame<Rows, Columns, typenum::U4> as Game>::Players;
+    type Board = <MnkGame<Rows, Columns, typenum::U4> as Game>::Board;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if data.column >= Columns::to_usize() {
+            return Err(GameError::invalid_move(format!(
+                "column {} is out of the board",
+                data.column
+            )));
+        }
+        let pos = self
+            .landing_cell(data.column)
+            .ok_or_else(|| GameError::invalid_move(format!("column {} is full", data.column)))?;
+        self.game.update(id, pos)
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        if self.is_finished() {
+            return vec![];
+        }
+        (0..Columns::to_usize())
+            .filter(|&column| self.landing_cell(column).is_some())
+            .map(TurnData::new)
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.game.undo()
+    }
+
+    fn board(&self) -> &Self::Board {
+        self.game.board()
+    }
+
+    fn board_mut(&mut self) -> impl DerefMut<Target = Self::Board> {
+        self.game.board_mut()
+    }
+
+    fn set_board(&mut self, board: Self::Board) {
+        self.game.set_board(board)
+    }
+
+    fn players(&self) -> &Self::Players {
+        self.game.players()
+    }
+
+    fn players_mut(&mut self) -> &mut Self::Players {
+        self.game.players_mut()
+    }
+
+    fn state(&self) -> GameState {
+        self.game.state()
+    }
+
+    fn set_state(&mut self, state: GameState) {
+        self.game.set_state(state)
+    }
+}
+
+impl ConnectFour {
+    /// Returns the lowest empty cell of the column, [`None`] if the column is full.
+    fn landing_cell(&self, column: usize) -> Option<GridIndex> {
+        (0..Rows::to_usize())
+            .rev()
+            .map(|row| GridIndex::new(row, column))
+            .find(|&pos| self.board()[pos].is_none())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{BoardCell, FinishedState, TerminationReason};
+
+    fn play(game: &mut ConnectFour, columns: &[usize]) -> GameState {
+        let mut state = game.state();
+        for &column in columns {
+            let id = *game.get_current_player().unwrap();
+            state = game.update(id, TurnData::new(column)).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_gravity() {
+        let mut game = ConnectFour::new();
+        assert_eq!(game.legal_actions().len(), 7);
+        play(&mut game, &[3, 3]);
+        assert_eq!(game.board()[GridIndex::new(5, 3)], BoardCell(Some(0)));
+        assert_eq!(game.board()[GridIndex::new(4, 3)], BoardCell(Some(1)));
+        assert_eq!(game.board()[GridIndex::new(3, 3)], BoardCell(None));
+
+        // the column is full after six discs
+        play(&mut game, &[3, 3, 3, 3]);
+        assert!(!game.legal_actions().contains(&TurnData::new(3)));
+        assert_eq!(
+            game.update(0, TurnData::new(3)).unwrap_err(),
+            GameError::invalid_move("column 3 is full".to_string())
+        );
+        assert_eq!(
+            game.update(0, TurnData::new(7)).unwrap_err(),
+            GameError::invalid_move("column 7 is out of the board".to_string())
+        );
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(1));
+        assert_eq!(game.board()[GridIndex::new(0, 3)], BoardCell(None));
+        assert!(game.legal_actions().contains(&TurnData::new(3)));
+    }
+
+    #[test]
+    fn test_win() {
+        let mut game = ConnectFour::new();
+        // first player stacks discs in the column
+        assert_eq!(play(&mut game, &[0, 1, 0, 1, 0, 1]), GameState::Turn(0));
+        assert_eq!(
+            play(&mut game, &[0]),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::LineCompleted))
+        );
+
+        // second player builds a diagonal from the bottom left corner
+        let mut game = ConnectFour::new();
+        let state = play(&mut game, &[6, 0, 1, 1, 2, 2, 3, 2, 3, 3, 6]);
+        assert_eq!(state, GameState::Turn(1));
+        assert_eq!(
+            play(&mut game, &[3]),
+            GameState::Finished(FinishedState::Win(1, TerminationReason::LineCompleted))
+        );
+    }
+
+    #[test]
+    fn test_protobuf() {
+        let data = TurnData::new(4);
+        assert_eq!(
+            TurnData::from_protobuf(&data.to_protobuf().unwrap()).unwrap(),
+            data
+        );
+    }
+}
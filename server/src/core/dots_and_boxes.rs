@@ -0,0 +1,346 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use generic_array::typenum;
+use prost::Message;
+
+use super::grid::GridLayout;
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    game_fields, BoardCell, CheckBounds, FromProtobuf, Game, GameError, GameResult, GameState,
+    Grid, GridIndex, History, PlayerPosition, ProtobufError, ProtobufResult, Snapshot,
+    TerminationReason, ToProtobuf,
+};
+use crate::proto;
+
+/// Dots with lines between them and boxes surrounded by the lines, 5×5 boxes in total.
+type Size = typenum::U11;
+/// Player who has drawn the line or completed the box.
+type Cell = BoardCell<PlayerPosition>;
+type Board = Grid<Cell, Size, Size>;
+
+/// Line between two neighbouring dots, dots are numbered by rows and columns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Edge {
+    pub first: GridIndex,
+    pub second: GridIndex,
+}
+
+impl Edge {
+    pub fn new(first: GridIndex, second: GridIndex) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns the cell of the board the line is drawn on, [`None`] if the dots aren't neighbours.
+    fn cell(&self) -> Option<GridIndex> {
+        let (first, second) = (self.first, self.second);
+        match (
+            first.row().abs_diff(second.row()),
+            first.col().abs_diff(second.col()),
+        ) {
+            (0, 1) | (1, 0) => Some(GridIndex::new(
+                first.row().saturating_add(second.row()),
+                first.col().saturating_add(second.col()),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the line drawn on the cell of the board.
+    fn from_cell(line: GridIndex) -> Self {
+        let first = GridIndex::new(line.row() / 2, line.col() / 2);
+        let second = GridIndex::new(line.row().div_ceil(2), line.col().div_ceil(2));
+        Self::new(first, second)
+    }
+}
+
+impl Display for Edge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.first, self.second)
+    }
+}
+
+impl FromProtobuf for Edge {
+    fn from_protobuf(buf: &[u8]) -> Result<Self, ProtobufError> {
+        let turn = proto::DotsAndBoxesTurn::decode(buf)?;
+        let dot = |pos: Option<proto::Position>, field: &str| {
+            let pos = pos.ok_or_else(|| ProtobufError::MessageDataMissing {
+                missing_field: field.to_string(),
+            })?;
+            Ok::<_, ProtobufError>(GridIndex::new(pos.row.try_into()?, pos.col.try_into()?))
+        };
+        Ok(Self::new(
+            dot(turn.first, "first")?,
+            dot(turn.second, "second")?,
+        ))
+    }
+}
+
+impl CheckBounds for Edge {
+    /// Dots lie on every other row and column of the board.
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        let (rows, cols) = (rows / 2 + 1, cols / 2 + 1);
+        self.first.check_bounds(rows, cols)?;
+        self.second.check_bounds(rows, cols)
+    }
+}
+
+impl ToProtobuf for Edge {
+    fn to_protobuf(self) -> Result<Vec<u8>, ProtobufError> {
+        proto::DotsAndBoxesTurn {
+            first: Some(self.first.try_into()?),
+            second: Some(self.second.try_into()?),
+        }
+        .to_protobuf()
+    }
+}
+
+/// Players draw lines between neighbouring dots, the one who completes a box owns it
+/// and moves again. Once every line is drawn the player with more boxes wins.
+#[derive(Clone, Debug)]
+pub struct DotsAndBoxes {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Board,
+    history: History<Snapshot<Self>>,
+}
+
+impl Default for DotsAndBoxes {
+    fn default() -> Self {
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            board: Board::default(),
+            history: History::default(),
+        }
+    }
+}
+
+impl Game for DotsAndBoxes {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = Edge;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Board;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if self.is_finished() {
+            return Err(GameError::GameIsFinished);
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        let Some(line) = data.cell() else {
+            return Err(GameError::invalid_move(format!(
+                "{} and {} aren't neighbouring dots",
+                data.first, data.second
+            )));
+        };
+        if !self.board.contains(line) {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                data
+            )));
+        }
+        if self.board[line].is_some() {
+            return Err(GameError::invalid_move(format!(
+                "line {} is already drawn",
+                data
+            )));
+        }
+
+        self.history.push(Snapshot::take(self, ()));
+        self.board[line] = id.into();
+        let completed = self
+            .boxes_around(line)
+            .into_iter()
+            .filter(|&pos| self.is_completed(pos))
+            .collect::<Vec<_>>();
+        for &pos in completed.iter() {
+            self.board[pos] = id.into();
+        }
+        if self.lines().all(|pos| self.board[pos].is_some()) {
+            return Ok(self.finish_by_box_count());
+        }
+        // completing a box earns another turn
+        if completed.is_empty() {
+            self.switch_player()
+        } else {
+            self.switch_player_to(id)
+        }
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        if !matches!(self.state, GameState::Turn(_)) {
+            return vec![];
+        }
+        self.lines()
+            .filter(|&pos| self.board[pos].is_none())
+            .map(Edge::from_cell)
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+
+    /// Boxes completed by each player.
+    fn scores(&self) -> Vec<f32> {
+        match self.state {
+            GameState::Finished(finished) if finished.reason() == TerminationReason::BoxCount => (0
+                ..PlayerPosition::from(Self::NUM_PLAYERS))
+                .map(|player| self.box_count(player) as f32)
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+impl DotsAndBoxes {
+    /// Returns cells of the board the lines are drawn on, they lie between two dots.
+    fn lines(&self) -> impl Iterator<Item = GridIndex> + '_ {
+        self.board
+            .all_indexed()
+            .map(|(pos, _)| pos)
+            .filter(|pos| (pos.row() + pos.col()) % 2 == 1)
+    }
+
+    /// Returns the boxes on both sides of the line, a line on the border has only one.
+    fn boxes_around(&self, line: GridIndex) -> Vec<GridIndex> {
+        let (row, col) = (line.row(), line.col());
+        let sides = if row.is_multiple_of(2) {
+            [(row.checked_sub(1), Some(col)), (Some(row + 1), Some(col))]
+        } else {
+            [(Some(row), col.checked_sub(1)), (Some(row), Some(col + 1))]
+        };
+        sides
+            .into_iter()
+            .filter_map(|side| match side {
+                (Some(row), Some(col)) => Some(GridIndex::new(row, col)),
+                _ => None,
+            })
+            .filter(|&pos| self.board.contains(pos))
+            .collect()
+    }
+
+    /// Checks if all four lines around the box are drawn.
+    fn is_completed(&self, pos: GridIndex) -> bool {
+        let (row, col) = (pos.row(), pos.col());
+        [
+            (row - 1, col),
+            (row + 1, col),
+            (row, col - 1),
+            (row, col + 1),
+        ]
+        .into_iter()
+        .all(|(row, col)| self.board[GridIndex::new(row, col)].is_some())
+    }
+
+    fn box_count(&self, player: PlayerPosition) -> usize {
+        self.board
+            .all_indexed()
+            .filter(|(pos, cell)| {
+                pos.row() % 2 == 1 && pos.col() % 2 == 1 && **cell == BoardCell(Some(player))
+            })
+            .count()
+    }
+
+    fn finish_by_box_count(&mut self) -> GameState {
+        match self.box_count(0).cmp(&self.box_count(1)) {
+            Ordering::Greater => self.set_winner(0, TerminationReason::BoxCount),
+            Ordering::Less => self.set_winner(1, TerminationReason::BoxCount),
+            Ordering::Equal => self.set_draw(TerminationReason::BoxCount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::FinishedState;
+
+    fn edge(first: (usize, usize), second: (usize, usize)) -> Edge {
+        Edge::new(first.into(), second.into())
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut game = DotsAndBoxes::new();
+        assert_eq!(game.legal_actions().len(), 60);
+        assert_eq!(
+            game.update(0, edge((0, 0), (1, 1))).unwrap_err(),
+            GameError::invalid_move("C0,R0 and C1,R1 aren't neighbouring dots".to_string())
+        );
+        assert_eq!(
+            game.update(0, edge((5, 5), (5, 6))).unwrap_err(),
+            GameError::invalid_move("C5,R5-C6,R5 is out of the board".to_string())
+        );
+        assert_eq!(
+            game.update(0, edge((1, 0), (0, 0))).unwrap(),
+            GameState::Turn(1)
+        );
+        assert_eq!(game.board()[GridIndex::new(1, 0)], BoardCell(Some(0)));
+        // the order of the dots doesn't matter
+        assert_eq!(
+            game.update(1, edge((0, 0), (1, 0))).unwrap_err(),
+            GameError::invalid_move("line C0,R0-C0,R1 is already drawn".to_string())
+        );
+        assert!(!game.legal_actions().contains(&edge((0, 0), (1, 0))));
+    }
+
+    #[test]
+    fn test_extra_turn() {
+        let mut game = DotsAndBoxes::new();
+        game.update(0, edge((0, 0), (0, 1))).unwrap();
+        game.update(1, edge((0, 0), (1, 0))).unwrap();
+        game.update(0, edge((1, 0), (1, 1))).unwrap();
+        // the second player completes the box and moves again
+        assert_eq!(
+            game.update(1, edge((0, 1), (1, 1))).unwrap(),
+            GameState::Turn(1)
+        );
+        assert_eq!(game.board()[GridIndex::new(1, 1)], BoardCell(Some(1)));
+        assert_eq!(
+            game.update(0, edge((2, 0), (2, 1))).unwrap_err(),
+            GameError::not_your_turn(1, 0)
+        );
+        assert_eq!(
+            game.update(1, edge((2, 0), (2, 1))).unwrap(),
+            GameState::Turn(0)
+        );
+
+        game.undo().unwrap();
+        assert_eq!(game.undo().unwrap(), GameState::Turn(1));
+        assert_eq!(game.board()[GridIndex::new(1, 1)], BoardCell(None));
+        assert_eq!(
+            game.update(1, edge((0, 1), (1, 1))).unwrap(),
+            GameState::Turn(1)
+        );
+    }
+
+    #[test]
+    fn test_box_count() {
+        let mut game = DotsAndBoxes::new();
+        while let Some(&line) = game.legal_actions().first() {
+            let GameState::Turn(player) = game.state() else {
+                unreachable!("legal actions are empty once the game is finished");
+            };
+            game.update(player, line).unwrap();
+        }
+        let scores = game.scores();
+        assert_eq!(scores.iter().sum::<f32>(), 25.0);
+        let winner = if scores[0] > scores[1] { 0 } else { 1 };
+        assert_eq!(
+            game.state(),
+            GameState::Finished(FinishedState::Win(winner, TerminationReason::BoxCount))
+        );
+    }
+}
@@ -19,6 +19,10 @@ pub enum ProtobufError {
     InvalidBoardLength { expected: usize, found: usize },
     #[error("game state is invalid")]
     InvalidGameState,
+    #[error("time control is invalid")]
+    InvalidTimeControl,
+    #[error("invalid chess piece kind: {0}")]
+    InvalidPieceKind(i32),
     #[error("message data has missing field: {missing_field}")]
     MessageDataMissing { missing_field: String },
     #[error(transparent)]
@@ -35,6 +39,12 @@ pub trait ToProtobuf {
     fn to_protobuf(self) -> ProtobufResult<Vec<u8>>;
 }
 
+/// Turn data received from a client, checked against the board before it reaches the game.
+pub trait CheckBounds {
+    /// Fails if the data refers to a cell outside of the `rows`×`cols` board.
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()>;
+}
+
 impl<T: Default + prost::Message> FromProtobuf for T {
     fn from_protobuf(buf: &[u8]) -> ProtobufResult<Self> {
         Ok(T::decode(buf)?)
@@ -60,6 +70,31 @@ impl FromProtobuf for GridIndex {
     }
 }
 
+impl CheckBounds for GridIndex {
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        if self.row() >= rows {
+            return Err(ProtobufError::InvalidGridRow {
+                max_expected: rows,
+                found: self.row().saturating_add(1),
+            });
+        }
+        if self.col() >= cols {
+            return Err(ProtobufError::InvalidGridCol {
+                max_expected: cols,
+                found: self.col().saturating_add(1),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Turn data without coordinates, there is nothing to check.
+impl CheckBounds for () {
+    fn check_bounds(&self, _rows: usize, _cols: usize) -> ProtobufResult<()> {
+        Ok(())
+    }
+}
+
 impl<T: prost::Message> ToProtobuf for T {
     fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
         Ok(self.encode_to_vec())
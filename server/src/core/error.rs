@@ -22,8 +22,16 @@ pub enum GameError {
         expected: PlayerPosition,
         found: PlayerPosition,
     },
+    #[error("there are no turns to undo")]
+    NothingToUndo,
     #[error("failed to switch players in the pool")]
     PlayerPoolCorrupted,
+    #[error("player {player} has already made its move this round")]
+    AlreadyMoved { player: PlayerPosition },
+    #[error("player {player} has already contributed to the seed")]
+    AlreadyContributed { player: PlayerPosition },
+    #[error("dice haven't been rolled yet")]
+    DiceNotRolled,
 }
 
 impl GameError {
@@ -46,4 +54,12 @@ impl GameError {
     pub fn unauthorized_move(expected: PlayerPosition, found: PlayerPosition) -> Self {
         Self::UnauthorizedMove { expected, found }
     }
+
+    pub fn already_moved(player: PlayerPosition) -> Self {
+        Self::AlreadyMoved { player }
+    }
+
+    pub fn already_contributed(player: PlayerPosition) -> Self {
+        Self::AlreadyContributed { player }
+    }
 }
@@ -0,0 +1,392 @@
+use std::cmp::Ordering;
+
+use generic_array::typenum;
+use generic_array::ArrayLength;
+use prost::Message;
+
+use super::grid::{GridLayout, WithGridIndex};
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    game_fields, BoardCell, CheckBounds, FromProtobuf, Game, GameError, GameResult, GameState,
+    Grid, GridIndex, History, PlayerPosition, ProtobufError, ProtobufResult, Snapshot,
+    TerminationReason, ToProtobuf,
+};
+use crate::proto;
+
+pub type Go9 = Go<typenum::U9>;
+pub type Go13 = Go<typenum::U13>;
+
+/// Points given to the second player to compensate for the first move.
+pub const KOMI: f32 = 6.5;
+
+type Cell = BoardCell<PlayerPosition>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TurnData {
+    Place(GridIndex),
+    Pass,
+}
+
+impl FromProtobuf for TurnData {
+    fn from_protobuf(buf: &[u8]) -> Result<Self, ProtobufError> {
+        let turn = proto::GoTurn::decode(buf)?;
+        match turn.place {
+            Some(pos) => Ok(Self::Place(GridIndex::new(
+                pos.row.try_into()?,
+                pos.col.try_into()?,
+            ))),
+            None => Ok(Self::Pass),
+        }
+    }
+}
+
+impl CheckBounds for TurnData {
+    fn check_bounds(&self, rows: usize, cols: usize) -> ProtobufResult<()> {
+        match self {
+            Self::Place(pos) => pos.check_bounds(rows, cols),
+            Self::Pass => Ok(()),
+        }
+    }
+}
+
+impl ToProtobuf for TurnData {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        let place = match self {
+            Self::Place(pos) => Some(pos.try_into()?),
+            Self::Pass => None,
+        };
+        proto::GoTurn { place }.to_protobuf()
+    }
+}
+
+/// Go on an `S`×`S` board with positional superko and area scoring,
+/// the game ends after two consecutive passes. Dead stones aren't removed
+/// when the game is scored, players are expected to capture them before passing.
+#[derive(Clone, Debug)]
+pub struct Go<S: ArrayLength> {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Grid<Cell, S, S>,
+    /// Number of passes made in a row.
+    passes: usize,
+    /// Boards before each turn, a placement can't repeat any of them.
+    positions: Vec<Grid<Cell, S, S>>,
+    history: History<Snapshot<Self, usize>>,
+}
+
+impl<S: ArrayLength> Default for Go<S> {
+    fn default() -> Self {
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            board: Grid::default(),
+            passes: 0,
+            positions: vec![],
+            history: History::default(),
+        }
+    }
+}
+
+impl<S: ArrayLength> Game for Go<S> {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = TurnData;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Grid<Cell, S, S>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if self.is_finished() {
+            return Err(GameError::GameIsFinished);
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        let board = match data {
+            TurnData::Place(pos) => Some(self.place(pos, id)?),
+            TurnData::Pass => None,
+        };
+
+        self.history.push(Snapshot::take(self, self.passes));
+        self.positions.push(self.board.clone());
+        match board {
+            Some(board) => {
+                self.board = board;
+                self.passes = 0;
+            }
+            None => self.passes += 1,
+        }
+        if self.passes == Self::NUM_PLAYERS as usize {
+            return Ok(self.finish_by_score());
+        }
+        self.switch_player()
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        let GameState::Turn(player) = self.state else {
+            return vec![];
+        };
+        self.board
+            .all_indexed()
+            .filter(|(pos, cell)| cell.is_none() && self.place(*pos, player).is_ok())
+            .map(|(pos, _)| TurnData::Place(pos))
+            .chain([TurnData::Pass])
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.passes = self.history.pop()?.restore(self);
+        self.positions.pop();
+        Ok(self.state)
+    }
+
+    game_fields!();
+
+    fn scores(&self) -> Vec<f32> {
+        match self.state {
+            GameState::Finished(finished) if finished.reason() == TerminationReason::Score => {
+                self.count_area()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl<S: ArrayLength> Go<S> {
+    /// Returns the board after the player's stone is placed on `pos`
+    /// and the opponent's groups left without liberties are captured.
+    fn place(&self, pos: GridIndex, player: PlayerPosition) -> GameResult<Grid<Cell, S, S>> {
+        let size = S::to_usize();
+        if pos.row() >= size || pos.col() >= size {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                pos
+            )));
+        }
+        if self.board[pos].is_some() {
+            return Err(GameError::cell_is_occupied(pos.row(), pos.col()));
+        }
+
+        let mut board = self.board.clone();
+        board[pos] = player.into();
+        for neighbour in Self::neighbours(&board, pos) {
+            if board[neighbour].is_some_and(|owner| owner != player) {
+                let (group, liberties) = Self::group(&board, neighbour);
+                if liberties == 0 {
+                    for stone in group {
+                        board[stone] = BoardCell(None);
+                    }
+                }
+            }
+        }
+        if Self::group(&board, pos).1 == 0 {
+            return Err(GameError::invalid_move(format!(
+                "stone on {} would have no liberties",
+                pos
+            )));
+        }
+        if self.positions.iter().any(|position| **position == *board) {
+            return Err(GameError::invalid_move(format!(
+                "stone on {} repeats an earlier position",
+                pos
+            )));
+        }
+        Ok(board)
+    }
+
+    /// Returns indices of the cells orthogonally adjacent to `pos`.
+    fn neighbours(board: &Grid<Cell, S, S>, pos: GridIndex) -> Vec<GridIndex> {
+        [
+            board.left_iter(pos).indexed().nth(1),
+            board.right_iter(pos).indexed().nth(1),
+            board.top_iter(pos).indexed().nth(1),
+            board.bottom_iter(pos).indexed().nth(1),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(index, _)| index)
+        .collect()
+    }
+
+    /// Returns cells of the connected area of cells equal to the one on `pos`
+    /// together with the contents of the cells bordering it.
+    fn area(board: &Grid<Cell, S, S>, pos: GridIndex) -> (Vec<GridIndex>, Vec<(GridIndex, Cell)>) {
+        let cell = board[pos];
+        let mut visited = Grid::<bool, S, S>::default();
+        let mut area = vec![pos];
+        let mut border = vec![];
+        visited[pos] = true;
+        let mut i = 0;
+        while let Some(&current) = area.get(i) {
+            for neighbour in Self::neighbours(board, current) {
+                if visited[neighbour] {
+                    continue;
+                }
+                visited[neighbour] = true;
+                if board[neighbour] == cell {
+                    area.push(neighbour);
+                } else {
+                    border.push((neighbour, board[neighbour]));
+                }
+            }
+            i += 1;
+        }
+        (area, border)
+    }
+
+    /// Returns stones of the group on `pos` and the number of its liberties.
+    fn group(board: &Grid<Cell, S, S>, pos: GridIndex) -> (Vec<GridIndex>, usize) {
+        let (group, border) = Self::area(board, pos);
+        let liberties = border.iter().filter(|(_, cell)| cell.is_none()).count();
+        (group, liberties)
+    }
+
+    /// Counts stones of each player and empty areas bordered by their stones only,
+    /// komi is added to the second player.
+    fn count_area(&self) -> Vec<f32> {
+        let mut scores = vec![0.0, KOMI];
+        let mut counted = Grid::<bool, S, S>::default();
+        for (pos, cell) in self.board.all_indexed() {
+            if let BoardCell(Some(player)) = cell {
+                scores[*player as usize] += 1.0;
+                continue;
+            }
+            if counted[pos] {
+                continue;
+            }
+            let (area, border) = Self::area(&self.board, pos);
+            for &cell in &area {
+                counted[cell] = true;
+            }
+            let mut owners = border.iter().filter_map(|(_, cell)| cell.0);
+            if let Some(owner) = owners.next() {
+                if owners.all(|other| other == owner) {
+                    scores[owner as usize] += area.len() as f32;
+                }
+            }
+        }
+        scores
+    }
+
+    fn finish_by_score(&mut self) -> GameState {
+        let scores = self.count_area();
+        match scores[0].total_cmp(&scores[1]) {
+            Ordering::Greater => self.set_winner(0, TerminationReason::Score),
+            Ordering::Less => self.set_winner(1, TerminationReason::Score),
+            Ordering::Equal => self.set_draw(TerminationReason::Score),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{FinishedState, WithCells};
+
+    fn place(row: usize, col: usize) -> TurnData {
+        TurnData::Place(GridIndex::new(row, col))
+    }
+
+    #[test]
+    fn test_capture() {
+        // white stone in the corner has a single liberty left
+        let mut game = Go9::with_cells(&[((0, 0), 1), ((0, 1), 0)]);
+        assert_eq!(game.update(0, place(1, 0)).unwrap(), GameState::Turn(1));
+        assert_eq!(game.board()[GridIndex::new(0, 0)], BoardCell(None));
+
+        // playing into the captured cell is a suicide
+        assert_eq!(
+            game.update(1, place(0, 0)).unwrap_err(),
+            GameError::invalid_move("stone on C0,R0 would have no liberties".to_string())
+        );
+        assert!(!game.legal_actions().contains(&place(0, 0)));
+        assert_eq!(
+            game.update(1, place(0, 1)).unwrap_err(),
+            GameError::cell_is_occupied(0, 1)
+        );
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(0));
+        assert_eq!(game.board()[GridIndex::new(0, 0)], BoardCell(Some(1)));
+    }
+
+    #[test]
+    fn test_ko() {
+        let mut game = Go9::with_cells(&[
+            ((0, 1), 0),
+            ((1, 0), 0),
+            ((2, 1), 0),
+            ((0, 2), 1),
+            ((1, 1), 1),
+            ((1, 3), 1),
+            ((2, 2), 1),
+        ]);
+        game.update(0, place(1, 2)).unwrap();
+        assert_eq!(game.board()[GridIndex::new(1, 1)], BoardCell(None));
+
+        // immediate recapture would repeat the position
+        assert_eq!(
+            game.update(1, place(1, 1)).unwrap_err(),
+            GameError::invalid_move("stone on C1,R1 repeats an earlier position".to_string())
+        );
+        game.update(1, place(8, 8)).unwrap();
+        game.update(0, place(8, 0)).unwrap();
+        game.update(1, place(1, 1)).unwrap();
+        assert_eq!(game.board()[GridIndex::new(1, 2)], BoardCell(None));
+    }
+
+    #[test]
+    fn test_area_scoring() {
+        let mut game = Go9::new();
+        assert_eq!(game.legal_actions().len(), 9 * 9 + 1);
+        // black walls off the two left columns, white the column on the right edge
+        for row in 0..9 {
+            game.update(0, place(row, 2)).unwrap();
+            game.update(1, place(row, 7)).unwrap();
+        }
+        assert_eq!(game.update(0, TurnData::Pass).unwrap(), GameState::Turn(1));
+        assert!(game.scores().is_empty());
+        assert_eq!(
+            game.update(1, TurnData::Pass).unwrap(),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::Score))
+        );
+        // the area between the walls touches both colors and isn't counted
+        assert_eq!(game.scores(), vec![27.0, 18.0 + KOMI]);
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(1));
+        assert!(game.scores().is_empty());
+    }
+
+    #[test]
+    fn test_undo_to_start() {
+        let mut game = Go13::new();
+        let plies = 150;
+        for _ in 0..plies {
+            let GameState::Turn(player) = game.state() else {
+                panic!("the game shouldn't be finished");
+            };
+            let action = game.legal_actions()[0];
+            game.update(player, action).unwrap();
+        }
+        for _ in 0..plies {
+            game.undo().unwrap();
+        }
+        assert_eq!(game.undo().unwrap_err(), GameError::NothingToUndo);
+        assert_eq!(game.state(), GameState::Turn(0));
+        assert!(game.board().all_indexed().all(|(_, cell)| cell.is_none()));
+        assert!(game.positions.is_empty());
+    }
+
+    #[test]
+    fn test_protobuf() {
+        for data in [place(3, 4), TurnData::Pass] {
+            assert_eq!(
+                TurnData::from_protobuf(&data.to_protobuf().unwrap()).unwrap(),
+                data
+            );
+        }
+    }
+}
@@ -129,92 +129,236 @@ impl<T, R: ArrayLength, C: ArrayLength> IndexMut<GridIndex> for Grid<T, R, C> {
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> Grid<T, R, C> {
+/// Cells arranged in rows and columns, the dimensions are known either at compile time or at runtime.
+pub trait GridLayout: Index<GridIndex> {
+    fn rows(&self) -> usize;
+    fn cols(&self) -> usize;
+
+    /// Checks if `pos` points to a cell of the grid.
+    fn contains(&self, pos: GridIndex) -> bool {
+        pos.row < self.rows() && pos.col < self.cols()
+    }
+
+    /// Returns the cell on `pos`, [`None`] if it's out of the grid.
+    fn get(&self, pos: GridIndex) -> Option<&Self::Output> {
+        self.contains(pos).then(|| &self[pos])
+    }
+
+    /// Returns the mutable cell on `pos`, [`None`] if it's out of the grid.
+    fn get_mut(&mut self, pos: GridIndex) -> Option<&mut Self::Output>
+    where
+        Self: IndexMut<GridIndex>,
+    {
+        if !self.contains(pos) {
+            return None;
+        }
+        Some(&mut self[pos])
+    }
+
     /// Returns an iterator to indexed grid elements row by row
-    pub fn all_indexed(&self) -> impl Iterator<Item = (GridIndex, &T)> {
-        (0..self.contents.len())
+    fn all_indexed(&self) -> impl Iterator<Item = (GridIndex, &Self::Output)>
+    where
+        Self: Sized,
+    {
+        (0..self.rows())
             .map(|i| self.right_iter((i, 0).into()).indexed())
             .flatten()
     }
 
     /// Returns an iterator with rightwards direction that starts with a `pos`.
-    pub fn right_iter(&self, pos: GridIndex) -> RightGridIterator<T, R, C> {
+    fn right_iter(&self, pos: GridIndex) -> RightGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         RightGridIterator {
             current: pos,
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns an iterator with leftwards direction that starts with a `pos`.
-    pub fn left_iter(&self, pos: GridIndex) -> LeftGridIterator<T, R, C> {
+    fn left_iter(&self, pos: GridIndex) -> LeftGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         LeftGridIterator {
             current: Some(pos),
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns an iterator with upwards direction that starts with a `pos`.
-    pub fn top_iter(&self, pos: GridIndex) -> TopGridIterator<T, R, C> {
+    fn top_iter(&self, pos: GridIndex) -> TopGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         TopGridIterator {
             current: Some(pos),
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns an iterator with downwards direction that starts with a `pos`.
-    pub fn bottom_iter(&self, pos: GridIndex) -> BottomGridIterator<T, R, C> {
+    fn bottom_iter(&self, pos: GridIndex) -> BottomGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         BottomGridIterator {
             current: pos,
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns a diagonal iterator with top-left direction that starts with a `pos`.
-    pub fn top_left_iter(&self, pos: GridIndex) -> TopLeftGridIterator<T, R, C> {
+    fn top_left_iter(&self, pos: GridIndex) -> TopLeftGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         TopLeftGridIterator {
             current: Some(pos),
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns a diagonal iterator with top-right direction that starts with a `pos`.
-    pub fn top_right_iter(&self, pos: GridIndex) -> TopRightGridIterator<T, R, C> {
+    fn top_right_iter(&self, pos: GridIndex) -> TopRightGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         TopRightGridIterator {
             current: Some(pos),
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns a diagonal iterator with bottom-right direction that starts with a `pos`.
-    pub fn bottom_right_iter(&self, pos: GridIndex) -> BottomRightGridIterator<T, R, C> {
+    fn bottom_right_iter(&self, pos: GridIndex) -> BottomRightGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         BottomRightGridIterator {
             current: pos,
-            grid: &self,
+            grid: self,
         }
     }
 
     /// Returns a diagonal iterator with bottom-left direction that starts with a `pos`.
-    pub fn bottom_left_iter(&self, pos: GridIndex) -> BottomLeftGridIterator<T, R, C> {
+    fn bottom_left_iter(&self, pos: GridIndex) -> BottomLeftGridIterator<'_, Self>
+    where
+        Self: Sized,
+    {
         BottomLeftGridIterator {
             current: Some(pos),
-            grid: &self,
+            grid: self,
         }
     }
 }
 
+impl<T, R: ArrayLength, C: ArrayLength> GridLayout for Grid<T, R, C> {
+    fn rows(&self) -> usize {
+        R::to_usize()
+    }
+
+    fn cols(&self) -> usize {
+        C::to_usize()
+    }
+}
+
+/// Grid with dimensions chosen at runtime, cells are stored row by row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynGrid<T> {
+    rows: usize,
+    cols: usize,
+    contents: Vec<T>,
+}
+
+impl<T: Clone + Default> DynGrid<T> {
+    /// Creates a `rows`×`cols` grid filled with default values.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            contents: vec![T::default(); rows * cols],
+        }
+    }
+}
+
+impl<T> DynGrid<T> {
+    /// Appends the row to the bottom of the grid.
+    ///
+    /// # Panics
+    /// If the row has another number of cells than the grid has columns.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let len = self.contents.len();
+        self.contents.extend(row);
+        assert_eq!(
+            self.contents.len() - len,
+            self.cols,
+            "row has to have {} cells",
+            self.cols
+        );
+        self.rows += 1;
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(|row| &self.contents[row * self.cols..(row + 1) * self.cols])
+    }
+}
+
+impl<T: Display> Display for DynGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[\n")?;
+        for row in self.iter_rows() {
+            f.write_str("[")?;
+            for val in row {
+                write!(f, "{}", val)?;
+            }
+            f.write_str("]\n")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<T> Index<GridIndex> for DynGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: GridIndex) -> &Self::Output {
+        assert!(self.contains(index), "{} is out of the grid", index);
+        &self.contents[index.row * self.cols + index.col]
+    }
+}
+
+impl<T> IndexMut<GridIndex> for DynGrid<T> {
+    fn index_mut(&mut self, index: GridIndex) -> &mut Self::Output {
+        assert!(self.contains(index), "{} is out of the grid", index);
+        &mut self.contents[index.row * self.cols + index.col]
+    }
+}
+
+impl<T> GridLayout for DynGrid<T> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+}
+
 /// An iterator with rightwards direction.
 /// On each step it's incrementing `col` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct RightGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct RightGridIterator<'a, G> {
     current: GridIndex, // no need for an Option as we're only incrementing
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for RightGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for RightGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current.row < R::to_usize() && self.current.col < C::to_usize() {
+        if self.current.row < self.grid.rows() && self.current.col < self.grid.cols() {
             let old_current = self.current;
             self.current = GridIndex::new(self.current.row, self.current.col + 1);
             return Some(&self.grid[old_current]);
@@ -226,17 +370,17 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for RightGridIterator<'a, T
 /// An iterator with leftwards direction.
 /// On each step it's decrementing `col` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct LeftGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct LeftGridIterator<'a, G> {
     current: Option<GridIndex>,
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for LeftGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for LeftGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.current {
-            if current.row < R::to_usize() && current.col < C::to_usize() {
+            if current.row < self.grid.rows() && current.col < self.grid.cols() {
                 let old_current = current;
                 if current.col == 0 {
                     self.current = None;
@@ -253,17 +397,17 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for LeftGridIterator<'a, T,
 /// An iterator with upwards direction.
 /// On each step it's decrementing `row` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct TopGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct TopGridIterator<'a, G> {
     current: Option<GridIndex>,
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for TopGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.current {
-            if current.row < R::to_usize() && current.col < C::to_usize() {
+            if current.row < self.grid.rows() && current.col < self.grid.cols() {
                 let old_current = current;
                 if current.row == 0 {
                     self.current = None;
@@ -280,16 +424,16 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopGridIterator<'a, T,
 /// An iterator with downwards direction.
 /// On each step it's incrementing `row` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct BottomGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct BottomGridIterator<'a, G> {
     current: GridIndex, // no need for an Option as we're only incrementing
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for BottomGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for BottomGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current.row < R::to_usize() && self.current.col < C::to_usize() {
+        if self.current.row < self.grid.rows() && self.current.col < self.grid.cols() {
             let old_current = self.current;
             self.current = GridIndex::new(self.current.row + 1, self.current.col);
             return Some(&self.grid[old_current]);
@@ -301,17 +445,17 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for BottomGridIterator<'a,
 /// A diagonal iterator with top-left direction.
 /// On each step it's decrementing `col` and `row` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct TopLeftGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct TopLeftGridIterator<'a, G> {
     current: Option<GridIndex>,
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopLeftGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for TopLeftGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.current {
-            if current.row < R::to_usize() && current.col < C::to_usize() {
+            if current.row < self.grid.rows() && current.col < self.grid.cols() {
                 let old_current = current;
                 if current.row == 0 || current.col == 0 {
                     self.current = None;
@@ -328,17 +472,17 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopLeftGridIterator<'a,
 /// A diagonal iterator with top-right direction.
 /// On each step it's decrementing `row` and incrementing `col` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct TopRightGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct TopRightGridIterator<'a, G> {
     current: Option<GridIndex>,
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopRightGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for TopRightGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.current {
-            if current.row < R::to_usize() && current.col < C::to_usize() {
+            if current.row < self.grid.rows() && current.col < self.grid.cols() {
                 let old_current = current;
                 if current.row == 0 {
                     self.current = None;
@@ -355,16 +499,16 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for TopRightGridIterator<'a
 /// A diagonal iterator with bottom-right direction.
 /// On each step it's incrementing `col` and `row` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct BottomRightGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct BottomRightGridIterator<'a, G> {
     current: GridIndex, // no need for an Option as we're only incrementing
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for BottomRightGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for BottomRightGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current.row < R::to_usize() && self.current.col < C::to_usize() {
+        if self.current.row < self.grid.rows() && self.current.col < self.grid.cols() {
             let old_current = self.current;
             self.current = GridIndex::new(self.current.row + 1, self.current.col + 1);
             return Some(&self.grid[old_current]);
@@ -376,17 +520,17 @@ impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for BottomRightGridIterator
 /// A diagonal iterator with bottom-left direction.
 /// On each step it's incrementing `row` and decrementing `col` by 1 in the underlying [`GridIndex`].
 /// Stops when underlying [`GridIndex`] goes out of [`Grid`] scope.
-pub struct BottomLeftGridIterator<'a, T, R: ArrayLength, C: ArrayLength> {
+pub struct BottomLeftGridIterator<'a, G> {
     current: Option<GridIndex>,
-    grid: &'a Grid<T, R, C>,
+    grid: &'a G,
 }
 
-impl<'a, T, R: ArrayLength, C: ArrayLength> Iterator for BottomLeftGridIterator<'a, T, R, C> {
-    type Item = &'a T;
+impl<'a, G: GridLayout> Iterator for BottomLeftGridIterator<'a, G> {
+    type Item = &'a G::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(current) = self.current {
-            if current.row < R::to_usize() && current.col < C::to_usize() {
+            if current.row < self.grid.rows() && current.col < self.grid.cols() {
                 let old_current = current;
                 if current.col == 0 {
                     self.current = None;
@@ -416,49 +560,49 @@ pub trait WithGridIndex {
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for RightGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for RightGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         Some(self.current)
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for LeftGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for LeftGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         self.current
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for TopGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for TopGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         self.current
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for BottomGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for BottomGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         Some(self.current)
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for TopLeftGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for TopLeftGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         self.current
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for TopRightGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for TopRightGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         self.current
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for BottomRightGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for BottomRightGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         Some(self.current)
     }
 }
 
-impl<T, R: ArrayLength, C: ArrayLength> WithGridIndex for BottomLeftGridIterator<'_, T, R, C> {
+impl<G> WithGridIndex for BottomLeftGridIterator<'_, G> {
     fn get_index(&self) -> Option<GridIndex> {
         self.current
     }
@@ -488,6 +632,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::GameBoard;
     use generic_array::typenum;
 
     #[test]
@@ -528,4 +673,42 @@ mod test {
             .into_iter(),
         );
     }
+
+    #[test]
+    fn test_dyn_grid_matches_grid() {
+        let mut grid = Grid::<u32, typenum::U2, typenum::U3>::default();
+        let mut dyn_grid = DynGrid::<u32>::new(2, 3);
+        for (pos, value) in [(0, 0), (0, 2), (1, 1), (1, 2)].into_iter().zip(1..) {
+            grid[pos.into()] = value;
+            dyn_grid[pos.into()] = value;
+        }
+        itertools::assert_equal(grid.all_indexed(), dyn_grid.all_indexed());
+        let pos = GridIndex::new(1, 2);
+        itertools::assert_equal(
+            grid.top_left_iter(pos).indexed(),
+            dyn_grid.top_left_iter(pos).indexed(),
+        );
+        itertools::assert_equal(grid.left_iter(pos), dyn_grid.left_iter(pos));
+        assert_eq!(grid.get_content(), dyn_grid.get_content());
+    }
+
+    #[test]
+    fn test_dyn_grid_get() {
+        let mut grid = DynGrid::<usize>::new(2, 3);
+        *grid.get_mut((1, 2).into()).unwrap() = 1;
+        assert_eq!(grid.get((1, 2).into()), Some(&1));
+        assert_eq!(grid[(1, 2).into()], 1);
+
+        // column past the end doesn't wrap to the next row
+        assert_eq!(grid.get((0, 3).into()), None);
+        assert_eq!(grid.get((2, 0).into()), None);
+        assert_eq!(grid.get_mut((0, 5).into()), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dyn_grid_index_out_of_bounds() {
+        let grid = DynGrid::<usize>::new(2, 3);
+        let _ = grid[(0, 3).into()];
+    }
 }
@@ -0,0 +1,117 @@
+use std::fmt::{Debug, Formatter};
+
+use super::{Game, GameError, GameResult, GameState};
+
+/// Entries saved before each turn, the last one is taken back by undo.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    entries: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+
+impl<T> History<T> {
+    pub fn push(&mut self, entry: T) {
+        self.entries.push(entry);
+    }
+
+    pub fn pop(&mut self) -> GameResult<T> {
+        self.entries.pop().ok_or(GameError::NothingToUndo)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Players, state and board of a game saved before a turn,
+/// along with the `extra` part of the game the turn changes.
+pub struct Snapshot<G: Game, E = ()> {
+    players: G::Players,
+    state: GameState,
+    board: G::Board,
+    extra: E,
+}
+
+impl<G: Game, E> Snapshot<G, E>
+where
+    G::Players: Clone,
+    G::Board: Clone,
+{
+    pub fn take(game: &G, extra: E) -> Self {
+        Self {
+            players: game.players().clone(),
+            state: game.state(),
+            board: game.board().clone(),
+            extra,
+        }
+    }
+}
+
+impl<G: Game, E> Snapshot<G, E> {
+    /// Puts the saved players, state and board back into the game, returns the extra part.
+    pub fn restore(self, game: &mut G) -> E {
+        *game.players_mut() = self.players;
+        game.set_state(self.state);
+        game.set_board(self.board);
+        self.extra
+    }
+}
+
+impl<G: Game, E: Clone> Clone for Snapshot<G, E>
+where
+    G::Players: Clone,
+    G::Board: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            players: self.players.clone(),
+            state: self.state,
+            board: self.board.clone(),
+            extra: self.extra.clone(),
+        }
+    }
+}
+
+impl<G: Game, E: Debug> Debug for Snapshot<G, E>
+where
+    G::Players: Debug,
+    G::Board: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("players", &self.players)
+            .field("state", &self.state)
+            .field("board", &self.board)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut history = History::default();
+        assert!(history.is_empty());
+        assert_eq!(history.pop(), Err(GameError::NothingToUndo));
+        for entry in 0..1000 {
+            history.push(entry);
+        }
+        assert_eq!(history.len(), 1000);
+        for entry in (0..1000).rev() {
+            assert_eq!(history.pop(), Ok(entry));
+        }
+        assert_eq!(history.pop(), Err(GameError::NothingToUndo));
+    }
+}
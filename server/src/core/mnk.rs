@@ -0,0 +1,232 @@
+use std::marker::PhantomData;
+use std::ops::DerefMut;
+
+use generic_array::typenum::{self, Unsigned};
+use generic_array::ArrayLength;
+
+use super::grid::{Grid, GridIndex, GridLayout};
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    BoardCell, Game, GameError, GameResult, GameState, History, PlayerPosition, TerminationReason,
+};
+
+/// Five in a row on a 15×15 board.
+pub type Gomoku = MnkGame<typenum::U15, typenum::U15, typenum::U5>;
+
+type Cell = BoardCell<PlayerPosition>;
+
+/// Turn that has been made and what it has changed besides the marked cell.
+#[derive(Clone, Debug)]
+struct HistoryEntry {
+    pos: GridIndex,
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+}
+
+/// Players take turns placing their marks on an `R`×`C` board,
+/// the first one to get `K` marks in a row, column or diagonal wins.
+#[derive(Clone, Debug)]
+pub struct MnkGame<R: ArrayLength, C: ArrayLength, K: Unsigned> {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    field: Grid<Cell, R, C>,
+    history: History<HistoryEntry>,
+    _line_length: PhantomData<K>,
+}
+
+impl<R: ArrayLength, C: ArrayLength, K: Unsigned> Default for MnkGame<R, C, K> {
+    fn default() -> Self {
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            field: Grid::default(),
+            history: History::default(),
+            _line_length: PhantomData,
+        }
+    }
+}
+
+impl<R: ArrayLength, C: ArrayLength, K: Unsigned> Game for MnkGame<R, C, K> {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = GridIndex;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Grid<Cell, R, C>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if matches!(self.state, GameState::Finished(_)) {
+            return Err(GameError::GameIsFinished);
+        }
+        if id != *self.get_current_player()? {
+            return Err(GameError::not_your_turn(*self.get_current_player()?, id));
+        }
+        if !self.field.contains(data) {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                data
+            )));
+        }
+
+        let player_id = *self.get_current_player()?;
+        let cell = &mut self.field[data];
+        if cell.is_some() {
+            return Err(GameError::cell_is_occupied(data.row(), data.col()));
+        }
+        *cell = player_id.into();
+        self.history.push(HistoryEntry {
+            pos: data,
+            players: self.players.clone(),
+            state: self.state,
+        });
+
+        self.update_state(data)
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        if self.is_finished() {
+            return vec![];
+        }
+        self.field
+            .all_indexed()
+            .filter(|(_, cell)| cell.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        let entry = self.history.pop()?;
+        self.field[entry.pos] = BoardCell(None);
+        self.players = entry.players;
+        self.state = entry.state;
+        Ok(self.state)
+    }
+
+    fn board(&self) -> &Self::Board {
+        &self.field
+    }
+
+    fn board_mut(&mut self) -> impl DerefMut<Target = Self::Board> {
+        &mut self.field
+    }
+
+    fn players(&self) -> &Self::Players {
+        &self.players
+    }
+
+    fn players_mut(&mut self) -> &mut Self::Players {
+        &mut self.players
+    }
+
+    fn state(&self) -> GameState {
+        self.state
+    }
+
+    fn set_state(&mut self, state: GameState) {
+        self.state = state;
+    }
+
+    fn set_board(&mut self, board: Self::Board) {
+        self.field = board;
+    }
+}
+
+impl<R: ArrayLength, C: ArrayLength, K: Unsigned> MnkGame<R, C, K> {
+    fn update_state(&mut self, last: GridIndex) -> GameResult<GameState> {
+        if let BoardCell(Some(player)) = self.field[last] {
+            if self.is_line_completed(last, player) {
+                return Ok(self.set_winner(player, TerminationReason::LineCompleted));
+            }
+        }
+
+        if self.field.iter().flatten().all(|cell| cell.is_some()) {
+            return Ok(self.set_draw(TerminationReason::Stalemate));
+        }
+
+        self.switch_player()
+    }
+
+    /// Checks if the mark on `pos` is a part of `K` or more marks of the player in a line,
+    /// only lines that pass through `pos` are scanned.
+    fn is_line_completed(&self, pos: GridIndex, player: PlayerPosition) -> bool {
+        let field = &self.field;
+        let mark = BoardCell(Some(player));
+        let marks = |it: &mut dyn Iterator<Item = &Cell>| {
+            it.skip(1).take_while(|&&cell| cell == mark).count()
+        };
+        [
+            marks(&mut field.left_iter(pos)) + marks(&mut field.right_iter(pos)),
+            marks(&mut field.top_iter(pos)) + marks(&mut field.bottom_iter(pos)),
+            marks(&mut field.top_left_iter(pos)) + marks(&mut field.bottom_right_iter(pos)),
+            marks(&mut field.top_right_iter(pos)) + marks(&mut field.bottom_left_iter(pos)),
+        ]
+        .into_iter()
+        .any(|line| line + 1 >= K::to_usize())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::FinishedState;
+
+    fn play(game: &mut Gomoku, turns: &[(usize, usize)]) -> GameState {
+        let mut state = game.state();
+        for &(row, col) in turns {
+            let id = *game.get_current_player().unwrap();
+            state = game.update(id, GridIndex::new(row, col)).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_gomoku_row() {
+        let mut game = Gomoku::new();
+        assert_eq!(game.legal_actions().len(), 15 * 15);
+        // first player fills the row from the right edge, the second one plays below
+        let state = play(
+            &mut game,
+            &[
+                (7, 14),
+                (8, 14),
+                (7, 13),
+                (8, 13),
+                (7, 12),
+                (8, 12),
+                (7, 10),
+                (8, 10),
+            ],
+        );
+        assert_eq!(state, GameState::Turn(0));
+        // the gap in the middle is filled last
+        assert_eq!(
+            play(&mut game, &[(7, 11)]),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::LineCompleted))
+        );
+        assert!(game.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_gomoku_diagonal() {
+        let mut game = Gomoku::new();
+        // four marks in a diagonal are not enough
+        let state = play(
+            &mut game,
+            &[(0, 0), (0, 14), (1, 1), (1, 14), (2, 2), (2, 14), (3, 3)],
+        );
+        assert_eq!(state, GameState::Turn(1));
+        // second player completes the other diagonal
+        let state = play(
+            &mut game,
+            &[(14, 0), (0, 5), (13, 1), (0, 7), (12, 2), (0, 9)],
+        );
+        assert_eq!(state, GameState::Turn(1));
+        assert_eq!(
+            play(&mut game, &[(11, 3), (0, 11), (10, 4)]),
+            GameState::Finished(FinishedState::Win(1, TerminationReason::LineCompleted))
+        );
+    }
+}
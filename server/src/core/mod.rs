@@ -1,10 +1,22 @@
+pub mod backgammon;
+pub mod battleship;
+pub mod checkers;
 pub mod chess;
+pub mod connect_four;
+pub mod dots_and_boxes;
+pub mod go;
+pub mod mnk;
+pub mod reversi;
+pub mod rock_paper_scissors;
 pub mod tic_tac_toe;
 
+mod clock;
 mod encoding;
 mod error;
 mod grid;
+mod history;
 mod player_pool;
+mod random;
 
 use std::fmt::{Display, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -13,10 +25,13 @@ use generic_array::ArrayLength;
 
 use player_pool::{Player, PlayerQueue};
 
-pub use encoding::{FromProtobuf, ProtobufError, ProtobufResult, ToProtobuf};
+pub use clock::{Clocks, TimeControl};
+pub use encoding::{CheckBounds, FromProtobuf, ProtobufError, ProtobufResult, ToProtobuf};
 pub use error::GameError;
-pub use grid::{Grid, GridIndex};
+pub use grid::{DynGrid, Grid, GridIndex, GridLayout};
+pub use history::{History, Snapshot};
 pub use player_pool::PlayerIdQueue;
+pub use random::{Rng, Seed, SeedCommitment};
 
 pub type GameResult<T> = Result<T, GameError>;
 pub type PlayerPosition = u32; // TODO: change to u8
@@ -67,15 +82,128 @@ impl<T> DerefMut for BoardCell<T> {
     }
 }
 
+/// Rule that has ended the game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TerminationReason {
+    /// King is in check and its owner has no legal moves.
+    Checkmate,
+    /// Player has placed its marks in a line.
+    LineCompleted,
+    /// Player to move has no legal moves and isn't in check, or the board is full.
+    Stalemate,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    InsufficientMaterial,
+    Resignation,
+    Timeout,
+    /// Players have agreed to a draw.
+    Agreement,
+    /// Player to move has no legal moves left and loses.
+    NoLegalMoves,
+    /// Neither player can move, the one with more discs wins.
+    DiscCount,
+    /// Players have passed in a row and the points have been counted.
+    Score,
+    /// Every ship of the opponent has been hit.
+    FleetSunk,
+    /// Player has won the required number of rounds.
+    RoundsWon,
+    /// Player has borne off all of its checkers.
+    BorneOff,
+    /// Every line is drawn, the one who has completed more boxes wins.
+    BoxCount,
+}
+
+impl Display for TerminationReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TerminationReason::Checkmate => "checkmate",
+            TerminationReason::LineCompleted => "line completed",
+            TerminationReason::Stalemate => "stalemate",
+            TerminationReason::ThreefoldRepetition => "threefold repetition",
+            TerminationReason::FiftyMoveRule => "fifty-move rule",
+            TerminationReason::InsufficientMaterial => "insufficient material",
+            TerminationReason::Resignation => "resignation",
+            TerminationReason::Timeout => "timeout",
+            TerminationReason::Agreement => "agreement",
+            TerminationReason::NoLegalMoves => "no legal moves",
+            TerminationReason::DiscCount => "disc count",
+            TerminationReason::Score => "score",
+            TerminationReason::FleetSunk => "fleet sunk",
+            TerminationReason::RoundsWon => "rounds won",
+            TerminationReason::BorneOff => "borne off",
+            TerminationReason::BoxCount => "box count",
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FinishedState {
-    Win(PlayerPosition),
-    Draw,
+    Win(PlayerPosition, TerminationReason),
+    Draw(TerminationReason),
+}
+
+impl FinishedState {
+    pub fn reason(&self) -> TerminationReason {
+        match self {
+            FinishedState::Win(_, reason) | FinishedState::Draw(reason) => *reason,
+        }
+    }
+}
+
+/// Set of player positions stored as bits, positions beyond [`PlayerSet::CAPACITY`] are ignored.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlayerSet(u64);
+
+impl PlayerSet {
+    pub const CAPACITY: PlayerPosition = u64::BITS;
+
+    /// Returns the set of the first `count` players.
+    pub fn first(count: u8) -> Self {
+        (0..PlayerPosition::from(count)).collect()
+    }
+
+    pub fn contains(&self, player: PlayerPosition) -> bool {
+        1u64.checked_shl(player)
+            .is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn insert(&mut self, player: PlayerPosition) {
+        self.0 |= 1u64.checked_shl(player).unwrap_or_default();
+    }
+
+    pub fn remove(&mut self, player: PlayerPosition) {
+        self.0 &= !1u64.checked_shl(player).unwrap_or_default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns positions of the players in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = PlayerPosition> + '_ {
+        (0..Self::CAPACITY).filter(|&player| self.contains(player))
+    }
+}
+
+impl FromIterator<PlayerPosition> for PlayerSet {
+    fn from_iter<I: IntoIterator<Item = PlayerPosition>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for player in iter {
+            set.insert(player);
+        }
+        set
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameState {
     Turn(PlayerPosition),
+    /// Players move at once, the round is resolved after every player of the set has moved.
+    Waiting(PlayerSet),
+    /// Players of the set haven't contributed to the seed of the chance events yet,
+    /// nobody moves and the clocks don't run until the game is seeded.
+    Seeding(PlayerSet),
     Finished(FinishedState),
 }
 
@@ -83,10 +211,30 @@ impl TryFrom<crate::proto::GameState> for GameState {
     type Error = ProtobufError;
 
     fn try_from(value: crate::proto::GameState) -> Result<Self, Self::Error> {
+        let reason = || -> Result<TerminationReason, ProtobufError> {
+            crate::proto::TerminationReason::try_from(value.termination)
+                .map_err(|_| ProtobufError::InvalidGameState)?
+                .try_into()
+        };
+        if value
+            .waiting_player_ids
+            .iter()
+            .any(|&id| id >= PlayerSet::CAPACITY)
+        {
+            return Err(ProtobufError::InvalidGameState);
+        }
         let state = match (value.next_player_id, value.winner) {
-            (Some(next), None) => GameState::Turn(next),
-            (None, Some(winner)) => GameState::Finished(FinishedState::Win(winner)),
-            (None, None) => GameState::Finished(FinishedState::Draw),
+            (Some(next), None) if value.waiting_player_ids.is_empty() => GameState::Turn(next),
+            (None, None) if !value.waiting_player_ids.is_empty() => {
+                let players = value.waiting_player_ids.iter().copied().collect();
+                if value.seeding {
+                    GameState::Seeding(players)
+                } else {
+                    GameState::Waiting(players)
+                }
+            }
+            (None, Some(winner)) => GameState::Finished(FinishedState::Win(winner, reason()?)),
+            (None, None) => GameState::Finished(FinishedState::Draw(reason()?)),
             _ => return Err(ProtobufError::InvalidGameState),
         };
         Ok(state)
@@ -96,6 +244,8 @@ impl TryFrom<crate::proto::GameState> for GameState {
 pub trait GameBoard {
     type Item: ToProtobuf;
     fn get_content(&self) -> Vec<Vec<Self::Item>>;
+    /// Returns the number of rows and columns of the board.
+    fn dimensions(&self) -> (usize, usize);
 }
 
 impl<T, R: ArrayLength, C: ArrayLength> GameBoard for Grid<T, R, C>
@@ -109,19 +259,116 @@ where
             .map(|row| row.iter().cloned().collect())
             .collect()
     }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+}
+
+impl<T> GameBoard for DynGrid<T>
+where
+    T: Clone + ToProtobuf,
+{
+    type Item = T;
+
+    fn get_content(&self) -> Vec<Vec<Self::Item>> {
+        self.iter_rows().map(|row| row.to_vec()).collect()
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+}
+
+/// Implements the accessors of [`Game`] for a game that keeps its players, state and board
+/// in the fields with the same names.
+macro_rules! game_fields {
+    () => {
+        fn board(&self) -> &Self::Board {
+            &self.board
+        }
+
+        fn board_mut(&mut self) -> impl std::ops::DerefMut<Target = Self::Board> {
+            &mut self.board
+        }
+
+        fn set_board(&mut self, board: Self::Board) {
+            self.board = board;
+        }
+
+        fn players(&self) -> &Self::Players {
+            &self.players
+        }
+
+        fn players_mut(&mut self) -> &mut Self::Players {
+            &mut self.players
+        }
+
+        fn state(&self) -> GameState {
+            self.state
+        }
+
+        fn set_state(&mut self, state: GameState) {
+            self.state = state;
+        }
+    };
+}
+pub(crate) use game_fields;
+
+/// Test fixture: a new game with the given cells of its board occupied.
+#[cfg(test)]
+pub(crate) trait WithCells<T>: Game {
+    fn with_cells(cells: &[((usize, usize), T)]) -> Self;
+}
+
+#[cfg(test)]
+impl<G, T, R, C> WithCells<T> for G
+where
+    G: Game<Board = Grid<BoardCell<T>, R, C>>,
+    T: Clone,
+    R: ArrayLength,
+    C: ArrayLength,
+{
+    fn with_cells(cells: &[((usize, usize), T)]) -> Self {
+        let mut board = Grid::default();
+        for (pos, item) in cells {
+            board[(*pos).into()] = item.clone().into();
+        }
+        let mut game = Self::new();
+        game.set_board(board);
+        game
+    }
 }
 
 pub trait Game: Sized {
     const NUM_PLAYERS: u8;
-    type TurnData: FromProtobuf + ToProtobuf;
+    type TurnData: FromProtobuf + ToProtobuf + CheckBounds;
     type Players: PlayerQueue<Id = PlayerPosition>;
     type Board: GameBoard;
 
     fn new() -> Self;
+
+    /// Creates a game from the starting position with the given number, [`None`] if there is
+    /// no such position. Games with a single starting position accept only [`None`].
+    fn from_start_position(position: Option<u32>) -> Option<Self> {
+        position.is_none().then(Self::new)
+    }
+
+    /// Returns number of the starting position in games that have several of them.
+    fn start_position(&self) -> Option<u32> {
+        None
+    }
+
     fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState>;
+    /// Returns all turns the current player is allowed to make, empty once the game is finished.
+    fn legal_actions(&self) -> Vec<Self::TurnData>;
+    /// Takes back the last turn restoring the game exactly as it was before it.
+    fn undo(&mut self) -> GameResult<GameState>;
 
     fn board(&self) -> &Self::Board;
-    fn board_mut(&mut self) -> &mut Self::Board;
+    /// Gives direct access to the board, games that keep derived state
+    /// bring it in line with the board once the returned guard is dropped.
+    fn board_mut(&mut self) -> impl DerefMut<Target = Self::Board>;
     fn set_board(&mut self, board: Self::Board);
 
     fn players(&self) -> &Self::Players;
@@ -130,20 +377,60 @@ pub trait Game: Sized {
     fn state(&self) -> GameState;
     fn set_state(&mut self, state: GameState);
 
+    /// Set for games where players don't see everything, their boards are sent
+    /// along with every turn then.
+    const HIDDEN_INFORMATION: bool = false;
+
+    /// Returns the board content `viewer` is allowed to see, [`None`] stands for a spectator.
+    fn board_view(
+        &self,
+        _viewer: Option<PlayerPosition>,
+    ) -> Vec<Vec<<Self::Board as GameBoard>::Item>> {
+        self.get_board_content()
+    }
+
+    /// Set for games with chance events, they don't start until the players have agreed
+    /// on the seed of the dice with the server, see [`SeedCommitment`].
+    const CHANCE: bool = false;
+
+    /// Seeds the generator chance events are drawn from, it's done once before the first turn.
+    fn seed(&mut self, _seed: Seed) -> GameResult<GameState> {
+        Ok(self.state())
+    }
+
+    /// Returns the dice rolled for the player to move, empty in games without chance events.
+    fn dice(&self) -> Vec<u32> {
+        vec![]
+    }
+
+    /// Returns points of each player ordered by position once they are counted,
+    /// empty for games whose result isn't decided by points.
+    fn scores(&self) -> Vec<f32> {
+        vec![]
+    }
+
     fn is_finished(&self) -> bool {
         matches!(self.state(), GameState::Finished(_))
     }
 
-    fn set_draw(&mut self) -> GameState {
-        self.set_state(GameState::Finished(FinishedState::Draw));
+    fn set_draw(&mut self, reason: TerminationReason) -> GameState {
+        self.set_state(GameState::Finished(FinishedState::Draw(reason)));
         self.state()
     }
 
-    fn set_winner(&mut self, id: PlayerPosition) -> GameState {
-        self.set_state(GameState::Finished(FinishedState::Win(id)));
+    fn set_winner(&mut self, id: PlayerPosition, reason: TerminationReason) -> GameState {
+        self.set_state(GameState::Finished(FinishedState::Win(id, reason)));
         self.state()
     }
 
+    /// Decodes turn data received from a client making sure it doesn't point out of the board.
+    fn decode_turn_data(&self, buf: &[u8]) -> ProtobufResult<Self::TurnData> {
+        let data = Self::TurnData::from_protobuf(buf)?;
+        let (rows, cols) = self.board().dimensions();
+        data.check_bounds(rows, cols)?;
+        Ok(data)
+    }
+
     fn get_board_content(&self) -> Vec<Vec<<Self::Board as GameBoard>::Item>> {
         self.board().get_content()
     }
@@ -174,4 +461,14 @@ pub trait Game: Sized {
         self.set_state(GameState::Turn(next_player));
         Ok(self.state())
     }
+
+    /// Hands the turn to the player instead of the next one in the queue,
+    /// used when the rules let the current player move again.
+    fn switch_player_to(&mut self, id: PlayerPosition) -> GameResult<GameState> {
+        self.players_mut()
+            .set_current(id)
+            .ok_or(GameError::PlayerNotFound)?;
+        self.set_state(GameState::Turn(id));
+        Ok(self.state())
+    }
 }
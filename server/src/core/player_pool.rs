@@ -29,6 +29,16 @@ pub trait PlayerQueue {
     {
         self.as_slice().iter().find(f)
     }
+
+    /// Advances the queue until the player with the id is the current one,
+    /// returns [`None`] if there is no such player.
+    fn set_current(&mut self, id: Self::Id) -> Option<&Self::Item> {
+        self.find_if(|player| player.id() == id)?;
+        while self.get_current()?.id() != id {
+            self.next()?;
+        }
+        self.get_current()
+    }
 }
 
 /// Queue that stores only player ids
@@ -215,6 +225,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_set_current() {
+        let mut pool = PlayerDataQueue::new(vec![1u64, 2, 3]);
+        pool.next();
+        assert_eq!(pool.set_current(1), Some(&1));
+        // the current player stays if it's set again
+        assert_eq!(pool.set_current(1), Some(&1));
+        assert_eq!(pool.next(), Some(&2));
+        assert_eq!(pool.set_current(4), None);
+        assert_eq!(pool.get_current(), Some(&2));
+    }
+
     #[test]
     fn test_as_slice() {
         let mut pool = PlayerDataQueue::new(vec![1u64, 2, 3]);
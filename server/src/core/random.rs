@@ -0,0 +1,172 @@
+use sha2::{Digest, Sha256};
+
+use super::{GameError, GameResult, PlayerPosition, PlayerSet};
+
+pub type Seed = [u8; 32];
+
+/// SplitMix64 step, returns updated state and generated value. It's fast and deterministic
+/// but predictable, so it's used only where nobody gains from guessing the numbers.
+pub(crate) const fn split_mix(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+/// Deterministic generator of chance events, anyone who knows the seed can recompute them.
+/// The n-th number is the first 8 bytes of `sha256(seed || n)` read as little endian,
+/// `n` is encoded as 8 little endian bytes and starts from 0.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Rng {
+    seed: Seed,
+    index: u64,
+}
+
+impl Rng {
+    pub fn new(seed: Seed) -> Self {
+        Self { seed, index: 0 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let hash = Sha256::new()
+            .chain_update(self.seed)
+            .chain_update(self.index.to_le_bytes())
+            .finalize();
+        self.index += 1;
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Rolls a die with the given number of sides, the result is `next_u64() % sides + 1`.
+    pub fn roll(&mut self, sides: u32) -> u32 {
+        (self.next_u64() % u64::from(sides)) as u32 + 1
+    }
+}
+
+/// Commit-reveal agreement on a seed: the server publishes the hash of its secret before
+/// the players contribute their entropy, so neither side is able to pick the seed alone.
+/// The seed is `sha256(secret || sha256(entropy) of each player by position)`,
+/// the secret is revealed once the game is finished to let the players check the dice.
+#[derive(Clone, Debug)]
+pub struct SeedCommitment {
+    secret: Seed,
+    entropy: Vec<Option<Vec<u8>>>,
+}
+
+impl SeedCommitment {
+    /// Starts the agreement with a secret drawn from the operating system.
+    pub fn new(players: usize) -> Self {
+        let mut secret = Seed::default();
+        getrandom::getrandom(&mut secret).expect("random source of the system is available");
+        Self::with_secret(secret, players)
+    }
+
+    pub fn with_secret(secret: Seed, players: usize) -> Self {
+        Self {
+            secret,
+            entropy: vec![None; players],
+        }
+    }
+
+    /// Hash of the secret, published before the players contribute.
+    pub fn commitment(&self) -> Seed {
+        Sha256::digest(self.secret).into()
+    }
+
+    pub fn secret(&self) -> &Seed {
+        &self.secret
+    }
+
+    /// Entropy contributed by each player so far, ordered by position.
+    pub fn entropy(&self) -> &[Option<Vec<u8>>] {
+        &self.entropy
+    }
+
+    /// Returns the players who haven't contributed yet.
+    pub fn waiting(&self) -> PlayerSet {
+        (0..)
+            .zip(self.entropy.iter())
+            .filter(|(_, entropy)| entropy.is_none())
+            .map(|(player, _)| player)
+            .collect()
+    }
+
+    /// Adds the entropy of the player, returns the seed once every player has contributed.
+    pub fn contribute(
+        &mut self,
+        player: PlayerPosition,
+        entropy: Vec<u8>,
+    ) -> GameResult<Option<Seed>> {
+        let contribution = self
+            .entropy
+            .get_mut(player as usize)
+            .ok_or(GameError::PlayerNotFound)?;
+        if contribution.is_some() {
+            return Err(GameError::already_contributed(player));
+        }
+        *contribution = Some(entropy);
+        Ok(self.seed())
+    }
+
+    fn seed(&self) -> Option<Seed> {
+        let mut hasher = Sha256::new().chain_update(self.secret);
+        for entropy in self.entropy.iter() {
+            hasher.update(Sha256::digest(entropy.as_ref()?));
+        }
+        Some(hasher.finalize().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new([7; 32]);
+        let rolls = (0..100).map(|_| rng.roll(6)).collect::<Vec<_>>();
+        assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+        for side in 1..=6 {
+            assert!(rolls.contains(&side));
+        }
+
+        let mut rng = Rng::new([7; 32]);
+        assert_eq!((0..100).map(|_| rng.roll(6)).collect::<Vec<_>>(), rolls);
+        assert_ne!(Rng::new([8; 32]).next_u64(), Rng::new([7; 32]).next_u64());
+    }
+
+    #[test]
+    fn test_commitment() {
+        let mut commitment = SeedCommitment::with_secret([1; 32], 2);
+        assert_eq!(
+            commitment.commitment().to_vec(),
+            Sha256::digest([1; 32]).to_vec()
+        );
+        assert_eq!(commitment.waiting(), PlayerSet::first(2));
+
+        assert_eq!(commitment.contribute(1, b"second".to_vec()).unwrap(), None);
+        assert_eq!(
+            commitment.contribute(1, vec![]).unwrap_err(),
+            GameError::already_contributed(1)
+        );
+        assert_eq!(
+            commitment.contribute(2, vec![]).unwrap_err(),
+            GameError::PlayerNotFound
+        );
+        assert_eq!(commitment.waiting(), [0].into_iter().collect());
+
+        let seed = commitment
+            .contribute(0, b"first".to_vec())
+            .unwrap()
+            .unwrap();
+        let expected = Sha256::new()
+            .chain_update([1; 32])
+            .chain_update(Sha256::digest(b"first"))
+            .chain_update(Sha256::digest(b"second"))
+            .finalize();
+        assert_eq!(seed.to_vec(), expected.to_vec());
+        assert!(commitment.waiting().is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use generic_array::typenum::{self, Unsigned};
+
+use super::grid::{GridLayout, WithGridIndex};
+use super::player_pool::{PlayerIdQueue, PlayerQueue};
+use crate::core::{
+    game_fields, BoardCell, Game, GameError, GameResult, GameState, Grid, GridIndex, History,
+    PlayerPosition, Snapshot, TerminationReason,
+};
+
+type Size = typenum::U8;
+type Cell = BoardCell<PlayerPosition>;
+type Board = Grid<Cell, Size, Size>;
+
+/// Players place discs on an 8×8 board flipping the opponent's discs they outflank,
+/// the game ends when neither player can move and the one with more discs wins.
+#[derive(Clone, Debug)]
+pub struct Reversi {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    board: Board,
+    history: History<Snapshot<Self>>,
+}
+
+impl Default for Reversi {
+    fn default() -> Self {
+        let mut board = Board::default();
+        board[GridIndex::new(3, 3)] = 1.into();
+        board[GridIndex::new(3, 4)] = 0.into();
+        board[GridIndex::new(4, 3)] = 0.into();
+        board[GridIndex::new(4, 4)] = 1.into();
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Turn(0),
+            board,
+            history: History::default(),
+        }
+    }
+}
+
+impl Game for Reversi {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = GridIndex;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = Board;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        if self.is_finished() {
+            return Err(GameError::GameIsFinished);
+        }
+        let player = *self.get_current_player()?;
+        if id != player {
+            return Err(GameError::not_your_turn(player, id));
+        }
+        let size = Size::to_usize();
+        if data.row() >= size || data.col() >= size {
+            return Err(GameError::invalid_move(format!(
+                "{} is out of the board",
+                data
+            )));
+        }
+        if self.board[data].is_some() {
+            return Err(GameError::cell_is_occupied(data.row(), data.col()));
+        }
+        let flipped = self.flipped_by(data, id);
+        if flipped.is_empty() {
+            return Err(GameError::invalid_move(format!(
+                "disc on {} doesn't outflank any discs",
+                data
+            )));
+        }
+
+        self.history.push(Snapshot::take(self, ()));
+        for pos in flipped.into_iter().chain([data]) {
+            self.board[pos] = id.into();
+        }
+        self.switch_player()
+    }
+
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        let GameState::Turn(player) = self.state else {
+            return vec![];
+        };
+        self.board
+            .all_indexed()
+            .filter(|(pos, cell)| cell.is_none() && !self.flipped_by(*pos, player).is_empty())
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+
+    /// Passes the turn to the next player who has a legal move,
+    /// the game is finished if nobody has one.
+    fn switch_player(&mut self) -> GameResult<GameState> {
+        for _ in 0..Self::NUM_PLAYERS {
+            let next = *self.players.next().ok_or(GameError::PlayerPoolCorrupted)?;
+            self.state = GameState::Turn(next);
+            if !self.legal_actions().is_empty() {
+                return Ok(self.state);
+            }
+        }
+        Ok(self.finish_by_disc_count())
+    }
+}
+
+impl Reversi {
+    /// Returns the opponent's discs the player flips by placing a disc on `pos`,
+    /// they are the ones lying between `pos` and another disc of the player in any direction.
+    fn flipped_by(&self, pos: GridIndex, player: PlayerPosition) -> Vec<GridIndex> {
+        let board = &self.board;
+        let lines: [Box<dyn Iterator<Item = (GridIndex, &Cell)>>; 8] = [
+            Box::new(board.left_iter(pos).indexed()),
+            Box::new(board.right_iter(pos).indexed()),
+            Box::new(board.top_iter(pos).indexed()),
+            Box::new(board.bottom_iter(pos).indexed()),
+            Box::new(board.top_left_iter(pos).indexed()),
+            Box::new(board.top_right_iter(pos).indexed()),
+            Box::new(board.bottom_left_iter(pos).indexed()),
+            Box::new(board.bottom_right_iter(pos).indexed()),
+        ];
+        let mut flipped = vec![];
+        for line in lines {
+            let mut outflanked = vec![];
+            for (index, cell) in line.skip(1) {
+                match **cell {
+                    Some(owner) if owner == player => {
+                        flipped.append(&mut outflanked);
+                        break;
+                    }
+                    Some(_) => outflanked.push(index),
+                    None => break,
+                }
+            }
+        }
+        flipped
+    }
+
+    fn finish_by_disc_count(&mut self) -> GameState {
+        let count = |player: PlayerPosition| {
+            self.board
+                .iter()
+                .flatten()
+                .filter(|&&cell| cell == BoardCell(Some(player)))
+                .count()
+        };
+        match count(0).cmp(&count(1)) {
+            Ordering::Greater => self.set_winner(0, TerminationReason::DiscCount),
+            Ordering::Less => self.set_winner(1, TerminationReason::DiscCount),
+            Ordering::Equal => self.set_draw(TerminationReason::DiscCount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{FinishedState, WithCells};
+
+    #[test]
+    fn test_flips() {
+        let mut game = Reversi::new();
+        assert_eq!(
+            game.legal_actions(),
+            vec![(2, 3).into(), (3, 2).into(), (4, 5).into(), (5, 4).into()]
+        );
+
+        assert_eq!(
+            game.update(0, GridIndex::new(3, 3)).unwrap_err(),
+            GameError::cell_is_occupied(3, 3)
+        );
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 0)).unwrap_err(),
+            GameError::invalid_move("disc on C0,R0 doesn't outflank any discs".to_string())
+        );
+        assert_eq!(
+            game.update(0, GridIndex::new(2, 3)).unwrap(),
+            GameState::Turn(1)
+        );
+        assert_eq!(game.board()[GridIndex::new(3, 3)], BoardCell(Some(0)));
+        assert_eq!(game.board()[GridIndex::new(4, 4)], BoardCell(Some(1)));
+
+        game.update(1, GridIndex::new(2, 2)).unwrap();
+        game.update(0, GridIndex::new(2, 1)).unwrap();
+        game.update(1, GridIndex::new(1, 3)).unwrap();
+        // every disc of the second player in the column is flipped
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 3)).unwrap(),
+            GameState::Turn(1)
+        );
+        for row in 0..5 {
+            assert_eq!(game.board()[GridIndex::new(row, 3)], BoardCell(Some(0)));
+        }
+
+        assert_eq!(game.undo().unwrap(), GameState::Turn(0));
+        assert_eq!(game.board()[GridIndex::new(0, 3)], BoardCell(None));
+        for row in 1..4 {
+            assert_eq!(game.board()[GridIndex::new(row, 3)], BoardCell(Some(1)));
+        }
+    }
+
+    #[test]
+    fn test_pass() {
+        let mut game = Reversi::with_cells(&[((0, 0), 0), ((0, 1), 1), ((0, 3), 1)]);
+        // the second player has no moves left and passes
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 2)).unwrap(),
+            GameState::Turn(0)
+        );
+        assert_eq!(
+            game.update(0, GridIndex::new(0, 4)).unwrap(),
+            GameState::Finished(FinishedState::Win(0, TerminationReason::DiscCount))
+        );
+        assert!(game.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_disc_count() {
+        // the last move fills the board, the second player has more discs
+        let mut board = Board::default();
+        for row in 0..8 {
+            for col in 0..8 {
+                board[GridIndex::new(row, col)] = BoardCell(Some(if row < 6 { 1 } else { 0 }));
+            }
+        }
+        board[GridIndex::new(7, 6)] = BoardCell(Some(1));
+        board[GridIndex::new(7, 7)] = BoardCell(None);
+        let mut game = Reversi::new();
+        game.set_board(board);
+        assert_eq!(game.legal_actions(), vec![GridIndex::new(7, 7)]);
+        assert_eq!(
+            game.update(0, GridIndex::new(7, 7)).unwrap(),
+            GameState::Finished(FinishedState::Win(1, TerminationReason::DiscCount))
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use prost::Message;
+
+use super::player_pool::PlayerIdQueue;
+use crate::core::{
+    game_fields, BoardCell, CheckBounds, DynGrid, FromProtobuf, Game, GameError, GameResult,
+    GameState, History, PlayerPosition, PlayerSet, ProtobufError, ProtobufResult, Snapshot,
+    TerminationReason, ToProtobuf,
+};
+use crate::proto;
+
+type Cell = BoardCell<Hand>;
+
+/// Rounds a player has to win to win the game.
+pub const ROUNDS_TO_WIN: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Hand {
+    pub const ALL: [Hand; 3] = [Hand::Rock, Hand::Paper, Hand::Scissors];
+
+    pub fn beats(self, other: Hand) -> bool {
+        matches!(
+            (self, other),
+            (Hand::Rock, Hand::Scissors)
+                | (Hand::Paper, Hand::Rock)
+                | (Hand::Scissors, Hand::Paper)
+        )
+    }
+}
+
+impl FromProtobuf for Hand {
+    fn from_protobuf(buf: &[u8]) -> ProtobufResult<Self> {
+        let hand = match proto::RockPaperScissorsTurn::decode(buf)?.hand() {
+            proto::RockPaperScissorsHand::Rock => Hand::Rock,
+            proto::RockPaperScissorsHand::Paper => Hand::Paper,
+            proto::RockPaperScissorsHand::Scissors => Hand::Scissors,
+            proto::RockPaperScissorsHand::Unspecified => {
+                return Err(ProtobufError::MessageDataMissing {
+                    missing_field: "hand".to_string(),
+                })
+            }
+        };
+        Ok(hand)
+    }
+}
+
+impl ToProtobuf for Hand {
+    fn to_protobuf(self) -> ProtobufResult<Vec<u8>> {
+        let hand = match self {
+            Hand::Rock => proto::RockPaperScissorsHand::Rock,
+            Hand::Paper => proto::RockPaperScissorsHand::Paper,
+            Hand::Scissors => proto::RockPaperScissorsHand::Scissors,
+        };
+        Ok(proto::RockPaperScissorsTurn { hand: hand.into() }.encode_to_vec())
+    }
+}
+
+impl CheckBounds for Hand {
+    fn check_bounds(&self, _rows: usize, _cols: usize) -> ProtobufResult<()> {
+        Ok(())
+    }
+}
+
+/// Both players show their hands at once in every round,
+/// the first one to win [`ROUNDS_TO_WIN`] rounds wins the game.
+#[derive(Clone, Debug)]
+pub struct RockPaperScissors {
+    players: PlayerIdQueue<PlayerPosition>,
+    state: GameState,
+    /// Hands shown in the rounds that have been resolved, a row per round.
+    board: DynGrid<Cell>,
+    /// Hands shown in the current round, they are hidden until every player has shown one.
+    hands: Vec<Option<Hand>>,
+    history: History<Snapshot<Self, Vec<Option<Hand>>>>,
+}
+
+impl Default for RockPaperScissors {
+    fn default() -> Self {
+        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
+        Self {
+            players: PlayerIdQueue::new(players),
+            state: GameState::Waiting(PlayerSet::first(Self::NUM_PLAYERS)),
+            board: DynGrid::new(0, Self::NUM_PLAYERS.into()),
+            hands: vec![None; Self::NUM_PLAYERS.into()],
+            history: History::default(),
+        }
+    }
+}
+
+impl Game for RockPaperScissors {
+    const NUM_PLAYERS: u8 = 2;
+    type TurnData = Hand;
+    type Players = PlayerIdQueue<PlayerPosition>;
+    type Board = DynGrid<Cell>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
+        let mut waiting = match self.state {
+            GameState::Waiting(waiting) => waiting,
+            GameState::Turn(_) | GameState::Seeding(_) => {
+                return Err(GameError::PlayerPoolCorrupted)
+            }
+            GameState::Finished(_) => return Err(GameError::GameIsFinished),
+        };
+        if id >= Self::NUM_PLAYERS.into() {
+            return Err(GameError::PlayerNotFound);
+        }
+        if !waiting.contains(id) {
+            return Err(GameError::already_moved(id));
+        }
+
+        self.history.push(Snapshot::take(self, self.hands.clone()));
+        self.hands[id as usize] = Some(data);
+        waiting.remove(id);
+        if !waiting.is_empty() {
+            self.state = GameState::Waiting(waiting);
+            return Ok(self.state);
+        }
+        let hands = std::mem::replace(&mut self.hands, vec![None; Self::NUM_PLAYERS.into()]);
+        self.board.push_row(hands.into_iter().map(BoardCell));
+        if let Some(winner) =
+            (0..Self::NUM_PLAYERS.into()).find(|&player| self.rounds_won(player) == ROUNDS_TO_WIN)
+        {
+            return Ok(self.set_winner(winner, TerminationReason::RoundsWon));
+        }
+        self.state = GameState::Waiting(PlayerSet::first(Self::NUM_PLAYERS));
+        Ok(self.state)
+    }
+
+    /// Every hand can be shown by the players who haven't shown one in the current round.
+    fn legal_actions(&self) -> Vec<Self::TurnData> {
+        match self.state {
+            GameState::Waiting(_) => Hand::ALL.to_vec(),
+            _ => vec![],
+        }
+    }
+
+    fn undo(&mut self) -> GameResult<GameState> {
+        self.hands = self.history.pop()?.restore(self);
+        Ok(self.state)
+    }
+
+    game_fields!();
+}
+
+impl RockPaperScissors {
+    /// Returns the number of resolved rounds where the hand of the player beats the opponent's.
+    pub fn rounds_won(&self, player: PlayerPosition) -> usize {
+        let player = player as usize;
+        self.board
+            .iter_rows()
+            .filter(|round| match (round.get(player), round.get(player ^ 1)) {
+                (Some(BoardCell(Some(hand))), Some(BoardCell(Some(other)))) => hand.beats(*other),
+                _ => false,
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{FinishedState, GridLayout};
+
+    fn play(game: &mut RockPaperScissors, rounds: &[(Hand, Hand)]) -> GameState {
+        let mut state = game.state();
+        for &(first, second) in rounds {
+            game.update(0, first).unwrap();
+            state = game.update(1, second).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_round() {
+        let mut game = RockPaperScissors::new();
+        let all = PlayerSet::first(2);
+        assert_eq!(game.state(), GameState::Waiting(all));
+        assert_eq!(
+            game.update(1, Hand::Rock).unwrap(),
+            GameState::Waiting([0].into_iter().collect())
+        );
+        assert_eq!(
+            game.update(1, Hand::Paper).unwrap_err(),
+            GameError::already_moved(1)
+        );
+        assert_eq!(
+            game.update(2, Hand::Paper).unwrap_err(),
+            GameError::PlayerNotFound
+        );
+        // the round isn't on the board until both hands are shown
+        assert_eq!(game.board().rows(), 0);
+
+        assert_eq!(
+            game.update(0, Hand::Paper).unwrap(),
+            GameState::Waiting(all)
+        );
+        assert_eq!(
+            game.get_board_content(),
+            vec![vec![
+                BoardCell(Some(Hand::Paper)),
+                BoardCell(Some(Hand::Rock))
+            ]]
+        );
+        assert_eq!(game.rounds_won(0), 1);
+        assert_eq!(game.rounds_won(1), 0);
+
+        assert_eq!(
+            game.undo().unwrap(),
+            GameState::Waiting([0].into_iter().collect())
+        );
+        assert_eq!(game.board().rows(), 0);
+        assert_eq!(game.undo().unwrap(), GameState::Waiting(all));
+    }
+
+    #[test]
+    fn test_win() {
+        let mut game = RockPaperScissors::new();
+        let state = play(
+            &mut game,
+            &[
+                (Hand::Rock, Hand::Paper),
+                (Hand::Rock, Hand::Rock),
+                (Hand::Scissors, Hand::Paper),
+                (Hand::Paper, Hand::Scissors),
+                (Hand::Scissors, Hand::Rock),
+            ],
+        );
+        assert_eq!(
+            state,
+            GameState::Finished(FinishedState::Win(1, TerminationReason::RoundsWon))
+        );
+        assert_eq!(game.board().rows(), 5);
+        assert!(game.legal_actions().is_empty());
+        assert_eq!(
+            game.update(0, Hand::Rock).unwrap_err(),
+            GameError::GameIsFinished
+        );
+    }
+
+    #[test]
+    fn test_protobuf() {
+        for hand in Hand::ALL {
+            assert_eq!(
+                Hand::from_protobuf(&hand.to_protobuf().unwrap()).unwrap(),
+                hand
+            );
+        }
+    }
+}
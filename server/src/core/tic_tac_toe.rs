@@ -1,8 +1,7 @@
 use generic_array::typenum;
 
-use super::grid::{Grid, GridIndex};
-use super::player_pool::PlayerIdQueue;
-use crate::core::{BoardCell, Game, GameError, GameResult, GameState, PlayerPosition};
+use super::grid::GridIndex;
+use super::mnk::MnkGame;
 
 pub fn winning_combinations() -> [(GridIndex, GridIndex, GridIndex); 8] {
     [
@@ -17,102 +16,65 @@ pub fn winning_combinations() -> [(GridIndex, GridIndex, GridIndex); 8] {
     ]
 }
 
-type Cell = BoardCell<PlayerPosition>;
-
-#[derive(Clone, Debug)]
-pub struct TicTacToe {
-    players: PlayerIdQueue<PlayerPosition>,
-    state: GameState,
-    field: Grid<Cell, typenum::U3, typenum::U3>,
-}
-
-impl Default for TicTacToe {
-    fn default() -> Self {
-        let players = (0..Self::NUM_PLAYERS).map(|id| id.into()).collect();
-        Self {
-            players: PlayerIdQueue::new(players),
-            state: GameState::Turn(0),
-            field: Grid::default(),
+/// Tic-tac-toe is the 3,3,3-game: three marks in a row on a 3×3 board.
+pub type TicTacToe = MnkGame<typenum::U3, typenum::U3, typenum::U3>;
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::core::{Game, GameBoard, GameError, GameState};
+
+    #[test]
+    fn test_legal_actions() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.legal_actions().len(), 9);
+
+        game.update(0, (1, 1).into()).unwrap();
+        let actions = game.legal_actions();
+        assert_eq!(actions.len(), 8);
+        assert!(!actions.contains(&(1, 1).into()));
+
+        // first player completes the top row
+        for (id, pos) in [
+            (1, (1, 0)),
+            (0, (0, 0)),
+            (1, (2, 0)),
+            (0, (0, 1)),
+            (1, (2, 2)),
+        ] {
+            game.update(id, pos.into()).unwrap();
         }
+        game.update(0, (0, 2).into()).unwrap();
+        assert!(game.is_finished());
+        assert!(game.legal_actions().is_empty());
     }
-}
-
-impl Game for TicTacToe {
-    const NUM_PLAYERS: u8 = 2;
-    type TurnData = GridIndex;
-    type Players = PlayerIdQueue<PlayerPosition>;
-    type Board = Grid<Cell, typenum::U3, typenum::U3>;
 
-    fn new() -> Self {
-        Self::default()
-    }
-
-    fn update(&mut self, id: PlayerPosition, data: Self::TurnData) -> GameResult<GameState> {
-        if matches!(self.state, GameState::Finished(_)) {
-            return Err(GameError::GameIsFinished);
-        }
-        if id != *self.get_current_player()? {
-            return Err(GameError::not_your_turn(*self.get_current_player()?, id));
-        }
-
-        let player_id = *self.get_current_player()?;
-        let cell = &mut self.field[data];
-        if cell.is_some() {
-            return Err(GameError::cell_is_occupied(
-                data.row().into(),
-                data.col().into(),
-            ));
-        }
-        *cell = player_id.into();
-
-        self.update_state()
-    }
-
-    fn board(&self) -> &Self::Board {
-        &self.field
-    }
-
-    fn board_mut(&mut self) -> &mut Self::Board {
-        &mut self.field
-    }
-
-    fn players(&self) -> &Self::Players {
-        &self.players
-    }
-
-    fn players_mut(&mut self) -> &mut Self::Players {
-        &mut self.players
-    }
-
-    fn state(&self) -> GameState {
-        self.state
-    }
-
-    fn set_state(&mut self, state: GameState) {
-        self.state = state;
-    }
-
-    fn set_board(&mut self, board: Self::Board) {
-        self.field = board;
-    }
-}
-
-impl TicTacToe {
-    fn update_state(&mut self) -> GameResult<GameState> {
-        for (idx1, idx2, idx3) in winning_combinations() {
-            if let (BoardCell(Some(p1)), BoardCell(Some(p2)), BoardCell(Some(p3))) =
-                (self.field[idx1], self.field[idx2], self.field[idx3])
-            {
-                if p1 == p2 && p2 == p3 {
-                    return Ok(self.set_winner(p1));
+    proptest! {
+        /// Random game followed by undo of every turn returns to the empty board.
+        #[test]
+        fn test_undo_random_game(choices in prop::collection::vec(any::<usize>(), 0..9)) {
+            let mut game = TicTacToe::new();
+            let mut states = vec![];
+            for choice in choices {
+                let actions = game.legal_actions();
+                if actions.is_empty() {
+                    break;
                 }
+                let id = *game.get_current_player().unwrap();
+                states.push((game.board().clone(), game.state()));
+                game.update(id, actions[choice % actions.len()]).unwrap();
             }
+            while let Some((field, state)) = states.pop() {
+                prop_assert_eq!(game.undo().unwrap(), state);
+                prop_assert_eq!(game.get_board_content(), field.get_content());
+            }
+            prop_assert_eq!(game.undo(), Err(GameError::NothingToUndo));
+            prop_assert_eq!(game.state(), GameState::Turn(0));
+            prop_assert_eq!(*game.get_current_player().unwrap(), 0);
+            game.update(0, (1, 1).into()).unwrap();
+            prop_assert_eq!(*game.get_current_player().unwrap(), 1);
         }
-
-        if self.field.iter().flatten().all(|cell| cell.is_some()) {
-            return Ok(self.set_draw());
-        }
-
-        self.switch_player()
     }
 }
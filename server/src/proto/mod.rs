@@ -1,9 +1,19 @@
 tonic::include_proto!("game");
 
 use std::num::TryFromIntError;
+use std::time::Duration;
 
 use crate::core;
+use crate::core::backgammon;
+use crate::core::battleship;
+use crate::core::checkers;
 use crate::core::chess;
+use crate::core::connect_four;
+use crate::core::dots_and_boxes;
+use crate::core::go;
+use crate::core::mnk;
+use crate::core::reversi;
+use crate::core::rock_paper_scissors;
 use crate::core::tic_tac_toe;
 
 pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("game_descriptor");
@@ -18,17 +28,85 @@ impl GetGameType for chess::Chess {
     }
 }
 
+impl GetGameType for chess::Chess960 {
+    fn get_game_type() -> GameType {
+        GameType::Chess960
+    }
+}
+
 impl GetGameType for tic_tac_toe::TicTacToe {
     fn get_game_type() -> GameType {
         GameType::TicTacToe
     }
 }
 
+impl GetGameType for mnk::Gomoku {
+    fn get_game_type() -> GameType {
+        GameType::Gomoku
+    }
+}
+
+impl GetGameType for connect_four::ConnectFour {
+    fn get_game_type() -> GameType {
+        GameType::ConnectFour
+    }
+}
+
+impl GetGameType for checkers::Checkers {
+    fn get_game_type() -> GameType {
+        GameType::Checkers
+    }
+}
+
+impl GetGameType for reversi::Reversi {
+    fn get_game_type() -> GameType {
+        GameType::Reversi
+    }
+}
+
+impl GetGameType for go::Go9 {
+    fn get_game_type() -> GameType {
+        GameType::Go9x9
+    }
+}
+
+impl GetGameType for go::Go13 {
+    fn get_game_type() -> GameType {
+        GameType::Go13x13
+    }
+}
+
+impl GetGameType for battleship::Battleship {
+    fn get_game_type() -> GameType {
+        GameType::Battleship
+    }
+}
+
+impl GetGameType for rock_paper_scissors::RockPaperScissors {
+    fn get_game_type() -> GameType {
+        GameType::RockPaperScissors
+    }
+}
+
+impl GetGameType for backgammon::Backgammon {
+    fn get_game_type() -> GameType {
+        GameType::Backgammon
+    }
+}
+
+impl GetGameType for dots_and_boxes::DotsAndBoxes {
+    fn get_game_type() -> GameType {
+        GameType::DotsAndBoxes
+    }
+}
+
 impl game_session_request::Request {
     pub fn name(&self) -> String {
         match self {
             Self::Init(_) => "Init".into(),
             Self::TurnData(_) => "TurnData".into(),
+            Self::Action(_) => "Action".into(),
+            Self::Entropy(_) => "Entropy".into(),
         }
     }
 }
@@ -40,15 +118,94 @@ impl From<core::GameState> for GameState {
                 next_player_id: Some(id),
                 ..Default::default()
             },
-            core::GameState::Finished(core::FinishedState::Win(id)) => Self {
+            core::GameState::Waiting(players) => Self {
+                waiting_player_ids: players.iter().collect(),
+                ..Default::default()
+            },
+            core::GameState::Seeding(players) => Self {
+                waiting_player_ids: players.iter().collect(),
+                seeding: true,
+                ..Default::default()
+            },
+            core::GameState::Finished(core::FinishedState::Win(id, reason)) => Self {
                 winner: Some(id),
+                termination: TerminationReason::from(reason).into(),
                 ..Default::default()
             },
-            core::GameState::Finished(core::FinishedState::Draw) => Self::default(),
+            core::GameState::Finished(core::FinishedState::Draw(reason)) => Self {
+                termination: TerminationReason::from(reason).into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl GameState {
+    pub fn with_scores(mut self, scores: Vec<f32>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    pub fn with_dice(mut self, dice: Vec<u32>) -> Self {
+        self.dice = dice;
+        self
+    }
+}
+
+impl From<core::TerminationReason> for TerminationReason {
+    fn from(value: core::TerminationReason) -> Self {
+        match value {
+            core::TerminationReason::Checkmate => TerminationReason::Checkmate,
+            core::TerminationReason::LineCompleted => TerminationReason::LineCompleted,
+            core::TerminationReason::Stalemate => TerminationReason::Stalemate,
+            core::TerminationReason::ThreefoldRepetition => TerminationReason::ThreefoldRepetition,
+            core::TerminationReason::FiftyMoveRule => TerminationReason::FiftyMoveRule,
+            core::TerminationReason::InsufficientMaterial => {
+                TerminationReason::InsufficientMaterial
+            }
+            core::TerminationReason::Resignation => TerminationReason::Resignation,
+            core::TerminationReason::Timeout => TerminationReason::Timeout,
+            core::TerminationReason::Agreement => TerminationReason::Agreement,
+            core::TerminationReason::NoLegalMoves => TerminationReason::NoLegalMoves,
+            core::TerminationReason::DiscCount => TerminationReason::DiscCount,
+            core::TerminationReason::Score => TerminationReason::Score,
+            core::TerminationReason::FleetSunk => TerminationReason::FleetSunk,
+            core::TerminationReason::RoundsWon => TerminationReason::RoundsWon,
+            core::TerminationReason::BorneOff => TerminationReason::BorneOff,
+            core::TerminationReason::BoxCount => TerminationReason::BoxCount,
         }
     }
 }
 
+impl TryFrom<TerminationReason> for core::TerminationReason {
+    type Error = core::ProtobufError;
+
+    fn try_from(value: TerminationReason) -> Result<Self, Self::Error> {
+        let reason = match value {
+            TerminationReason::Checkmate => core::TerminationReason::Checkmate,
+            TerminationReason::LineCompleted => core::TerminationReason::LineCompleted,
+            TerminationReason::Stalemate => core::TerminationReason::Stalemate,
+            TerminationReason::ThreefoldRepetition => core::TerminationReason::ThreefoldRepetition,
+            TerminationReason::FiftyMoveRule => core::TerminationReason::FiftyMoveRule,
+            TerminationReason::InsufficientMaterial => {
+                core::TerminationReason::InsufficientMaterial
+            }
+            TerminationReason::Resignation => core::TerminationReason::Resignation,
+            TerminationReason::Timeout => core::TerminationReason::Timeout,
+            TerminationReason::Agreement => core::TerminationReason::Agreement,
+            TerminationReason::NoLegalMoves => core::TerminationReason::NoLegalMoves,
+            TerminationReason::DiscCount => core::TerminationReason::DiscCount,
+            TerminationReason::Score => core::TerminationReason::Score,
+            TerminationReason::FleetSunk => core::TerminationReason::FleetSunk,
+            TerminationReason::RoundsWon => core::TerminationReason::RoundsWon,
+            TerminationReason::BorneOff => core::TerminationReason::BorneOff,
+            TerminationReason::BoxCount => core::TerminationReason::BoxCount,
+            TerminationReason::Unspecified => return Err(core::ProtobufError::InvalidGameState),
+        };
+        Ok(reason)
+    }
+}
+
 impl From<chess::types::PieceKind> for ChessPieceKind {
     fn from(value: chess::types::PieceKind) -> Self {
         match value {
@@ -62,6 +219,25 @@ impl From<chess::types::PieceKind> for ChessPieceKind {
     }
 }
 
+impl TryFrom<ChessPieceKind> for chess::types::PieceKind {
+    type Error = core::ProtobufError;
+
+    fn try_from(value: ChessPieceKind) -> Result<Self, Self::Error> {
+        let kind = match value {
+            ChessPieceKind::PieceKindPawn => chess::types::PieceKind::Pawn,
+            ChessPieceKind::PieceKindBishop => chess::types::PieceKind::Bishop,
+            ChessPieceKind::PieceKindKnight => chess::types::PieceKind::Knight,
+            ChessPieceKind::PieceKindRook => chess::types::PieceKind::Rook,
+            ChessPieceKind::PieceKindQueen => chess::types::PieceKind::Queen,
+            ChessPieceKind::PieceKindKing => chess::types::PieceKind::King,
+            ChessPieceKind::PieceKindUnspecified => {
+                return Err(core::ProtobufError::InvalidPieceKind(value.into()))
+            }
+        };
+        Ok(kind)
+    }
+}
+
 impl From<chess::types::Piece> for ChessPiece {
     fn from(value: chess::types::Piece) -> Self {
         let kind: ChessPieceKind = value.kind.into();
@@ -90,17 +266,76 @@ impl TryFrom<chess::TurnData> for PositionPair {
         Ok(Self {
             first: Some(value.from.try_into()?),
             second: Some(value.to.try_into()?),
+            promotion: value
+                .promotion
+                .map(|kind| ChessPieceKind::from(kind).into()),
         })
     }
 }
 
+impl TryFrom<TimeControl> for core::TimeControl {
+    type Error = core::ProtobufError;
+
+    fn try_from(value: TimeControl) -> Result<Self, Self::Error> {
+        let control = match value.control {
+            Some(time_control::Control::Clock(clock)) => core::TimeControl::Clock {
+                base: Duration::from_millis(clock.base_ms),
+                increment: Duration::from_millis(clock.increment_ms),
+                delay: Duration::from_millis(clock.delay_ms),
+            },
+            Some(time_control::Control::Correspondence(correspondence)) => {
+                core::TimeControl::Correspondence {
+                    days_per_move: correspondence.days_per_move,
+                }
+            }
+            None => return Err(core::ProtobufError::InvalidTimeControl),
+        };
+        if !control.is_valid() {
+            return Err(core::ProtobufError::InvalidTimeControl);
+        }
+        Ok(control)
+    }
+}
+
+impl TimeControl {
+    pub fn clock(base_ms: u64, increment_ms: u64, delay_ms: u64) -> Self {
+        Self {
+            control: Some(time_control::Control::Clock(ClockTimeControl {
+                base_ms,
+                increment_ms,
+                delay_ms,
+            })),
+        }
+    }
+
+    pub fn correspondence(days_per_move: u32) -> Self {
+        Self {
+            control: Some(time_control::Control::Correspondence(
+                CorrespondenceTimeControl { days_per_move },
+            )),
+        }
+    }
+}
+
 impl CreateGameRequest {
     pub fn new(game_type: i32, player_ids: Vec<u64>) -> Self {
         Self {
             game_type,
             player_ids,
+            time_control: None,
+            start_position: None,
         }
     }
+
+    pub fn with_time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self
+    }
+
+    pub fn with_start_position(mut self, start_position: u32) -> Self {
+        self.start_position = Some(start_position);
+        self
+    }
 }
 
 impl MakeTurnRequest {
@@ -130,11 +365,32 @@ impl GameSessionRequest {
             request: Some(game_session_request::Request::TurnData(data)),
         }
     }
+
+    pub fn action(action: GameAction) -> Self {
+        Self {
+            request: Some(game_session_request::Request::Action(action.into())),
+        }
+    }
+
+    pub fn entropy(entropy: Vec<u8>) -> Self {
+        Self {
+            request: Some(game_session_request::Request::Entropy(entropy)),
+        }
+    }
 }
 
 impl GetGameRequest {
     pub fn new(game_type: i32, game_id: u64) -> Self {
-        Self { game_type, game_id }
+        Self {
+            game_type,
+            game_id,
+            player_id: None,
+        }
+    }
+
+    pub fn with_player(mut self, player_id: u64) -> Self {
+        self.player_id = Some(player_id);
+        self
     }
 }
 
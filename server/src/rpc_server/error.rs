@@ -15,6 +15,10 @@ pub enum RpcError {
     DuplicateGame,
     #[error("unrecognized game type")]
     InvalidGameType,
+    #[error("time control must have either positive base time or positive days per move")]
+    InvalidTimeControl,
+    #[error("game doesn't have starting position {position}")]
+    InvalidStartPosition { position: u32 },
     #[error("game with this id doesn't exist: {id}")]
     NoSuchGame { id: GameId },
     #[error("player trying to access game they doesn't belong to")]
@@ -34,6 +38,12 @@ pub enum RpcError {
     StreamingRequestReadFailed(#[from] Status),
     #[error("received an empty request")]
     EmptyRequest,
+    #[error("unrecognized game action")]
+    InvalidGameAction,
+    #[error("there is no draw offer from the opponent")]
+    NoDrawOffer,
+    #[error("game has no chance events to contribute entropy to")]
+    NoChanceEvents,
     #[error("unexpected request: expected {expected}, found: {found}")]
     UnexpectedRequest { expected: String, found: String },
     #[error("authentication failed: {0}")]
@@ -70,10 +80,15 @@ impl From<RpcError> for Status {
             RpcError::DeleteActiveGameFailed => Status::failed_precondition(value.to_string()),
             RpcError::DuplicateGame => Status::already_exists(value.to_string()),
             RpcError::InvalidGameType => Status::invalid_argument(value.to_string()),
+            RpcError::InvalidTimeControl => Status::invalid_argument(value.to_string()),
+            RpcError::InvalidStartPosition { .. } => Status::invalid_argument(value.to_string()),
             RpcError::NoSuchGame { .. } => Status::not_found(value.to_string()),
             RpcError::ForeignGame => Status::permission_denied(value.to_string()),
             RpcError::StreamingRequestReadFailed(status) => status,
             RpcError::EmptyRequest => Status::invalid_argument(value.to_string()),
+            RpcError::InvalidGameAction => Status::invalid_argument(value.to_string()),
+            RpcError::NoDrawOffer => Status::failed_precondition(value.to_string()),
+            RpcError::NoChanceEvents => Status::failed_precondition(value.to_string()),
             RpcError::RequestDataMissing(_) => Status::invalid_argument(value.to_string()),
             RpcError::UnexpectedRequest { .. } => Status::failed_precondition(value.to_string()),
             RpcError::Authentication { .. } => Status::unauthenticated(value.to_string()),
@@ -2,11 +2,12 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use super::error::RpcError;
 use super::lobby::{Connection, Lobby};
 use super::rpc::{GameId, RpcInnerResult};
-use crate::core::{Game, GameBoard, GameState, ProtobufResult, ToProtobuf};
+use crate::core::{Game, GameState, TimeControl};
 use crate::rpc_server::UserId;
 use crate::proto;
 
@@ -66,16 +67,29 @@ impl<T> GameStorage<T> {
 
 impl<T: Game> GameStorage<T> {
     // TODO: replace proto::GameInfo with own GameInfo type (move it from client)
-    pub fn create(&self, id: GameId, players: &[UserId]) -> RpcInnerResult<proto::GameInfo> {
+    pub fn create(
+        &self,
+        id: GameId,
+        players: &[UserId],
+        time_control: Option<TimeControl>,
+        start_position: Option<u32>,
+    ) -> RpcInnerResult<proto::GameInfo> {
         let mut guard = self.lock()?;
         return match guard.entry(id) {
             Entry::Vacant(e) => {
-                let lobby = Lobby::<T>::new(players);
+                let game = T::from_start_position(start_position).ok_or(
+                    RpcError::InvalidStartPosition {
+                        position: start_position.unwrap_or_default(),
+                    },
+                )?;
+                let lobby = Lobby::new(players, time_control, game);
                 let info = proto::GameInfo {
                     game_id: id,
                     players: players.to_vec(),
-                    game_state: Some(lobby.game().state().into()),
+                    game_state: Some(lobby.game_state()),
                     board: vec![],
+                    seed: lobby.seed_commitment(),
+                    start_position: lobby.game().start_position(),
                 };
                 e.insert(lobby);
                 Ok(info)
@@ -84,10 +98,55 @@ impl<T: Game> GameStorage<T> {
         };
     }
 
-    pub fn update(&self, id: GameId, player: UserId, data: &[u8]) -> RpcInnerResult<GameState> {
+    pub fn update(
+        &self,
+        id: GameId,
+        player: UserId,
+        data: &[u8],
+    ) -> RpcInnerResult<proto::GameState> {
         let mut guard = self.lock()?;
         let lobby = guard.get_mut(&id).ok_or(RpcError::NoSuchGame { id })?;
-        lobby.update(player, data)
+        lobby.update(player, data)?;
+        Ok(lobby.game_state())
+    }
+
+    pub fn make_action(
+        &self,
+        id: GameId,
+        player: UserId,
+        action: proto::GameAction,
+    ) -> RpcInnerResult<GameState> {
+        let mut guard = self.lock()?;
+        let lobby = guard.get_mut(&id).ok_or(RpcError::NoSuchGame { id })?;
+        lobby.make_action(player, action)
+    }
+
+    pub fn contribute(
+        &self,
+        id: GameId,
+        player: UserId,
+        entropy: Vec<u8>,
+    ) -> RpcInnerResult<GameState> {
+        let mut guard = self.lock()?;
+        let lobby = guard.get_mut(&id).ok_or(RpcError::NoSuchGame { id })?;
+        lobby.contribute(player, entropy)
+    }
+
+    /// Returns the earliest moment a clock of an active game runs out.
+    pub fn next_deadline(&self) -> RpcInnerResult<Option<Instant>> {
+        let guard = self.lock()?;
+        Ok(guard.values().filter_map(Lobby::deadline).min())
+    }
+
+    /// Finishes games of players whose clocks have run out by `now`.
+    pub fn flag_timeouts(&self, now: Instant) -> RpcInnerResult<()> {
+        let mut guard = self.lock()?;
+        for (id, lobby) in guard.iter_mut() {
+            if let Some(state) = lobby.flag_timeout(now) {
+                println!("game {} is finished by timeout: {:?}", id, state);
+            }
+        }
+        Ok(())
     }
 
     pub fn delete(&self, id: GameId) -> RpcInnerResult<()> {
@@ -101,22 +160,17 @@ impl<T: Game> GameStorage<T> {
         Ok(())
     }
 
-    pub fn get(&self, id: GameId) -> RpcInnerResult<proto::GameInfo> {
+    /// Returns the game with the board as `user` sees it.
+    pub fn get(&self, id: GameId, user: Option<UserId>) -> RpcInnerResult<proto::GameInfo> {
         let guard = self.lock()?;
         let lobby = guard.get(&id).ok_or(RpcError::NoSuchGame { id })?;
-        let board = lobby
-            .game()
-            .board()
-            .get_content()
-            .into_iter()
-            .flatten()
-            .map(|item| item.to_protobuf())
-            .collect::<ProtobufResult<Vec<_>>>()?;
         Ok(proto::GameInfo {
             game_id: id,
             players: lobby.players().to_vec(),
-            game_state: Some(lobby.game().state().into()),
-            board,
+            game_state: Some(lobby.game_state()),
+            board: lobby.board_view(user)?,
+            seed: lobby.seed_commitment(),
+            start_position: lobby.game().start_position(),
         })
     }
 
@@ -129,8 +183,10 @@ impl<T: Game> GameStorage<T> {
                     return Some(proto::GameInfo {
                         game_id: *id,
                         players: lobby.players().to_vec(),
-                        game_state: Some(lobby.game().state().into()),
+                        game_state: Some(lobby.game_state()),
                         board: vec![],
+                        seed: lobby.seed_commitment(),
+                        start_position: lobby.game().start_position(),
                     });
                 }
                 None
@@ -1,4 +1,5 @@
 use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
 
 use smallvec::SmallVec;
 use tokio::select;
@@ -12,21 +13,65 @@ use super::error::RpcError;
 use super::lobby_manager::WorkerCommand;
 use super::rpc::RpcInnerResult;
 use super::GameId;
-use crate::core::{FromProtobuf, Game, GameState, PlayerPosition};
-use crate::proto::{game_session_request, GameSessionRequest};
+use crate::core::{
+    Clocks, Game, GameError, GameState, PlayerPosition, ProtobufResult, SeedCommitment,
+    TerminationReason, TimeControl, ToProtobuf,
+};
+use crate::proto::{self, game_session_request, GameAction, GameSessionRequest};
 use crate::rpc_server::UserId;
 
 type ChannelSendResult<T> = Result<(), SendError<T>>;
 
-#[derive(Debug)]
+/// Turn or action made by the player, `data` is empty if it's not a turn.
+#[derive(Clone, Debug)]
 pub struct MoveEvent {
     pub player: PlayerPosition,
     pub data: Vec<u8>,
+    /// Remaining time of each player, empty if the game has no time control.
+    pub clocks: Vec<Duration>,
+    pub state: GameState,
+    /// Points of each player, empty unless the game has been finished by counting them.
+    pub scores: Vec<f32>,
+    /// Dice rolled for the player to move, empty in games without chance events.
+    pub dice: Vec<u32>,
+    /// Encoded board as the recipient sees it, empty unless the game has hidden information.
+    pub board: Vec<Vec<u8>>,
+    /// Action made instead of a turn, [`GameAction::Unspecified`] for turns and timeouts.
+    pub action: GameAction,
 }
 
 impl MoveEvent {
-    pub fn new(player: PlayerPosition, data: Vec<u8>) -> Self {
-        Self { player, data }
+    pub fn new(
+        player: PlayerPosition,
+        data: Vec<u8>,
+        clocks: Vec<Duration>,
+        state: GameState,
+    ) -> Self {
+        Self {
+            player,
+            data,
+            clocks,
+            state,
+            scores: vec![],
+            dice: vec![],
+            board: vec![],
+            action: GameAction::Unspecified,
+        }
+    }
+
+    pub fn with_action(mut self, action: GameAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn with_scores(mut self, scores: Vec<f32>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    pub fn with_dice(mut self, dice: Vec<u32>) -> Self {
+        self.dice = dice;
+        self
     }
 }
 
@@ -84,6 +129,22 @@ impl UpdateRequestReader {
                                             }
                                         }
                                     }
+                                    game_session_request::Request::Action(action) => {
+                                        let action = WorkerCommand::MakeAction { game, user, action };
+                                        if let Err(err) = command_sender.send(action) {
+                                            if let Err(err) = reply_sender.send(Err(err.into())) {
+                                                println!("failed to send error to client: {}", err);
+                                            }
+                                        }
+                                    }
+                                    game_session_request::Request::Entropy(entropy) => {
+                                        let contribution = WorkerCommand::Contribute { game, user, entropy };
+                                        if let Err(err) = command_sender.send(contribution) {
+                                            if let Err(err) = reply_sender.send(Err(err.into())) {
+                                                println!("failed to send error to client: {}", err);
+                                            }
+                                        }
+                                    }
                                     _ => {
                                         if let Err(err) = reply_sender.send(Err(
                                             RpcError::unexpected_request("TurnData", request.name()),
@@ -133,12 +194,8 @@ impl Connection {
         }
     }
 
-    pub fn notify(
-        &self,
-        player: PlayerPosition,
-        data: Vec<u8>,
-    ) -> ChannelSendResult<RpcInnerResult<MoveEvent>> {
-        self.reply_sender.send(Ok(MoveEvent::new(player, data)))
+    pub fn notify(&self, event: MoveEvent) -> ChannelSendResult<RpcInnerResult<MoveEvent>> {
+        self.reply_sender.send(Ok(event))
     }
 
     pub fn notify_err(&self, err: RpcError) -> ChannelSendResult<RpcInnerResult<MoveEvent>> {
@@ -154,6 +211,14 @@ impl Connection {
 pub struct Lobby<T> {
     players: SmallVec<[UserId; 8]>,
     game: T,
+    /// Clocks of the players, [`None`] if the game has no time control.
+    clocks: Option<Clocks>,
+    /// Player whose draw offer is waiting for the opponent's answer.
+    draw_offer: Option<PlayerPosition>,
+    /// Moves of the round where players move at once, held until every player has moved.
+    pending_moves: Vec<(PlayerPosition, Vec<u8>)>,
+    /// Agreement on the seed of the dice, [`None`] for games without chance events.
+    seed: Option<SeedCommitment>,
     connections: Vec<Connection>,
     reader_cancellation_token: CancellationToken,
 }
@@ -197,22 +262,222 @@ impl<T> Lobby<T> {
         }
         Ok(())
     }
+
+    fn remaining_time(&self, now: Instant) -> Vec<Duration> {
+        self.clocks
+            .as_ref()
+            .map(|clocks| clocks.remaining_all(now))
+            .unwrap_or_default()
+    }
 }
 
 impl<T: Game> Lobby<T> {
-    pub fn new(players: &[UserId]) -> Self {
+    /// Creates a lobby for a new game, the clocks don't run until the first turn is made,
+    /// so nobody loses on time before the opponents have joined.
+    pub fn new(players: &[UserId], time_control: Option<TimeControl>, game: T) -> Self {
         Self {
             players: SmallVec::from_slice(players),
-            game: T::new(),
+            game,
+            clocks: time_control.map(|control| Clocks::new(control, players.len())),
+            draw_offer: None,
+            pending_moves: vec![],
+            seed: T::CHANCE.then(|| SeedCommitment::new(players.len())),
             connections: Default::default(),
             reader_cancellation_token: Default::default(),
         }
     }
 
+    /// Returns the encoded board as `user` sees it, anyone but the players is a spectator.
+    pub fn board_view(&self, user: Option<UserId>) -> ProtobufResult<Vec<Vec<u8>>> {
+        let viewer = user.and_then(|user| self.player_position(user).ok());
+        self.game
+            .board_view(viewer)
+            .into_iter()
+            .flatten()
+            .map(ToProtobuf::to_protobuf)
+            .collect()
+    }
+
+    /// Sends the event to every connection, in games with hidden information
+    /// each recipient gets the board as it sees it along with the event.
+    fn notify_all(&self, event: MoveEvent) {
+        for conn in self.connections.iter() {
+            let mut event = event.clone();
+            if T::HIDDEN_INFORMATION {
+                match self.board_view(Some(conn.id)) {
+                    Ok(board) => event.board = board,
+                    Err(err) => {
+                        println!("failed to encode board view: {}", err);
+                        continue;
+                    }
+                }
+            }
+            if let Err(err) = conn.notify(event) {
+                println!("failed to notify subscriber: {}", err);
+            }
+        }
+    }
+
+    /// Returns the game state along with the scores once they are counted.
+    pub fn game_state(&self) -> proto::GameState {
+        proto::GameState::from(self.game.state())
+            .with_scores(self.game.scores())
+            .with_dice(self.game.dice())
+    }
+
+    /// Returns the agreement on the seed of the dice, the secret is revealed
+    /// once the game is finished.
+    pub fn seed_commitment(&self) -> Option<proto::SeedCommitment> {
+        let seed = self.seed.as_ref()?;
+        Some(proto::SeedCommitment {
+            commitment: seed.commitment().to_vec(),
+            entropy: seed
+                .entropy()
+                .iter()
+                .map(|entropy| entropy.clone().unwrap_or_default())
+                .collect(),
+            secret: if self.game.is_finished() {
+                seed.secret().to_vec()
+            } else {
+                vec![]
+            },
+        })
+    }
+
     pub fn update(&mut self, player: UserId, data: &[u8]) -> RpcInnerResult<GameState> {
-        let decoded_data = T::TurnData::from_protobuf(data)?;
-        let player_position = self
-            .get_player_position(player)
+        let now = Instant::now();
+        if self.flag_timeout(now).is_some() {
+            return Err(GameError::GameIsFinished.into());
+        }
+        let decoded_data = self.game.decode_turn_data(data)?;
+        let player_position = self.player_position(player)?;
+        let previous_state = self.game.state();
+        let state = self.game.update(player_position, decoded_data)?;
+        // making a turn declines the opponent's draw offer
+        if self
+            .draw_offer
+            .is_some_and(|offer| offer != player_position)
+        {
+            self.draw_offer = None;
+        }
+        // the player who resolves a round of simultaneous moves gets the increment
+        // and a fresh turn in the next round like everybody else
+        if let Some(clocks) = self.clocks.as_mut() {
+            clocks.stop_player(player_position, now);
+        }
+        self.switch_clocks(state, now);
+        let clocks = self.remaining_time(now);
+        let mut moves = vec![(player_position, data.to_vec())];
+        if let GameState::Waiting(mut waiting) = previous_state {
+            self.pending_moves.append(&mut moves);
+            waiting.remove(player_position);
+            if state == GameState::Waiting(waiting) {
+                // the others learn only that the player has moved until the round is resolved
+                moves.push((player_position, vec![]));
+            } else {
+                moves = std::mem::take(&mut self.pending_moves);
+            }
+        }
+        for (player, data) in moves {
+            self.notify_all(
+                MoveEvent::new(player, data, clocks.clone(), state)
+                    .with_scores(self.game.scores())
+                    .with_dice(self.game.dice()),
+            );
+        }
+        if matches!(state, GameState::Finished(_)) {
+            self.reader_cancellation_token.cancel();
+        }
+        Ok(state)
+    }
+
+    /// Adds the entropy of the player to the seed of the dice,
+    /// the game starts once every player has contributed.
+    pub fn contribute(&mut self, player: UserId, entropy: Vec<u8>) -> RpcInnerResult<GameState> {
+        let now = Instant::now();
+        if self.flag_timeout(now).is_some() || self.game.is_finished() {
+            return Err(GameError::GameIsFinished.into());
+        }
+        let player_position = self.player_position(player)?;
+        let seed = self.seed.as_mut().ok_or(RpcError::NoChanceEvents)?;
+        let state = match seed.contribute(player_position, entropy)? {
+            Some(seed) => self.game.seed(seed)?,
+            None => {
+                let state = GameState::Seeding(seed.waiting());
+                self.game.set_state(state);
+                state
+            }
+        };
+        self.switch_clocks(state, now);
+        let clocks = self.remaining_time(now);
+        self.notify_all(
+            MoveEvent::new(player_position, vec![], clocks, state).with_dice(self.game.dice()),
+        );
+        Ok(state)
+    }
+
+    /// Runs the clocks of the players to move in the new state, stops them all once it's finished.
+    /// The clocks don't run while the game is being seeded.
+    fn switch_clocks(&mut self, state: GameState, now: Instant) {
+        if let Some(clocks) = self.clocks.as_mut() {
+            match state {
+                GameState::Turn(next) => clocks.start(next, now),
+                GameState::Waiting(players) => clocks.run(players, now),
+                GameState::Seeding(_) => {}
+                GameState::Finished(_) => clocks.stop(now),
+            }
+        }
+    }
+
+    /// Applies the action of the player, resignation and an accepted draw finish the game.
+    pub fn make_action(&mut self, player: UserId, action: GameAction) -> RpcInnerResult<GameState> {
+        let now = Instant::now();
+        if self.flag_timeout(now).is_some() || self.game.is_finished() {
+            return Err(GameError::GameIsFinished.into());
+        }
+        let player_position = self.player_position(player)?;
+        let opponent_offer = self
+            .draw_offer
+            .is_some_and(|offer| offer != player_position);
+        let state = match action {
+            GameAction::Resign => {
+                let winner = self
+                    .opponent(player_position)
+                    .ok_or(GameError::PlayerNotFound)?;
+                self.game.set_winner(winner, TerminationReason::Resignation)
+            }
+            GameAction::OfferDraw | GameAction::AcceptDraw if opponent_offer => {
+                self.game.set_draw(TerminationReason::Agreement)
+            }
+            GameAction::OfferDraw => {
+                self.draw_offer = Some(player_position);
+                self.game.state()
+            }
+            GameAction::DeclineDraw if opponent_offer => {
+                self.draw_offer = None;
+                self.game.state()
+            }
+            GameAction::AcceptDraw | GameAction::DeclineDraw => {
+                return Err(RpcError::NoDrawOffer);
+            }
+            GameAction::Unspecified => return Err(RpcError::InvalidGameAction),
+        };
+        if matches!(state, GameState::Finished(_)) {
+            self.draw_offer = None;
+            if let Some(clocks) = self.clocks.as_mut() {
+                clocks.stop(now);
+            }
+        }
+        let clocks = self.remaining_time(now);
+        self.notify_all(MoveEvent::new(player_position, vec![], clocks, state).with_action(action));
+        if matches!(state, GameState::Finished(_)) {
+            self.reader_cancellation_token.cancel();
+        }
+        Ok(state)
+    }
+
+    fn player_position(&self, user: UserId) -> RpcInnerResult<PlayerPosition> {
+        self.get_player_position(user)
             .ok_or(RpcError::ForeignGame)?
             .try_into()
             .map_err(|err| {
@@ -220,16 +485,361 @@ impl<T: Game> Lobby<T> {
                     "failed to convert usize to player position: {}",
                     err
                 ))
-            })?;
-        let state = self.game.update(player_position, decoded_data)?;
-        for conn in self.connections.iter() {
-            if let Err(err) = conn.notify(player_position, data.to_vec()) {
-                println!("failed to notify subscriber: {}", err);
+            })
+    }
+
+    fn opponent(&self, player: PlayerPosition) -> Option<PlayerPosition> {
+        self.game
+            .get_player_ids()
+            .into_iter()
+            .find(|&id| id != player)
+    }
+
+    /// Returns the moment the clock of the player to move runs out.
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.game.is_finished() {
+            return None;
+        }
+        self.clocks.as_ref()?.deadline()
+    }
+
+    /// Finishes the game if the clock of the player to move has run out by `now`,
+    /// the other player wins on time.
+    pub fn flag_timeout(&mut self, now: Instant) -> Option<GameState> {
+        if self.game.is_finished() {
+            return None;
+        }
+        let clocks = self.clocks.as_mut()?;
+        let player = clocks.flagged(now)?;
+        clocks.stop(now);
+        let winner = self.opponent(player)?;
+        let state = self.game.set_winner(winner, TerminationReason::Timeout);
+        let clocks = self.remaining_time(now);
+        self.notify_all(MoveEvent::new(player, vec![], clocks, state));
+        self.reader_cancellation_token.cancel();
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use prost::Message;
+
+    use super::*;
+    use crate::core::{
+        backgammon, battleship, checkers, chess, connect_four, dots_and_boxes, go, mnk, reversi,
+        rock_paper_scissors, tic_tac_toe, PlayerSet, ProtobufError,
+    };
+    use crate::proto::{
+        BackgammonMove, BackgammonTurn, CheckersTurn, ConnectFourTurn, DotsAndBoxesTurn, GoTurn,
+        Position, PositionPair, RockPaperScissorsTurn,
+    };
+
+    #[derive(Clone, Debug)]
+    enum Input {
+        /// One of the legal turns of the player to move, picked by index.
+        Legal(usize),
+        /// Encoded turn that may point anywhere or not decode at all.
+        Data(Vec<u8>),
+    }
+
+    fn coordinate() -> impl Strategy<Value = u32> {
+        prop_oneof![0u32..20, any::<u32>()]
+    }
+
+    fn position() -> impl Strategy<Value = Position> {
+        (coordinate(), coordinate()).prop_map(|(row, col)| Position { row, col })
+    }
+
+    /// Turns sent by both players and an outsider in random order.
+    fn inputs(data: impl Strategy<Value = Vec<u8>>) -> impl Strategy<Value = Vec<(UserId, Input)>> {
+        let input = prop_oneof![
+            any::<usize>().prop_map(Input::Legal),
+            data.prop_map(Input::Data),
+            prop::collection::vec(any::<u8>(), 0..16).prop_map(Input::Data),
+        ];
+        prop::collection::vec((0u64..3, input), 0..40)
+    }
+
+    fn grid_index() -> impl Strategy<Value = Vec<u8>> {
+        position().prop_map(|pos| pos.encode_to_vec())
+    }
+
+    fn chess_turn() -> impl Strategy<Value = Vec<u8>> {
+        (
+            prop::option::of(position()),
+            prop::option::of(position()),
+            prop::option::of(any::<i32>()),
+        )
+            .prop_map(|(first, second, promotion)| {
+                PositionPair {
+                    first,
+                    second,
+                    promotion,
+                }
+                .encode_to_vec()
+            })
+    }
+
+    fn checkers_turn() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(position(), 0..4)
+            .prop_map(|path| CheckersTurn { path }.encode_to_vec())
+    }
+
+    fn go_turn() -> impl Strategy<Value = Vec<u8>> {
+        prop::option::of(position()).prop_map(|place| GoTurn { place }.encode_to_vec())
+    }
+
+    fn connect_four_turn() -> impl Strategy<Value = Vec<u8>> {
+        coordinate().prop_map(|column| ConnectFourTurn { column }.encode_to_vec())
+    }
+
+    fn backgammon_turn() -> impl Strategy<Value = Vec<u8>> {
+        let column = prop_oneof![0u32..30, any::<u32>()];
+        prop::collection::vec((column.clone(), column), 0..5).prop_map(|moves| {
+            let moves = moves
+                .into_iter()
+                .map(|(from, to)| BackgammonMove { from, to })
+                .collect();
+            BackgammonTurn { moves }.encode_to_vec()
+        })
+    }
+
+    fn dots_and_boxes_turn() -> impl Strategy<Value = Vec<u8>> {
+        (prop::option::of(position()), prop::option::of(position()))
+            .prop_map(|(first, second)| DotsAndBoxesTurn { first, second }.encode_to_vec())
+    }
+
+    /// Feeds the inputs to a new lobby, errors are expected and ignored, panics fail the test.
+    fn play<T: Game>(inputs: Vec<(UserId, Input)>) {
+        let mut lobby = Lobby::new(&[1, 2], None, T::new());
+        if T::CHANCE {
+            lobby.seed = Some(SeedCommitment::with_secret([0; 32], 2));
+            lobby.contribute(1, vec![1]).unwrap();
+            lobby.contribute(2, vec![2]).unwrap();
+        }
+        for (user, input) in inputs {
+            let data = match input {
+                Input::Legal(choice) => {
+                    let mut actions = lobby.game().legal_actions();
+                    if actions.is_empty() {
+                        continue;
+                    }
+                    let turn = actions.swap_remove(choice % actions.len());
+                    turn.to_protobuf().unwrap()
+                }
+                Input::Data(data) => data,
+            };
+            let _ = lobby.update(user, &data);
+        }
+    }
+
+    #[test]
+    fn test_turn_out_of_board() {
+        let mut lobby = Lobby::new(&[1, 2], None, tic_tac_toe::TicTacToe::new());
+        let data = Position { row: 3, col: 0 }.encode_to_vec();
+        assert!(matches!(
+            lobby.update(1, &data).unwrap_err(),
+            RpcError::TurnDataConversion {
+                source: ProtobufError::InvalidGridRow {
+                    max_expected: 3,
+                    found: 4
+                }
             }
+        ));
+        let data = Position {
+            row: 0,
+            col: u32::MAX,
         }
-        if matches!(state, GameState::Finished(_)) {
-            self.reader_cancellation_token.cancel();
+        .encode_to_vec();
+        assert!(matches!(
+            lobby.update(1, &data).unwrap_err(),
+            RpcError::TurnDataConversion {
+                source: ProtobufError::InvalidGridCol {
+                    max_expected: 3,
+                    ..
+                }
+            }
+        ));
+        assert_eq!(lobby.game().state(), GameState::Turn(0));
+
+        let mut lobby = Lobby::new(&[1, 2], None, connect_four::ConnectFour::new());
+        let data = ConnectFourTurn { column: 7 }.encode_to_vec();
+        assert!(matches!(
+            lobby.update(1, &data).unwrap_err(),
+            RpcError::TurnDataConversion {
+                source: ProtobufError::InvalidGridCol {
+                    max_expected: 7,
+                    found: 8
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_clocks_start_with_first_turn() {
+        let minute = Duration::from_secs(60);
+        let control = TimeControl::with_increment(minute, Duration::ZERO);
+        let mut lobby = Lobby::new(&[1, 2], Some(control), tic_tac_toe::TicTacToe::new());
+        assert!(lobby.deadline().is_none());
+        assert!(lobby.flag_timeout(Instant::now() + minute * 2).is_none());
+
+        let data = Position { row: 0, col: 0 }.encode_to_vec();
+        lobby.update(1, &data).unwrap();
+        let now = Instant::now();
+        assert_eq!(lobby.remaining_time(now)[0], minute);
+        assert!(lobby.remaining_time(now)[1] <= minute);
+        assert!(lobby.deadline().is_some());
+    }
+
+    #[test]
+    fn test_clocks_of_simultaneous_rounds() {
+        let minute = Duration::from_secs(60);
+        let increment = Duration::from_secs(2);
+        let control = TimeControl::with_increment(minute, increment);
+        let mut lobby = Lobby::new(
+            &[1, 2],
+            Some(control),
+            rock_paper_scissors::RockPaperScissors::new(),
+        );
+        let rock = rock_paper_scissors::Hand::Rock.to_protobuf().unwrap();
+        // the clocks don't run before the first move, so the first player
+        // gets no increment for it, the time spent on the moves is negligible
+        for expected in [
+            [minute, minute + increment],
+            [minute + increment, minute + increment * 2],
+        ] {
+            lobby.update(1, &rock).unwrap();
+            lobby.update(2, &rock).unwrap();
+            let remaining = lobby.remaining_time(Instant::now());
+            for (clock, expected) in remaining.into_iter().zip(expected) {
+                assert!(clock > expected - increment / 2);
+                assert!(clock <= expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contribute() {
+        let mut lobby = Lobby::new(&[1, 2], None, backgammon::Backgammon::new());
+        let pass = backgammon::TurnData::new(vec![]).to_protobuf().unwrap();
+        assert!(matches!(
+            lobby.update(1, &pass).unwrap_err(),
+            RpcError::GameError(GameError::DiceNotRolled)
+        ));
+        assert_eq!(
+            lobby.contribute(2, vec![2]).unwrap(),
+            GameState::Seeding(PlayerSet::first(1))
+        );
+        assert!(matches!(
+            lobby.contribute(2, vec![]).unwrap_err(),
+            RpcError::GameError(GameError::AlreadyContributed { player: 1 })
+        ));
+        assert!(matches!(
+            lobby.contribute(3, vec![]).unwrap_err(),
+            RpcError::ForeignGame
+        ));
+        assert!(matches!(
+            lobby.contribute(1, vec![1]).unwrap(),
+            GameState::Turn(_)
+        ));
+        assert_eq!(lobby.game().dice().len(), 2);
+        assert_eq!(lobby.game_state().dice, lobby.game().dice());
+
+        let seed = lobby.seed_commitment().unwrap();
+        assert_eq!(seed.entropy, vec![vec![1], vec![2]]);
+        assert!(seed.secret.is_empty());
+        lobby.make_action(1, GameAction::Resign).unwrap();
+        assert_eq!(lobby.seed_commitment().unwrap().secret.len(), 32);
+
+        let mut lobby = Lobby::new(&[1, 2], None, tic_tac_toe::TicTacToe::new());
+        assert!(lobby.seed_commitment().is_none());
+        assert!(matches!(
+            lobby.contribute(1, vec![]).unwrap_err(),
+            RpcError::NoChanceEvents
+        ));
+    }
+
+    #[test]
+    fn test_clocks_start_once_seeded() {
+        let minute = Duration::from_secs(60);
+        let control = TimeControl::with_increment(minute, Duration::ZERO);
+        let mut lobby = Lobby::new(&[1, 2], Some(control), backgammon::Backgammon::new());
+        lobby.contribute(1, vec![1]).unwrap();
+        assert!(lobby.deadline().is_none());
+        assert_eq!(lobby.remaining_time(Instant::now()), vec![minute; 2]);
+        assert!(lobby.flag_timeout(Instant::now() + minute * 2).is_none());
+
+        let GameState::Turn(first) = lobby.contribute(2, vec![2]).unwrap() else {
+            panic!("the game should start once seeded");
+        };
+        assert!(lobby.deadline().is_some());
+        assert!(lobby.remaining_time(Instant::now())[first as usize] <= minute);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_tic_tac_toe_update(inputs in inputs(grid_index())) {
+            play::<tic_tac_toe::TicTacToe>(inputs);
+        }
+
+        #[test]
+        fn test_gomoku_update(inputs in inputs(grid_index())) {
+            play::<mnk::Gomoku>(inputs);
+        }
+
+        #[test]
+        fn test_reversi_update(inputs in inputs(grid_index())) {
+            play::<reversi::Reversi>(inputs);
+        }
+
+        #[test]
+        fn test_connect_four_update(inputs in inputs(connect_four_turn())) {
+            play::<connect_four::ConnectFour>(inputs);
+        }
+
+        #[test]
+        fn test_checkers_update(inputs in inputs(checkers_turn())) {
+            play::<checkers::Checkers>(inputs);
+        }
+
+        #[test]
+        fn test_go_update(inputs in inputs(go_turn())) {
+            play::<go::Go9>(inputs.clone());
+            play::<go::Go13>(inputs);
+        }
+
+        #[test]
+        fn test_battleship_update(inputs in inputs(grid_index())) {
+            play::<battleship::Battleship>(inputs);
+        }
+
+        #[test]
+        fn test_rock_paper_scissors_update(
+            inputs in inputs(any::<i32>().prop_map(|hand| {
+                RockPaperScissorsTurn { hand }.encode_to_vec()
+            }))
+        ) {
+            play::<rock_paper_scissors::RockPaperScissors>(inputs);
+        }
+
+        #[test]
+        fn test_backgammon_update(inputs in inputs(backgammon_turn())) {
+            play::<backgammon::Backgammon>(inputs);
+        }
+
+        #[test]
+        fn test_dots_and_boxes_update(inputs in inputs(dots_and_boxes_turn())) {
+            play::<dots_and_boxes::DotsAndBoxes>(inputs);
+        }
+
+        #[test]
+        fn test_chess_update(inputs in inputs(chess_turn())) {
+            play::<chess::Chess>(inputs.clone());
+            play::<chess::Chess960>(inputs);
         }
-        Ok(state)
     }
 }
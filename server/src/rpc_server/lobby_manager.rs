@@ -1,4 +1,5 @@
 use std::future::{Future, IntoFuture};
+use std::time::Instant;
 
 use tokio::select;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
@@ -11,7 +12,7 @@ use super::game_storage::GameStorage;
 use super::lobby::{Connection, MoveEvent, UpdateRequestReader};
 use super::rpc::{GameImpl, RpcInnerResult};
 use super::GameId;
-use crate::core::{Game, GameState};
+use crate::core::{Game, TimeControl};
 use crate::proto;
 use crate::rpc_server::UserId;
 
@@ -22,14 +23,34 @@ pub enum WorkerCommand {
         user: UserId,
         data: Vec<u8>,
     },
+    MakeAction {
+        game: GameId,
+        user: UserId,
+        action: i32,
+    },
+    Contribute {
+        game: GameId,
+        user: UserId,
+        entropy: Vec<u8>,
+    },
     Disconnect {
         game: GameId,
         user: UserId,
     },
+    /// Clocks have been started outside of the worker, the next deadline has to be updated.
+    CheckTimeouts,
 }
 
 pub struct Worker(JoinHandle<()>);
 
+/// Waits until the deadline, never finishes if there is none.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
 impl IntoFuture for Worker {
     type Output = <JoinHandle<()> as Future>::Output;
     type IntoFuture = JoinHandle<()>;
@@ -47,6 +68,10 @@ impl Worker {
     ) -> Self {
         let worker = tokio::spawn(async move {
             loop {
+                let deadline = storage.next_deadline().unwrap_or_else(|err| {
+                    println!("worker: failed to get next deadline: {}", err);
+                    None
+                });
                 select! {
                     biased;
                     _ = ct.cancelled() => {
@@ -74,12 +99,53 @@ impl Worker {
                                     }
                                 }
                             }
+                            WorkerCommand::MakeAction { game, user, action } => {
+                                println!(
+                                    "worker: MakeAction game={}, user={}, action={}",
+                                    game, user, action
+                                );
+                                let result = match proto::GameAction::try_from(action) {
+                                    Ok(action) => storage.make_action(game, user, action),
+                                    Err(_) => Err(RpcError::InvalidGameAction),
+                                };
+                                if let Err(err) = result {
+                                    println!("worker: MakeAction failed: {}", err);
+                                    if let Err(err) = storage.notify_err(game, user, err) {
+                                        println!("worker: failed to notify on error: {}", err);
+                                    }
+                                    if let Err(err) = storage.disconnect(game, user).await {
+                                        println!("worker: failed to disconnect on error: {}", err);
+                                    }
+                                }
+                            }
+                            WorkerCommand::Contribute { game, user, entropy } => {
+                                println!("worker: Contribute game={}, user={}", game, user);
+                                if let Err(err) = storage.contribute(game, user, entropy) {
+                                    println!("worker: Contribute failed: {}", err);
+                                    if let Err(err) = storage.notify_err(game, user, err) {
+                                        println!("worker: failed to notify on error: {}", err);
+                                    }
+                                    if let Err(err) = storage.disconnect(game, user).await {
+                                        println!("worker: failed to disconnect on error: {}", err);
+                                    }
+                                }
+                            }
                             WorkerCommand::Disconnect { game, user } => {
                                 println!("worker: Disconnect game={}, user={}", game, user);
                                 if let Err(err) = storage.disconnect(game, user).await {
                                     println!("worker: Disconnect failed: {}", err);
                                 }
                             }
+                            WorkerCommand::CheckTimeouts => {
+                                if let Err(err) = storage.flag_timeouts(Instant::now()) {
+                                    println!("worker: CheckTimeouts failed: {}", err);
+                                }
+                            }
+                        }
+                    }
+                    _ = sleep_until(deadline) => {
+                        if let Err(err) = storage.flag_timeouts(Instant::now()) {
+                            println!("worker: failed to flag timeouts: {}", err);
                         }
                     }
                 }
@@ -156,8 +222,17 @@ impl<T> LobbyManager<T> {
         let mut reply_receiver = self.create_connection(game, user, stream)?;
         let reply_stream = async_stream::try_stream! {
             while let Some(event) = reply_receiver.recv().await {
-                let MoveEvent { player, data } = event?;
-                yield proto::GameSessionReply { player_position: player, turn_data: data };
+                let MoveEvent { player, data, clocks, state, scores, dice, board, action } = event?;
+                yield proto::GameSessionReply {
+                    player_position: player,
+                    turn_data: data,
+                    clocks_ms: clocks.iter().map(|clock| clock.as_millis() as u64).collect(),
+                    game_state: Some(
+                        proto::GameState::from(state).with_scores(scores).with_dice(dice),
+                    ),
+                    action: action.into(),
+                    board,
+                };
             }
         };
         Ok(Box::pin(reply_stream))
@@ -165,20 +240,43 @@ impl<T> LobbyManager<T> {
 }
 
 impl<T: Game> LobbyManager<T> {
-    pub fn create(&self, id: GameId, players: &[UserId]) -> RpcInnerResult<proto::GameInfo> {
-        self.storage.create(id, players)
+    pub fn create(
+        &self,
+        id: GameId,
+        players: &[UserId],
+        time_control: Option<TimeControl>,
+        start_position: Option<u32>,
+    ) -> RpcInnerResult<proto::GameInfo> {
+        self.storage
+            .create(id, players, time_control, start_position)
     }
 
-    pub fn update(&self, game: GameId, user: UserId, data: &[u8]) -> RpcInnerResult<GameState> {
-        self.storage.update(game, user, data)
+    pub fn update(
+        &self,
+        game: GameId,
+        user: UserId,
+        data: &[u8],
+    ) -> RpcInnerResult<proto::GameState> {
+        let state = self.storage.update(game, user, data)?;
+        self.check_timeouts();
+        Ok(state)
+    }
+
+    /// Makes the worker wait for the clock that has been started last.
+    fn check_timeouts(&self) {
+        if let Some(sender) = self.command_sender() {
+            if let Err(err) = sender.send(WorkerCommand::CheckTimeouts) {
+                println!("failed to send command to worker: {}", err);
+            }
+        }
     }
 
     pub fn delete(&self, id: GameId) -> RpcInnerResult<()> {
         self.storage.delete(id)
     }
 
-    pub fn get_game(&self, id: GameId) -> RpcInnerResult<proto::GameInfo> {
-        self.storage.get(id)
+    pub fn get_game(&self, id: GameId, user: Option<UserId>) -> RpcInnerResult<proto::GameInfo> {
+        self.storage.get(id, user)
     }
 
     pub fn get_player_games(&self, player: UserId) -> RpcInnerResult<Vec<proto::GameInfo>> {
@@ -10,34 +10,79 @@ use super::auth;
 use super::error::RpcError;
 use super::lobby_manager::LobbyManager;
 use super::RpcResult;
-use crate::core::chess::Chess;
+use crate::core::backgammon::Backgammon;
+use crate::core::battleship::Battleship;
+use crate::core::checkers::Checkers;
+use crate::core::chess::{Chess, Chess960};
+use crate::core::connect_four::ConnectFour;
+use crate::core::dots_and_boxes::DotsAndBoxes;
+use crate::core::go::{Go13, Go9};
+use crate::core::mnk::Gomoku;
+use crate::core::reversi::Reversi;
+use crate::core::rock_paper_scissors::RockPaperScissors;
 use crate::core::tic_tac_toe::TicTacToe;
+use crate::core::TimeControl;
 use crate::proto;
 
 pub type GameId = u64;
 
 pub type RpcInnerResult<T> = Result<T, RpcError>;
 
-#[derive(Default)]
-pub struct GameImpl {
-    tic_tac_toe: LobbyManager<TicTacToe>,
-    chess: LobbyManager<Chess>,
-}
+/// Declares the lobby manager of every game type along with the `dispatch!` macro
+/// that forwards a request to the manager of its game type, so a game is wired in one line.
+macro_rules! games {
+    ($d:tt $($game_type:ident => $field:ident: $game:ty),+ $(,)?) => {
+        #[derive(Default)]
+        pub struct GameImpl {
+            $($field: LobbyManager<$game>,)+
+        }
 
-impl GameImpl {
-    pub fn start_workers(
-        &mut self,
-        ct: CancellationToken,
-    ) -> impl Future<Output = Result<(), JoinError>> {
-        let ttt_worker = self.tic_tac_toe.start_worker(ct.clone());
-        let chess_worker = self.chess.start_worker(ct.clone());
-        async move {
-            ttt_worker.await?;
-            chess_worker.await
+        impl GameImpl {
+            pub fn start_workers(
+                &mut self,
+                ct: CancellationToken,
+            ) -> impl Future<Output = Result<(), JoinError>> {
+                let workers = vec![$(self.$field.start_worker(ct.clone())),+];
+                async move {
+                    for worker in workers {
+                        worker.await?;
+                    }
+                    Ok(())
+                }
+            }
         }
-    }
+
+        /// Evaluates the call with `manager` bound to the lobby manager of the game type.
+        macro_rules! dispatch {
+            ($d this:ident, $d game_type:expr, |$d manager:ident| $d call:expr) => {
+                match $d game_type {
+                    $(proto::GameType::$game_type => {
+                        let $d manager = &$d this.$field;
+                        $d call
+                    })+
+                    proto::GameType::Unspecified => Err(RpcError::InvalidGameType),
+                }
+            };
+        }
+    };
 }
 
+games!($
+    TicTacToe => tic_tac_toe: TicTacToe,
+    Chess => chess: Chess,
+    Chess960 => chess960: Chess960,
+    Gomoku => gomoku: Gomoku,
+    ConnectFour => connect_four: ConnectFour,
+    Checkers => checkers: Checkers,
+    Reversi => reversi: Reversi,
+    Go9x9 => go9: Go9,
+    Go13x13 => go13: Go13,
+    Battleship => battleship: Battleship,
+    RockPaperScissors => rock_paper_scissors: RockPaperScissors,
+    Backgammon => backgammon: Backgammon,
+    DotsAndBoxes => dots_and_boxes: DotsAndBoxes,
+);
+
 #[tonic::async_trait]
 impl proto::game_server::Game for GameImpl {
     async fn create_game(
@@ -54,11 +99,19 @@ impl proto::game_server::Game for GameImpl {
             .player_ids
             .first()
             .ok_or(RpcError::RequestDataMissing("player_ids".into()))?;
-        let game_info = match game_type {
-            proto::GameType::TicTacToe => self.tic_tac_toe.create(player1, &request.player_ids)?,
-            proto::GameType::Chess => self.chess.create(player1, &request.player_ids)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        let time_control = request
+            .time_control
+            .map(TimeControl::try_from)
+            .transpose()
+            .map_err(|_| RpcError::InvalidTimeControl)?;
+        let players = &request.player_ids;
+        let start_position = request.start_position;
+        let game_info = dispatch!(self, game_type, |manager| manager.create(
+            player1,
+            players,
+            time_control,
+            start_position
+        ))?;
         Ok(Response::new(proto::CreateGameReply {
             game_info: Some(game_info),
         }))
@@ -76,15 +129,13 @@ impl proto::game_server::Game for GameImpl {
             proto::GameType::try_from(request.game_type).map_err(|_| RpcError::InvalidGameType)?;
         let game = request.game_id;
         let player = request.player_id;
-        let game_state = match game_type {
-            proto::GameType::TicTacToe => {
-                self.tic_tac_toe.update(game, player, &request.turn_data)?
-            }
-            proto::GameType::Chess => self.chess.update(game, player, &request.turn_data)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        let game_state = dispatch!(self, game_type, |manager| manager.update(
+            game,
+            player,
+            &request.turn_data
+        ))?;
         Ok(Response::new(proto::MakeTurnReply {
-            game_state: Some(game_state.into()),
+            game_state: Some(game_state),
         }))
     }
 
@@ -111,14 +162,11 @@ impl proto::game_server::Game for GameImpl {
             proto::GameType::try_from(session.game_type).map_err(|_| RpcError::InvalidGameType)?;
         let game = session.game_id;
         let player = session.player_id;
-        let stream = match game_type {
-            proto::GameType::TicTacToe => {
-                self.tic_tac_toe
-                    .start_game_session(game, player, input_stream)?
-            }
-            proto::GameType::Chess => self.chess.start_game_session(game, player, input_stream)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        let stream = dispatch!(self, game_type, |manager| manager.start_game_session(
+            game,
+            player,
+            input_stream
+        ))?;
         Ok(Response::new(stream))
     }
 
@@ -133,11 +181,7 @@ impl proto::game_server::Game for GameImpl {
             proto::GameType::try_from(request.game_type).map_err(|_| RpcError::InvalidGameType)?;
         // For now, it's a creator id
         let game = request.game_id;
-        match game_type {
-            proto::GameType::TicTacToe => self.tic_tac_toe.delete(game)?,
-            proto::GameType::Chess => self.chess.delete(game)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        dispatch!(self, game_type, |manager| manager.delete(game))?;
         Ok(Response::new(proto::DeleteGameReply {}))
     }
 
@@ -147,15 +191,17 @@ impl proto::game_server::Game for GameImpl {
     ) -> RpcResult<proto::GetGameReply> {
         println!("Got request {:?}", request);
 
-        let request = request.into_inner();
+        let (metadata, _, request) = request.into_parts();
+        // the board is shown as the player sees it only to the player itself
+        if let Some(player) = request.player_id {
+            auth::check_credentials(&metadata, auth::Check::Single(player))?;
+        }
+
         let game_type =
             proto::GameType::try_from(request.game_type).map_err(|_| RpcError::InvalidGameType)?;
         let game = request.game_id;
-        let info = match game_type {
-            proto::GameType::TicTacToe => self.tic_tac_toe.get_game(game)?,
-            proto::GameType::Chess => self.chess.get_game(game)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        let user = request.player_id;
+        let info = dispatch!(self, game_type, |manager| manager.get_game(game, user))?;
         Ok(Response::new(proto::GetGameReply {
             game_info: Some(info),
         }))
@@ -171,11 +217,7 @@ impl proto::game_server::Game for GameImpl {
         let game_type =
             proto::GameType::try_from(request.game_type).map_err(|_| RpcError::InvalidGameType)?;
         let player = request.player_id;
-        let games = match game_type {
-            proto::GameType::TicTacToe => self.tic_tac_toe.get_player_games(player)?,
-            proto::GameType::Chess => self.chess.get_player_games(player)?,
-            proto::GameType::Unspecified => return Err(RpcError::InvalidGameType.into()),
-        };
+        let games = dispatch!(self, game_type, |manager| manager.get_player_games(player))?;
         Ok(Response::new(proto::GetPlayerGamesReply { games }))
     }
 }
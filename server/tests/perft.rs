@@ -0,0 +1,93 @@
+extern crate server;
+
+use server::core::chess::{Chess, INITIAL_POSITION};
+
+/// Positions with published perft results, see https://www.chessprogramming.org/Perft_Results
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+/// Chess960 positions with castling rights in Shredder-FEN.
+const CHESS960_1: &str = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+const CHESS960_2: &str = "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let chess = Chess::from_fen(fen).unwrap();
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(
+            chess.perft(depth + 1),
+            nodes,
+            "{} at depth {}",
+            fen,
+            depth + 1
+        );
+    }
+}
+
+#[test]
+fn test_perft_initial_position() {
+    assert_perft(INITIAL_POSITION, &[20, 400, 8902]);
+}
+
+#[test]
+fn test_perft_kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039]);
+}
+
+#[test]
+fn test_perft_position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812]);
+}
+
+#[test]
+fn test_perft_position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+}
+
+#[test]
+fn test_perft_position_5() {
+    assert_perft(POSITION_5, &[44, 1486]);
+}
+
+#[test]
+fn test_perft_position_6() {
+    assert_perft(POSITION_6, &[46, 2079]);
+}
+
+#[test]
+fn test_perft_chess960() {
+    assert_perft(CHESS960_1, &[21, 528, 12189]);
+    assert_perft(CHESS960_2, &[22, 593, 13440]);
+}
+
+#[test]
+fn test_perft_ignores_draw_adjudication() {
+    // bare kings are a draw by insufficient material, their moves are counted anyway
+    assert_perft("4k3/8/8/8/8/8/8/4K3 w - - 0 1", &[5, 25]);
+    // fifty-move rule is reached by the first move
+    let nodes = Chess::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 80")
+        .unwrap()
+        .perft(3);
+    assert_eq!(
+        Chess::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80")
+            .unwrap()
+            .perft(3),
+        nodes
+    );
+}
+
+#[test]
+fn test_perft_divide() {
+    let chess = Chess::from_fen(INITIAL_POSITION).unwrap();
+    let divide = chess.perft_divide(2);
+    assert_eq!(divide.len(), 20);
+    assert!(divide.iter().all(|&(_, nodes)| nodes == 20));
+    assert_eq!(
+        divide
+            .iter()
+            .map(|(data, _)| data.to_string())
+            .find(|m| m == "g1f3"),
+        Some("g1f3".to_string())
+    );
+}
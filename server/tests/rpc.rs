@@ -11,7 +11,12 @@ use tonic::metadata::MetadataValue;
 use tonic::transport::{server::TcpIncoming, Channel, Server};
 use tonic::{Code, Request};
 
-use server::core::{BoardCell, GridIndex, ToProtobuf};
+use sha2::{Digest, Sha256};
+
+use server::core::rock_paper_scissors::Hand;
+use server::core::{
+    backgammon, battleship, dots_and_boxes, go, BoardCell, FromProtobuf, GridIndex, Rng, ToProtobuf,
+};
 use server::proto::game_client::GameClient;
 use server::proto::game_server::GameServer;
 use server::proto::*;
@@ -49,6 +54,16 @@ where
     }
 }
 
+fn create_channel_request_stream(
+    mut receiver: UnboundedReceiver<GameSessionRequest>,
+) -> impl Stream<Item = GameSessionRequest> {
+    async_stream::stream! {
+        while let Some(request) = receiver.recv().await {
+            yield request;
+        }
+    }
+}
+
 async fn run_server(addr: &str) -> (JoinHandle<()>, CancellationToken) {
     let ct = CancellationToken::new();
     let ct_cloned = ct.clone();
@@ -250,6 +265,8 @@ async fn game_session_ttt_success() {
         Some(GameState {
             next_player_id: None,
             winner: Some(0),
+            termination: TerminationReason::LineCompleted.into(),
+            ..Default::default()
         })
     );
     itertools::assert_equal(game_info.players, vec![1, 2]);
@@ -371,6 +388,8 @@ async fn game_session_ttt_session_retry_success() {
         Some(GameState {
             next_player_id: None,
             winner: Some(0),
+            termination: TerminationReason::LineCompleted.into(),
+            ..Default::default()
         })
     );
     itertools::assert_equal(game_info.players, vec![1, 2]);
@@ -434,6 +453,7 @@ async fn game_session_turn_notification_on_single_request() {
         Some(GameState {
             next_player_id: Some(1),
             winner: None,
+            ..Default::default()
         })
     );
     itertools::assert_equal(game_info.players, vec![1, 2]);
@@ -495,3 +515,635 @@ async fn get_player_games() {
     ct.cancel();
     server_thread.await.unwrap();
 }
+
+#[serial_test::serial]
+#[tokio::test]
+async fn game_session_timeout() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+
+    // base time must be positive
+    let mut request = Request::new(
+        CreateGameRequest::new(1, vec![1, 2]).with_time_control(TimeControl::clock(0, 0, 0)),
+    );
+    mock_auth(&mut request, 1);
+    let status = client.create_game(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // oversized time controls are rejected
+    for time_control in [
+        TimeControl::clock(500, u64::MAX, 0),
+        TimeControl::clock(u64::MAX, 0, 0),
+        TimeControl::correspondence(u32::MAX),
+    ] {
+        let mut request =
+            Request::new(CreateGameRequest::new(1, vec![1, 2]).with_time_control(time_control));
+        mock_auth(&mut request, 1);
+        let status = client.create_game(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    let mut request = Request::new(
+        CreateGameRequest::new(1, vec![1, 2]).with_time_control(TimeControl::clock(500, 0, 0)),
+    );
+    mock_auth(&mut request, 1);
+    client.create_game(request).await.unwrap();
+
+    let (p1_ready_sender, p1_ready_receiver) = unbounded_channel();
+    let only_init =
+        create_game_session_request_stream(1, 1, 1, Vec::<GridIndex>::new(), p1_ready_receiver);
+    let mut request = Request::new(only_init);
+    mock_auth(&mut request, 1);
+    let mut stream = client.game_session(request).await.unwrap().into_inner();
+
+    let mut request = Request::new(MakeTurnRequest::new(
+        1,
+        1,
+        1,
+        GridIndex::new(1, 1).to_protobuf().unwrap(),
+    ));
+    mock_auth(&mut request, 1);
+    client.make_turn(request).await.unwrap();
+    let reply = stream.next().await.unwrap().unwrap();
+    assert_eq!(reply.player_position, 0);
+    assert_eq!(reply.clocks_ms.len(), 2);
+    // clocks start with the first turn, so it's made for free
+    assert_eq!(reply.clocks_ms[0], 500);
+    assert_eq!(reply.clocks_ms[1], 500);
+    assert_eq!(reply.game_state.unwrap().next_player_id, Some(1));
+
+    // second player doesn't make a turn and loses on time without any request
+    let reply = stream.next().await.unwrap().unwrap();
+    assert_eq!(reply.player_position, 1);
+    assert!(reply.turn_data.is_empty());
+    assert_eq!(reply.clocks_ms[1], 0);
+    let timeout = GameState {
+        next_player_id: None,
+        winner: Some(0),
+        termination: TerminationReason::Timeout.into(),
+        ..Default::default()
+    };
+    assert_eq!(reply.game_state, Some(timeout.clone()));
+    assert!(stream.next().await.is_none());
+    drop(p1_ready_sender);
+
+    let mut request = Request::new(MakeTurnRequest::new(
+        1,
+        1,
+        2,
+        GridIndex::new(0, 0).to_protobuf().unwrap(),
+    ));
+    mock_auth(&mut request, 2);
+    assert!(client.make_turn(request).await.is_err());
+
+    let request = Request::new(GetGameRequest::new(1, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.game_state, Some(timeout));
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn game_session_draw_agreement() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    create_tic_tac_toe_game(&mut client, &[1, 2]).await;
+
+    let (p1_sender, p1_receiver) = unbounded_channel();
+    let (p2_sender, p2_receiver) = unbounded_channel();
+    p1_sender.send(GameSessionRequest::init(1, 1, 1)).unwrap();
+    p2_sender.send(GameSessionRequest::init(1, 1, 2)).unwrap();
+    let mut request = Request::new(create_channel_request_stream(p1_receiver));
+    mock_auth(&mut request, 1);
+    let mut p1_stream = client.game_session(request).await.unwrap().into_inner();
+    let mut request = Request::new(create_channel_request_stream(p2_receiver));
+    mock_auth(&mut request, 2);
+    let mut p2_stream = client.game_session(request).await.unwrap().into_inner();
+
+    let actions = [
+        (&p1_sender, 0, GameAction::OfferDraw),
+        (&p2_sender, 1, GameAction::DeclineDraw),
+        (&p2_sender, 1, GameAction::OfferDraw),
+        (&p1_sender, 0, GameAction::AcceptDraw),
+    ];
+    for (sender, player, action) in actions {
+        sender.send(GameSessionRequest::action(action)).unwrap();
+        for stream in [&mut p1_stream, &mut p2_stream] {
+            let reply = stream.next().await.unwrap().unwrap();
+            assert_eq!(reply.player_position, player);
+            assert_eq!(reply.action(), action);
+            assert!(reply.turn_data.is_empty());
+        }
+    }
+    // both sessions are finished once the draw is accepted
+    assert!(p1_stream.next().await.is_none());
+    assert!(p2_stream.next().await.is_none());
+
+    let request = Request::new(GetGameRequest::new(1, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(
+        game_info.game_state,
+        Some(GameState {
+            next_player_id: None,
+            winner: None,
+            termination: TerminationReason::Agreement.into(),
+            ..Default::default()
+        })
+    );
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn game_session_resign() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    create_tic_tac_toe_game(&mut client, &[1, 2]).await;
+
+    // there is no draw offer to accept
+    let mut request = Request::new(tokio_stream::iter([
+        GameSessionRequest::init(1, 1, 1),
+        GameSessionRequest::action(GameAction::AcceptDraw),
+    ]));
+    mock_auth(&mut request, 1);
+    let mut stream = client.game_session(request).await.unwrap().into_inner();
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_err().code(),
+        Code::FailedPrecondition
+    );
+    assert!(stream.next().await.is_none());
+
+    let (sender, receiver) = unbounded_channel();
+    sender.send(GameSessionRequest::init(1, 1, 1)).unwrap();
+    let mut request = Request::new(create_channel_request_stream(receiver));
+    mock_auth(&mut request, 1);
+    let mut stream = client.game_session(request).await.unwrap().into_inner();
+    sender
+        .send(GameSessionRequest::action(GameAction::Resign))
+        .unwrap();
+    let reply = stream.next().await.unwrap().unwrap();
+    let resignation = GameState {
+        next_player_id: None,
+        winner: Some(1),
+        termination: TerminationReason::Resignation.into(),
+        ..Default::default()
+    };
+    assert_eq!(reply.action(), GameAction::Resign);
+    assert_eq!(reply.game_state, Some(resignation.clone()));
+    assert!(stream.next().await.is_none());
+
+    let request = Request::new(GetGameRequest::new(1, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.game_state, Some(resignation));
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn make_turn_go_scores() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let game_type = GameType::Go9x9 as i32;
+    let mut request = Request::new(CreateGameRequest::new(game_type, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    client.create_game(request).await.unwrap();
+
+    let turns = [
+        (1, go::TurnData::Place(GridIndex::new(4, 4))),
+        (2, go::TurnData::Pass),
+        (1, go::TurnData::Pass),
+    ];
+    let mut game_state = None;
+    for (player, turn) in turns {
+        let data = turn.to_protobuf().unwrap();
+        let mut request = Request::new(MakeTurnRequest::new(game_type, 1, player, data));
+        mock_auth(&mut request, player);
+        game_state = client
+            .make_turn(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .game_state;
+    }
+    // the only stone on the board owns every empty cell
+    let score = GameState {
+        next_player_id: None,
+        winner: Some(0),
+        termination: TerminationReason::Score.into(),
+        scores: vec![81.0, go::KOMI],
+        ..Default::default()
+    };
+    assert_eq!(game_state, Some(score.clone()));
+
+    let request = Request::new(GetGameRequest::new(game_type, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.game_state, Some(score));
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn make_turn_dots_and_boxes_extra_turn() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let game_type = GameType::DotsAndBoxes as i32;
+    let mut request = Request::new(CreateGameRequest::new(game_type, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    client.create_game(request).await.unwrap();
+
+    // the second player draws the last side of the top left box
+    let turns = [
+        (1, (0, 0), (0, 1), 1),
+        (2, (0, 0), (1, 0), 0),
+        (1, (1, 0), (1, 1), 1),
+        (2, (0, 1), (1, 1), 1),
+        (2, (2, 0), (2, 1), 0),
+    ];
+    for (player, first, second, next_player) in turns {
+        let edge = dots_and_boxes::Edge::new(first.into(), second.into());
+        let data = edge.to_protobuf().unwrap();
+        let mut request = Request::new(MakeTurnRequest::new(game_type, 1, player, data));
+        mock_auth(&mut request, player);
+        let game_state = client
+            .make_turn(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .game_state
+            .unwrap();
+        assert_eq!(game_state.next_player_id, Some(next_player));
+    }
+
+    let request = Request::new(GetGameRequest::new(game_type, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    let owner = BoardCell::<u32>::from_protobuf(&game_info.board[12]).unwrap();
+    assert_eq!(owner, BoardCell(Some(1)));
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+/// Counts ships in the seas of both players, the first player owns the left half of every row.
+fn battleship_ships(board: &[Vec<u8>]) -> (usize, usize) {
+    let mut ships = (0, 0);
+    for (i, bytes) in board.iter().enumerate() {
+        let cell = BoardCell::<battleship::Square>::from_protobuf(bytes).unwrap();
+        if cell == BoardCell(Some(battleship::Square::Ship)) {
+            if i % 20 < 10 {
+                ships.0 += 1;
+            } else {
+                ships.1 += 1;
+            }
+        }
+    }
+    ships
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn battleship_board_views() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let game_type = GameType::Battleship as i32;
+    let mut request = Request::new(CreateGameRequest::new(game_type, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    client.create_game(request).await.unwrap();
+    let fleet = battleship::FLEET.iter().sum::<usize>();
+
+    // players see only their own ships, spectators see none
+    for (player, ships) in [(Some(1), (fleet, 0)), (Some(2), (0, fleet)), (None, (0, 0))] {
+        let mut request = GetGameRequest::new(game_type, 1);
+        if let Some(player) = player {
+            request = request.with_player(player);
+        }
+        let mut request = Request::new(request);
+        if let Some(player) = player {
+            mock_auth(&mut request, player);
+        }
+        let game_info = client
+            .get_game(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .game_info
+            .unwrap();
+        assert_eq!(battleship_ships(&game_info.board), ships);
+    }
+    let mut request = Request::new(GetGameRequest::new(game_type, 1).with_player(2));
+    mock_auth(&mut request, 1);
+    assert_eq!(
+        client.get_game(request).await.unwrap_err().code(),
+        Code::PermissionDenied
+    );
+
+    let (sender, receiver) = unbounded_channel();
+    sender
+        .send(GameSessionRequest::init(game_type, 1, 2))
+        .unwrap();
+    let mut request = Request::new(create_channel_request_stream(receiver));
+    mock_auth(&mut request, 2);
+    let mut stream = client.game_session(request).await.unwrap().into_inner();
+
+    let data = GridIndex::new(0, 10).to_protobuf().unwrap();
+    let mut request = Request::new(MakeTurnRequest::new(game_type, 1, 1, data.clone()));
+    mock_auth(&mut request, 1);
+    client.make_turn(request).await.unwrap();
+
+    // the opponent gets the shot along with the board as it sees it
+    let reply = stream.next().await.unwrap().unwrap();
+    assert_eq!(reply.turn_data, data);
+    assert_eq!(reply.board.len(), 200);
+    let shot = BoardCell::<battleship::Square>::from_protobuf(&reply.board[10]).unwrap();
+    let hits = usize::from(shot == BoardCell(Some(battleship::Square::Hit)));
+    assert_eq!(battleship_ships(&reply.board), (0, fleet - hits));
+    drop(sender);
+    assert!(stream.next().await.is_none());
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn game_session_simultaneous_round() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let game_type = GameType::RockPaperScissors as i32;
+    let mut request = Request::new(CreateGameRequest::new(game_type, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    let game_info = client
+        .create_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    let everyone = GameState {
+        waiting_player_ids: vec![0, 1],
+        ..Default::default()
+    };
+    assert_eq!(game_info.game_state, Some(everyone.clone()));
+
+    let (sender, receiver) = unbounded_channel();
+    sender
+        .send(GameSessionRequest::init(game_type, 1, 2))
+        .unwrap();
+    let mut request = Request::new(create_channel_request_stream(receiver));
+    mock_auth(&mut request, 2);
+    let mut stream = client.game_session(request).await.unwrap().into_inner();
+
+    let rock = Hand::Rock.to_protobuf().unwrap();
+    let mut request = Request::new(MakeTurnRequest::new(game_type, 1, 1, rock.clone()));
+    mock_auth(&mut request, 1);
+    client.make_turn(request).await.unwrap();
+
+    // the hand is kept secret until the opponent shows its own
+    let reply = stream.next().await.unwrap().unwrap();
+    assert_eq!(reply.player_position, 0);
+    assert!(reply.turn_data.is_empty());
+    let second = GameState {
+        waiting_player_ids: vec![1],
+        ..Default::default()
+    };
+    assert_eq!(reply.game_state, Some(second));
+
+    let paper = Hand::Paper.to_protobuf().unwrap();
+    sender
+        .send(GameSessionRequest::turn_data(paper.clone()))
+        .unwrap();
+    for (player, data) in [(0, rock), (1, paper)] {
+        let reply = stream.next().await.unwrap().unwrap();
+        assert_eq!(reply.player_position, player);
+        assert_eq!(reply.turn_data, data);
+        assert_eq!(reply.game_state, Some(everyone.clone()));
+    }
+    drop(sender);
+    assert!(stream.next().await.is_none());
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn game_session_backgammon_dice() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let game_type = GameType::Backgammon as i32;
+    let mut request = Request::new(CreateGameRequest::new(game_type, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    let game_info = client
+        .create_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    let seed = game_info.seed.unwrap();
+    assert_eq!(seed.commitment.len(), 32);
+    assert_eq!(seed.entropy, vec![Vec::<u8>::new(); 2]);
+    assert!(seed.secret.is_empty());
+    let seeding = GameState {
+        waiting_player_ids: vec![0, 1],
+        seeding: true,
+        ..Default::default()
+    };
+    assert_eq!(game_info.game_state, Some(seeding));
+
+    // the dice aren't rolled until both players have contributed
+    let pass = backgammon::TurnData::new(vec![]).to_protobuf().unwrap();
+    let mut request = Request::new(MakeTurnRequest::new(game_type, 1, 1, pass));
+    mock_auth(&mut request, 1);
+    client.make_turn(request).await.unwrap_err();
+
+    // every contribution is announced to the players connected so far
+    let mut sessions = vec![];
+    let mut opening = None;
+    for (player, entropy) in [(1, &b"first"[..]), (2, b"second")] {
+        let (sender, receiver) = unbounded_channel();
+        sender
+            .send(GameSessionRequest::init(game_type, 1, player))
+            .unwrap();
+        let mut request = Request::new(create_channel_request_stream(receiver));
+        mock_auth(&mut request, player);
+        let stream = client.game_session(request).await.unwrap().into_inner();
+        sender
+            .send(GameSessionRequest::entropy(entropy.to_vec()))
+            .unwrap();
+        sessions.push((sender, stream));
+        for (_, stream) in sessions.iter_mut() {
+            let reply = stream.next().await.unwrap().unwrap();
+            assert_eq!(u64::from(reply.player_position), player - 1);
+            opening = reply.game_state;
+        }
+    }
+    let opening = opening.unwrap();
+    assert!(opening.waiting_player_ids.is_empty());
+    assert_eq!(opening.dice.len(), 2);
+
+    sessions[0]
+        .0
+        .send(GameSessionRequest::action(GameAction::Resign))
+        .unwrap();
+    for (sender, mut stream) in sessions {
+        let reply = stream.next().await.unwrap().unwrap();
+        assert_eq!(reply.action(), GameAction::Resign);
+        drop(sender);
+        assert!(stream.next().await.is_none());
+    }
+
+    // the revealed secret lets the players recompute the dice
+    let request = Request::new(GetGameRequest::new(game_type, 1));
+    let seed = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap()
+        .seed
+        .unwrap();
+    assert_eq!(Sha256::digest(&seed.secret).to_vec(), seed.commitment);
+    assert_eq!(seed.entropy, vec![b"first".to_vec(), b"second".to_vec()]);
+    let mut hasher = Sha256::new().chain_update(&seed.secret);
+    for entropy in seed.entropy.iter() {
+        hasher.update(Sha256::digest(entropy));
+    }
+    let mut rng = Rng::new(hasher.finalize().into());
+    let dice = loop {
+        let dice = vec![rng.roll(6), rng.roll(6)];
+        if dice[0] != dice[1] {
+            break dice;
+        }
+    };
+    assert_eq!(opening.dice, dice);
+    let first = if dice[0] > dice[1] { 0 } else { 1 };
+    assert_eq!(opening.next_player_id, Some(first));
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
+
+#[serial_test::serial]
+#[tokio::test]
+async fn create_game_start_position() {
+    let addr = "127.0.0.1:50051";
+    let (server_thread, ct) = run_server(addr).await;
+    let mut client = GameClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+
+    // there are only 960 starting positions
+    let game_type = GameType::Chess960 as i32;
+    let mut request =
+        Request::new(CreateGameRequest::new(game_type, vec![1, 2]).with_start_position(960));
+    mock_auth(&mut request, 1);
+    let status = client.create_game(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // standard chess has a single starting position
+    let mut request = Request::new(
+        CreateGameRequest::new(GameType::Chess as i32, vec![1, 2]).with_start_position(518),
+    );
+    mock_auth(&mut request, 1);
+    let status = client.create_game(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let mut request =
+        Request::new(CreateGameRequest::new(game_type, vec![1, 2]).with_start_position(518));
+    mock_auth(&mut request, 1);
+    let game_info = client
+        .create_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.start_position, Some(518));
+
+    let request = Request::new(GetGameRequest::new(game_type, 1));
+    let game_info = client
+        .get_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.start_position, Some(518));
+
+    let mut request = Request::new(CreateGameRequest::new(GameType::Chess as i32, vec![1, 2]));
+    mock_auth(&mut request, 1);
+    let game_info = client
+        .create_game(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .game_info
+        .unwrap();
+    assert_eq!(game_info.start_position, None);
+
+    ct.cancel();
+    server_thread.await.unwrap();
+}
@@ -50,15 +50,10 @@ fn state_to_index(state: &<TicTacToe as Game>::Board) -> usize {
 }
 
 // TODO: try SmallVec here
-fn get_valid_actions(board: &<TicTacToe as Game>::Board) -> Vec<Action> {
-    board
-        .all_indexed()
-        .filter_map(|(index, cell)| {
-            if cell.is_none() {
-                return Some((index.row(), index.col()));
-            }
-            None
-        })
+fn get_valid_actions(game: &TicTacToe) -> Vec<Action> {
+    game.legal_actions()
+        .into_iter()
+        .map(|index| (index.row(), index.col()))
         .collect()
 }
 
@@ -213,13 +208,13 @@ impl Agent {
         Ok(Self { q_table })
     }
 
-    pub fn get_best_action(&self, board: &<TicTacToe as Game>::Board) -> Option<Action> {
-        let valid_actions = get_valid_actions(board);
+    pub fn get_best_action(&self, game: &TicTacToe) -> Option<Action> {
+        let valid_actions = get_valid_actions(game);
         if valid_actions.is_empty() {
             return None;
         }
 
-        let q_values = self.q_table.get_values(state_to_index(board));
+        let q_values = self.q_table.get_values(state_to_index(game.board()));
         let best_actions = get_best_actions(&valid_actions, q_values);
         if best_actions.is_empty() {
             None
@@ -366,7 +361,7 @@ impl<R: Rng> Model<R> {
 
     /// true - greedy, false - exploration
     fn choose_epsilon_greedy_action(&mut self) -> (Action, bool) {
-        let valid_actions = get_valid_actions(self.env.board());
+        let valid_actions = get_valid_actions(&self.env);
         if self.rng.sample(Uniform::from(0.0..1.0)) < self.exploration_level {
             (
                 valid_actions[self.rng.sample(Uniform::from(0..valid_actions.len()))],
@@ -532,7 +527,7 @@ mod test {
         let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
         let mut model = Model::new(rng, 1.0, 1.0);
         // starting actions
-        let valid_actions = get_valid_actions(model.env.board());
+        let valid_actions = get_valid_actions(&model.env);
 
         // uninitialized state -> choose randomly from all actions
         assert_eq!(model.choose_best_action(&valid_actions), (2, 0));